@@ -0,0 +1,25 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_mod_or_admin};
+use lemmy_db_schema::source::{comment::Comment, comment_revision::CommentRevision, post::Post};
+use lemmy_db_views_comment::api::{GetCommentHistory, GetCommentHistoryResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_comment_history(
+  Query(data): Query<GetCommentHistory>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetCommentHistoryResponse>> {
+  let comment = Comment::read(&mut context.pool(), data.comment_id).await?;
+
+  // Only the creator, mods and admins can see previous versions
+  if comment.creator_id != local_user_view.person.id {
+    let post = Post::read(&mut context.pool(), comment.post_id).await?;
+    is_mod_or_admin(&mut context.pool(), &local_user_view, post.community_id).await?;
+  }
+
+  let revisions = CommentRevision::list(&mut context.pool(), comment.id).await?;
+  Ok(Json(GetCommentHistoryResponse { revisions }))
+}
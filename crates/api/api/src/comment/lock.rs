@@ -3,12 +3,14 @@ use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_comment_response,
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
 };
 use lemmy_db_schema::source::{
   comment::Comment,
+  mod_template::ModTemplate,
   modlog::{Modlog, ModlogInsertForm},
 };
 use lemmy_db_views_comment::{
@@ -38,6 +40,14 @@ pub async fn lock_comment(
   )
   .await?;
 
+  let mod_template = ModTemplate::read_for_action(
+    &mut context.pool(),
+    data.mod_template_id,
+    orig_comment.community.id,
+  )
+  .await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
   let comments = Comment::update_locked_for_comment_and_children(
     &mut context.pool(),
     &orig_comment.comment.path,
@@ -46,22 +56,35 @@ pub async fn lock_comment(
   .await?;
   let comment = comments.first().ok_or(LemmyErrorType::NotFound)?;
 
-  let form = ModlogInsertForm::mod_lock_comment(
+  let mut form = ModlogInsertForm::mod_lock_comment(
     local_user_view.person.id,
     comment,
     orig_comment.community.id,
     locked,
-    &data.reason,
+    &reason,
   );
+  form.mod_template_id = data.mod_template_id;
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(action.clone(), &context);
 
+  ModActionReply {
+    post: &orig_comment.post,
+    comment: Some(comment),
+    community: &orig_comment.community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
     SendActivityData::LockComment(
       comment.clone(),
       local_user_view.person.clone(),
       data.locked,
-      data.reason.clone(),
+      reason,
     ),
     &context,
   )?;
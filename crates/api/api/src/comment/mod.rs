@@ -1,4 +1,5 @@
 pub mod distinguish;
+pub mod history;
 pub mod like;
 pub mod list_comment_likes;
 pub mod lock;
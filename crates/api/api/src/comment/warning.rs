@@ -3,11 +3,15 @@ use actix_web::web::Json;
 use either::Either;
 use lemmy_api_utils::{
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_comment_deleted_or_removed, check_community_mod_action},
 };
-use lemmy_db_schema::source::modlog::{Modlog, ModlogInsertForm};
+use lemmy_db_schema::source::{
+  mod_template::ModTemplate,
+  modlog::{Modlog, ModlogInsertForm},
+};
 use lemmy_db_views_comment::{
   CommentView,
   api::{CommentResponse, CreateCommentWarning},
@@ -38,21 +42,42 @@ pub async fn create_comment_warning(
   // Don't allow creating warnings for removed / deleted comments
   check_comment_deleted_or_removed(&orig_comment.comment)?;
 
-  let form = ModlogInsertForm::mod_create_comment_warning(
+  let mod_template = ModTemplate::read_for_action(
+    &mut context.pool(),
+    data.mod_template_id,
+    orig_comment.community.id,
+  )
+  .await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
+  let mut form = ModlogInsertForm::mod_create_comment_warning(
     local_user_view.person.id,
     &orig_comment.comment,
     orig_comment.community.id,
-    &data.reason,
+    &reason,
   );
+  form.mod_template_id = data.mod_template_id;
 
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
 
   notify_mod_action(action, &context);
 
+  ModActionReply {
+    post: &orig_comment.post,
+    comment: Some(&orig_comment.comment),
+    community: &orig_comment.community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
     SendActivityData::Warning(
       Box::new(Either::Right(orig_comment.clone())),
-      data.reason,
+      reason,
       local_user_view.person,
     ),
     &context,
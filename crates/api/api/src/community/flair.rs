@@ -0,0 +1,205 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    check_community_mod_action,
+    check_community_user_action,
+    check_private_instance,
+    slur_regex,
+  },
+};
+use lemmy_db_schema::source::{
+  community::{Community, CommunityActions},
+  community_flair::{CommunityFlair, CommunityFlairInsertForm, CommunityFlairUpdateForm},
+  custom_emoji::CustomEmoji,
+};
+use lemmy_db_views_community::api::{
+  AssignCommunityFlair,
+  CreateCommunityFlair,
+  DeleteCommunityFlair,
+  EditCommunityFlair,
+  ListCommunityFlairs,
+  ListCommunityFlairsResponse,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{SiteView, api::SuccessResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{check_api_elements_count, is_valid_flair_text},
+  },
+};
+use rand::{RngExt, distr::Alphanumeric};
+use url::Url;
+
+pub async fn create_community_flair(
+  Json(data): Json<CreateCommunityFlair>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityFlair>> {
+  is_valid_flair_text(&data.text)?;
+  check_slurs(&data.text, &slur_regex(&context).await?)?;
+
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods can create flairs
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let existing = CommunityFlair::read_for_community(&mut context.pool(), community.id).await?;
+  check_api_elements_count(existing.len())?;
+  if let Some(custom_emoji_id) = data.custom_emoji_id {
+    CustomEmoji::read(&mut context.pool(), custom_emoji_id).await?;
+  }
+
+  // The flair text may change, so use a random string in the id
+  let random: String = rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(16)
+    .map(char::from)
+    .collect();
+  let ap_id = Url::parse(&format!("{}/flair/{random}", community.ap_id))?;
+
+  let flair_form = CommunityFlairInsertForm {
+    ap_id: ap_id.into(),
+    community_id: community.id,
+    text: data.text,
+    color: data.color,
+    custom_emoji_id: data.custom_emoji_id,
+    self_assignable: data.self_assignable,
+    deleted: Some(false),
+  };
+
+  let flair = CommunityFlair::create(&mut context.pool(), &flair_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(flair))
+}
+
+pub async fn edit_community_flair(
+  Json(data): Json<EditCommunityFlair>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityFlair>> {
+  let flair = CommunityFlair::read(&mut context.pool(), data.flair_id).await?;
+  let community = Community::read(&mut context.pool(), flair.community_id).await?;
+
+  // Verify that only mods can update flairs
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  if let Some(text) = &data.text {
+    is_valid_flair_text(text)?;
+    check_slurs(text, &slur_regex(&context).await?)?;
+  }
+  if let Some(custom_emoji_id) = data.custom_emoji_id {
+    CustomEmoji::read(&mut context.pool(), custom_emoji_id).await?;
+  }
+  let custom_emoji_id = if data.remove_custom_emoji.unwrap_or_default() {
+    Some(None)
+  } else {
+    data.custom_emoji_id.map(Some)
+  };
+
+  let flair_form = CommunityFlairUpdateForm {
+    text: data.text,
+    color: data.color,
+    custom_emoji_id,
+    self_assignable: data.self_assignable,
+    updated_at: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+
+  let flair = CommunityFlair::update(&mut context.pool(), data.flair_id, &flair_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(flair))
+}
+
+pub async fn delete_community_flair(
+  Json(data): Json<DeleteCommunityFlair>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityFlair>> {
+  let flair = CommunityFlair::read(&mut context.pool(), data.flair_id).await?;
+  let community = Community::read(&mut context.pool(), flair.community_id).await?;
+
+  // Verify that only mods can delete flairs
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  // Soft delete the flair, this also hides it from users who have it assigned
+  let flair_form = CommunityFlairUpdateForm {
+    updated_at: Some(Some(Utc::now())),
+    deleted: Some(data.delete),
+    ..Default::default()
+  };
+
+  let flair = CommunityFlair::update(&mut context.pool(), data.flair_id, &flair_form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(flair))
+}
+
+pub async fn list_community_flairs(
+  Query(data): Query<ListCommunityFlairs>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListCommunityFlairsResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let flairs = CommunityFlair::read_for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListCommunityFlairsResponse { flairs }))
+}
+
+/// Users can assign self assignable flairs to themselves. Mods can assign any flair to any user.
+/// The flair federates with the next post or comment of the user.
+pub async fn assign_community_flair(
+  Json(data): Json<AssignCommunityFlair>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  let my_person_id = local_user_view.person.id;
+  let person_id = data.person_id.unwrap_or(my_person_id);
+
+  let is_mod =
+    check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await;
+  let is_mod = if person_id != my_person_id {
+    is_mod?;
+    true
+  } else {
+    check_community_user_action(&local_user_view, &community, &mut context.pool()).await?;
+    is_mod.is_ok()
+  };
+
+  if let Some(flair_id) = data.flair_id {
+    let flair = CommunityFlair::read(&mut context.pool(), flair_id).await?;
+    if flair.community_id != community.id || flair.deleted {
+      return Err(LemmyErrorType::NotFound.into());
+    }
+    if !flair.self_assignable && !is_mod {
+      return Err(LemmyErrorType::FlairNotSelfAssignable.into());
+    }
+  }
+
+  CommunityActions::set_flair(&mut context.pool(), person_id, community.id, data.flair_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -16,10 +16,15 @@ use lemmy_utils::error::LemmyResult;
 pub mod add_mod;
 pub mod ban;
 pub mod block;
+pub mod flair;
 pub mod follow;
+pub mod mod_template;
 pub mod multi_community_follow;
 pub mod pending_follows;
+pub mod pending_posts;
 pub mod random;
+pub mod recommended;
+pub mod rule;
 pub mod tag;
 pub mod transfer;
 pub mod update_notifications;
@@ -0,0 +1,125 @@
+use crate::check_report_rule;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, slur_regex},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    community::Community,
+    mod_template::{ModTemplate, ModTemplateInsertForm, ModTemplateUpdateForm},
+  },
+};
+use lemmy_db_views_community::api::{
+  CreateModTemplate,
+  DeleteModTemplate,
+  EditModTemplate,
+  ListModTemplates,
+  ListModTemplatesResponse,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::{
+    slurs::check_slurs,
+    validation::{check_api_elements_count, is_valid_body_field, is_valid_rule_title},
+  },
+};
+
+pub async fn create_mod_template(
+  Json(data): Json<CreateModTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModTemplate>> {
+  check_mod_template_permission(data.community_id, &local_user_view, &context).await?;
+  check_mod_template_fields(&data.title, &data.body, &context).await?;
+  check_report_rule(data.rule_id, data.community_id, &context).await?;
+
+  let existing = ModTemplate::list_for_community(&mut context.pool(), data.community_id).await?;
+  check_api_elements_count(existing.len())?;
+
+  let form = ModTemplateInsertForm {
+    community_id: data.community_id,
+    title: data.title,
+    body: data.body,
+    rule_id: data.rule_id,
+  };
+  let mod_template = ModTemplate::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(mod_template))
+}
+
+pub async fn edit_mod_template(
+  Json(data): Json<EditModTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModTemplate>> {
+  let mod_template = ModTemplate::read(&mut context.pool(), data.mod_template_id).await?;
+  check_mod_template_permission(mod_template.community_id, &local_user_view, &context).await?;
+  check_mod_template_fields(&data.title, &data.body, &context).await?;
+  check_report_rule(data.rule_id, mod_template.community_id, &context).await?;
+
+  let form = ModTemplateUpdateForm {
+    title: Some(data.title),
+    body: Some(data.body),
+    rule_id: Some(data.rule_id),
+    updated_at: Some(Some(Utc::now())),
+  };
+  let mod_template = ModTemplate::update(&mut context.pool(), mod_template.id, &form).await?;
+
+  Ok(Json(mod_template))
+}
+
+pub async fn delete_mod_template(
+  Json(data): Json<DeleteModTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let mod_template = ModTemplate::read(&mut context.pool(), data.mod_template_id).await?;
+  check_mod_template_permission(mod_template.community_id, &local_user_view, &context).await?;
+
+  ModTemplate::delete(&mut context.pool(), mod_template.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+pub async fn list_mod_templates(
+  Query(data): Query<ListModTemplates>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListModTemplatesResponse>> {
+  check_mod_template_permission(data.community_id, &local_user_view, &context).await?;
+
+  let mod_templates =
+    ModTemplate::list_for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListModTemplatesResponse { mod_templates }))
+}
+
+/// Templates are only visible to the mods of the community.
+async fn check_mod_template_permission(
+  community_id: CommunityId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await
+}
+
+async fn check_mod_template_fields(
+  title: &str,
+  body: &str,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let slur_regex = slur_regex(context).await?;
+  is_valid_rule_title(title)?;
+  check_slurs(title, &slur_regex)?;
+  is_valid_body_field(body, false)?;
+  check_slurs(body, &slur_regex)?;
+  Ok(())
+}
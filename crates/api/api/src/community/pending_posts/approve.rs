@@ -0,0 +1,74 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_post_response,
+  context::LemmyContext,
+  notify::{NotifyData, notify_mod_action},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_mod_action, send_webmention},
+  webhooks::send_webhooks,
+};
+use lemmy_db_schema::source::{
+  community::Community,
+  modlog::{Modlog, ModlogInsertForm},
+  person::Person,
+  post::{Post, PostUpdateForm},
+};
+use lemmy_db_schema_file::enums::WebhookEventType;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{ApproveCommunityPendingPost, PostResponse};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn post_pending_posts_approve(
+  Json(data): Json<ApproveCommunityPendingPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  let orig_post = Post::read(&mut context.pool(), data.post_id).await?;
+  let community = Community::read(&mut context.pool(), orig_post.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+  if !orig_post.approval_pending {
+    return Err(LemmyErrorType::PostNotPendingApproval.into());
+  }
+
+  // Rejected posts are removed, so that the creator can still see them
+  let form = PostUpdateForm {
+    approval_pending: Some(false),
+    removed: (!data.approve).then_some(true),
+    ..Default::default()
+  };
+  let post = Post::update(&mut context.pool(), data.post_id, &form).await?;
+
+  let form = ModlogInsertForm::mod_approve_post(
+    local_user_view.person.id,
+    &post,
+    data.approve,
+    data.reason.as_deref(),
+  );
+  let action = Modlog::create(&mut context.pool(), &[form]).await?;
+  notify_mod_action(action, &context);
+
+  // Scheduled posts are sent out by the scheduled task instead
+  if data.approve && post.scheduled_publish_time_at.is_none() {
+    ActivityChannel::submit_activity(SendActivityData::CreatePost(post.clone()), &context)?;
+    send_webmention(post.clone(), &community, context.clone());
+    send_webhooks(
+      WebhookEventType::PostCreated,
+      Some(post.community_id),
+      &post,
+      &context,
+    );
+
+    let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+    let creator = Person::read(&mut context.pool(), post.creator_id).await?;
+    NotifyData {
+      do_send_email: !local_site.email_notifications_disabled,
+      ..NotifyData::new(post.clone(), creator, community.clone())
+    }
+    .send(&context);
+  }
+
+  build_post_response(&context, community.id, local_user_view, post.id).await
+}
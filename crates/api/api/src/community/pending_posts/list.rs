@@ -0,0 +1,28 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_of_any_or_admin_action};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{PostView, api::ListCommunityPendingPosts};
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_pending_posts_list(
+  Query(data): Query<ListCommunityPendingPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<PostView>>> {
+  check_community_mod_of_any_or_admin_action(&local_user_view, &mut context.pool()).await?;
+  let all_communities =
+    data.all_communities.unwrap_or_default() && local_user_view.local_user.admin;
+
+  let items = PostView::list_pending_approval(
+    &mut context.pool(),
+    &local_user_view.person,
+    data.community_id,
+    all_communities,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(items))
+}
@@ -0,0 +1,38 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_views_community::{
+  CommunityView,
+  api::{ListRecommendedCommunities, ListRecommendedCommunitiesResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_recommended_communities(
+  Query(data): Query<ListRecommendedCommunities>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListRecommendedCommunitiesResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+
+  // Show nsfw content if param is true, or if content_warning exists
+  let show_nsfw = data.show_nsfw.unwrap_or(site.content_warning.is_some());
+
+  let communities = CommunityView::list_recommended(
+    &mut context.pool(),
+    local_user,
+    &site,
+    show_nsfw,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(ListRecommendedCommunitiesResponse { communities }))
+}
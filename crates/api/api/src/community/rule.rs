@@ -0,0 +1,184 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_mod_action, check_private_instance, is_admin, slur_regex},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    community::Community,
+    rule::{Rule, RuleInsertForm, RuleUpdateForm},
+  },
+};
+use lemmy_db_views_community::api::{
+  CreateRule,
+  DeleteRule,
+  EditRule,
+  ListRules,
+  ListRulesResponse,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::{
+    slurs::check_slurs,
+    validation::{check_api_elements_count, is_valid_body_field, is_valid_rule_title},
+  },
+};
+use rand::{RngExt, distr::Alphanumeric};
+use url::Url;
+
+pub async fn create_rule(
+  Json(data): Json<CreateRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Rule>> {
+  check_rule_fields(Some(&data.title), data.description.as_deref(), &context).await?;
+  let community = check_rule_permission(data.community_id, &local_user_view, &context).await?;
+
+  let existing = Rule::read_for_community(&mut context.pool(), data.community_id).await?;
+  check_api_elements_count(existing.len())?;
+
+  // The rule title may change, so use a random string in the id
+  let random: String = rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(16)
+    .map(char::from)
+    .collect();
+  let ap_id = match &community {
+    Some(community) => Url::parse(&format!("{}/rule/{random}", community.ap_id))?,
+    None => Url::parse(&format!(
+      "{}/rule/{random}",
+      context.settings().get_protocol_and_hostname()
+    ))?,
+  };
+
+  let rule_form = RuleInsertForm {
+    ap_id: ap_id.into(),
+    community_id: data.community_id,
+    title: data.title,
+    description: data.description.filter(|d| !d.is_empty()),
+    position: data.position,
+    deleted: Some(false),
+  };
+  let rule = Rule::create(&mut context.pool(), &rule_form).await?;
+
+  send_community_update(community, &local_user_view, &context)?;
+
+  Ok(Json(rule))
+}
+
+pub async fn edit_rule(
+  Json(data): Json<EditRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Rule>> {
+  let rule = Rule::read(&mut context.pool(), data.rule_id).await?;
+  let community = check_rule_permission(rule.community_id, &local_user_view, &context).await?;
+  check_rule_fields(data.title.as_deref(), data.description.as_deref(), &context).await?;
+
+  let rule_form = RuleUpdateForm {
+    title: data.title,
+    description: data
+      .description
+      .map(|d| if d.is_empty() { None } else { Some(d) }),
+    position: data.position,
+    updated_at: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  let rule = Rule::update(&mut context.pool(), rule.id, &rule_form).await?;
+
+  send_community_update(community, &local_user_view, &context)?;
+
+  Ok(Json(rule))
+}
+
+pub async fn delete_rule(
+  Json(data): Json<DeleteRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Rule>> {
+  let rule = Rule::read(&mut context.pool(), data.rule_id).await?;
+  let community = check_rule_permission(rule.community_id, &local_user_view, &context).await?;
+
+  let rule_form = RuleUpdateForm {
+    deleted: Some(data.delete),
+    updated_at: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  let rule = Rule::update(&mut context.pool(), rule.id, &rule_form).await?;
+
+  send_community_update(community, &local_user_view, &context)?;
+
+  Ok(Json(rule))
+}
+
+pub async fn list_rules(
+  Query(data): Query<ListRules>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListRulesResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let rules = Rule::read_for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListRulesResponse { rules }))
+}
+
+async fn check_rule_fields(
+  title: Option<&str>,
+  description: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let slur_regex = slur_regex(context).await?;
+  if let Some(title) = title {
+    is_valid_rule_title(title)?;
+    check_slurs(title, &slur_regex)?;
+  }
+  if let Some(description) = description {
+    is_valid_body_field(description, false)?;
+    check_slurs(description, &slur_regex)?;
+  }
+  Ok(())
+}
+
+/// Community rules can only be managed by mods, site rules only by admins. Returns the community
+/// for community rules.
+async fn check_rule_permission(
+  community_id: Option<CommunityId>,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<Option<Community>> {
+  match community_id {
+    Some(community_id) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await?;
+      Ok(Some(community))
+    }
+    None => {
+      is_admin(local_user_view)?;
+      Ok(None)
+    }
+  }
+}
+
+/// Community rules are part of the group, so federate the change.
+fn send_community_update(
+  community: Option<Community>,
+  local_user_view: &LocalUserView,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if let Some(community) = community {
+    ActivityChannel::submit_activity(
+      SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+      context,
+    )?;
+  }
+  Ok(())
+}
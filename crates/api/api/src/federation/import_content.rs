@@ -0,0 +1,234 @@
+use crate::federation::resolve_object::resolve_object_internal;
+use activitypub_federation::config::Data;
+use actix_web::web::{Bytes, Json};
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use futures::StreamExt;
+use itertools::Itertools;
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_banned_or_deleted};
+use lemmy_db_schema::{
+  source::{
+    comment::{CommentActions, CommentSavedForm},
+    content_import::{ContentImport, ContentImportFailure},
+    post::{PostActions, PostSavedForm},
+  },
+  traits::Saveable,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{ResolveObjectView, api::ContentImportResponse};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  spawn_try_task,
+  utils::validation::check_api_elements_count,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::io::{Cursor, Read};
+use tracing::{info, warn};
+use url::Url;
+use zip::ZipArchive;
+
+const PARALLELISM: usize = 10;
+
+/// Maximum size of an uploaded archive. It may contain media files which are ignored here, so
+/// this is much larger than for other requests.
+pub const MAX_ARCHIVE_SIZE: usize = 100 * 1024 * 1024;
+
+/// Maximum uncompressed size of the `outbox.json` in the archive.
+const MAX_OUTBOX_SIZE: u64 = 20 * 1024 * 1024;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Outbox {
+  ordered_items: Vec<OutboxItem>,
+}
+
+#[derive(Deserialize)]
+struct OutboxItem {
+  #[serde(rename = "type")]
+  kind: String,
+  object: Value,
+}
+
+/// Imports the posts and comments from the `outbox.json` of a data archive, which was exported
+/// from Lemmy or another platform. The items are resolved in the background and added to the
+/// saved items, so that they stay accessible after moving to this instance.
+pub async fn import_content_history(
+  body: Bytes,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<ContentImportResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  let local_user_id = local_user_view.local_user.id;
+
+  // Only allow a single import at a time. If the previous one got stuck for some reason, allow
+  // starting a new one after an hour.
+  if let Some(latest) = ContentImport::read_latest(&mut context.pool(), local_user_id).await?
+    && latest.finished_at.is_none()
+    && latest.published_at > Utc::now() - Duration::hours(1)
+  {
+    return Err(LemmyErrorType::ContentImportInProgress.into());
+  }
+
+  let items = tokio::task::spawn_blocking(move || read_outbox(&body)).await??;
+  check_api_elements_count(items.len())?;
+
+  let content_import =
+    ContentImport::create(&mut context.pool(), local_user_id, items.len().try_into()?).await?;
+
+  let import_id = content_import.id;
+  spawn_try_task(async move {
+    info!(
+      "Starting content import for {}",
+      local_user_view.person.name
+    );
+    let local_user_view = Some(local_user_view);
+    let results = futures::stream::iter(items)
+      .map(|ap_id| {
+        // need to reset outgoing request count to avoid running into limit
+        let context = context.reset_request_count();
+        let local_user_view = &local_user_view;
+        async move {
+          let failure = import_item(&ap_id, local_user_view, &context)
+            .await
+            .err()
+            .map(|e| e.to_string());
+          ContentImport::item_processed(
+            &mut context.pool(),
+            import_id,
+            failure.as_deref().map(|reason| (ap_id.as_str(), reason)),
+          )
+          .await
+        }
+      })
+      .buffer_unordered(PARALLELISM)
+      .collect::<Vec<_>>()
+      .await;
+    // Always mark the import as finished, otherwise it would block new imports for an hour
+    for e in results.into_iter().filter_map(Result::err) {
+      warn!("Failed to record content import progress: {e}");
+    }
+    ContentImport::finish(&mut context.pool(), import_id).await?;
+    Ok(())
+  });
+
+  Ok(Json(ContentImportResponse {
+    content_import: Some(content_import),
+    failures: vec![],
+  }))
+}
+
+pub async fn get_content_import(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<ContentImportResponse>> {
+  let content_import =
+    ContentImport::read_latest(&mut context.pool(), local_user_view.local_user.id).await?;
+  let failures = match &content_import {
+    Some(i) => ContentImportFailure::list(&mut context.pool(), i.id).await?,
+    None => vec![],
+  };
+  Ok(Json(ContentImportResponse {
+    content_import,
+    failures,
+  }))
+}
+
+/// Reads the ids of all created objects from the `outbox.json` in the archive.
+fn read_outbox(archive: &[u8]) -> LemmyResult<Vec<Url>> {
+  let mut archive =
+    ZipArchive::new(Cursor::new(archive)).with_lemmy_type(LemmyErrorType::InvalidDataArchive)?;
+  let outbox = archive
+    .by_name("outbox.json")
+    .with_lemmy_type(LemmyErrorType::InvalidDataArchive)?;
+  // The size in the header may be wrong, so also limit the amount of data which is read
+  if outbox.size() > MAX_OUTBOX_SIZE {
+    return Err(LemmyErrorType::InvalidDataArchive.into());
+  }
+  let outbox: Outbox = serde_json::from_reader(outbox.take(MAX_OUTBOX_SIZE))
+    .with_lemmy_type(LemmyErrorType::InvalidDataArchive)?;
+
+  Ok(
+    outbox
+      .ordered_items
+      .into_iter()
+      // Ignore announces and other activities, which don't contain own content
+      .filter(|item| item.kind == "Create")
+      .filter_map(|item| match item.object {
+        Value::String(id) => Some(id),
+        Value::Object(object) => object.get("id")?.as_str().map(ToString::to_string),
+        _ => None,
+      })
+      .filter_map(|id| Url::parse(&id).ok())
+      .unique()
+      .collect(),
+  )
+}
+
+async fn import_item(
+  ap_id: &Url,
+  local_user_view: &Option<LocalUserView>,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let person_id = local_user_view
+    .as_ref()
+    .map(|l| l.person.id)
+    .ok_or(LemmyErrorType::NotLoggedIn)?;
+  match resolve_object_internal(ap_id.as_str(), local_user_view, context).await? {
+    ResolveObjectView::Post(p) => {
+      let form = PostSavedForm::new(p.post.id, person_id);
+      PostActions::save(&mut context.pool(), &form).await?;
+    }
+    ResolveObjectView::Comment(c) => {
+      let form = CommentSavedForm::new(person_id, c.comment.id);
+      CommentActions::save(&mut context.pool(), &form).await?;
+    }
+    _ => return Err(anyhow!("Only posts and comments can be imported").into()),
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::read_outbox;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use std::io::{Cursor, Write};
+  use url::Url;
+  use zip::{ZipWriter, write::SimpleFileOptions};
+
+  #[test]
+  fn test_read_outbox() -> LemmyResult<()> {
+    let outbox = r#"{
+      "type": "OrderedCollection",
+      "orderedItems": [
+        {"type": "Create", "object": {"type": "Page", "id": "https://example.com/post/1"}},
+        {"type": "Create", "object": "https://example.com/comment/2"},
+        {"type": "Announce", "object": "https://example.com/post/3"},
+        {"type": "Create", "object": {"type": "Page", "id": "https://example.com/post/1"}}
+      ]
+    }"#;
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("outbox.json", SimpleFileOptions::default())?;
+    zip.write_all(outbox.as_bytes())?;
+    let archive = zip.finish()?.into_inner();
+
+    assert_eq!(
+      vec![
+        Url::parse("https://example.com/post/1")?,
+        Url::parse("https://example.com/comment/2")?
+      ],
+      read_outbox(&archive)?
+    );
+    assert!(read_outbox(b"not a zip").is_err());
+
+    // Reject an outbox which is too large after decompression
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("outbox.json", SimpleFileOptions::default())?;
+    zip.write_all(&vec![b' '; 21 * 1024 * 1024])?;
+    zip.write_all(outbox.as_bytes())?;
+    let archive = zip.finish()?.into_inner();
+    assert!(read_outbox(&archive).is_err());
+    Ok(())
+  }
+}
@@ -94,6 +94,7 @@ async fn list_comments_common(
     search_term,
     page_cursor,
     limit,
+    ..Default::default()
   }
   .list(&mut context.pool(), &site, &local_site)
   .await
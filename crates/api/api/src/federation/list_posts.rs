@@ -117,6 +117,7 @@ pub async fn list_posts(
     search_url_only,
     tag_id,
     page_cursor,
+    ..Default::default()
   }
   .list(&mut context.pool(), site, local_site)
   .await?;
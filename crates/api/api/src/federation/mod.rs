@@ -6,15 +6,18 @@ use lemmy_db_schema::{
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
 
 mod fetcher;
+pub mod import_content;
 pub mod list_comments;
 pub mod list_person_content;
 pub mod list_persons;
 pub mod list_posts;
+pub mod move_account;
 pub mod read_community;
 pub mod read_multi_community;
 pub mod read_person;
 pub mod resolve_object;
 pub mod search;
+mod search_operators;
 pub mod user_settings_backup;
 
 /// Returns default listing type, depending if the query is for frontpage or community.
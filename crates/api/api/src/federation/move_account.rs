@@ -0,0 +1,123 @@
+use crate::federation::fetcher::resolve_ap_identifier;
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use actix_web::web::Json;
+use bcrypt::verify;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_local_user_banned_or_deleted,
+};
+use lemmy_apub_objects::objects::person::ApubPerson;
+use lemmy_db_schema::{
+  source::{
+    community::{CommunityActions, CommunityFollowerForm},
+    person::{Person, PersonUpdateForm},
+  },
+  traits::Followable,
+};
+use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{MoveAccount, SetAccountAliases, SuccessResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::check_api_elements_count,
+};
+
+pub async fn set_account_aliases(
+  Json(data): Json<SetAccountAliases>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  check_api_elements_count(data.aliases.len())?;
+
+  let mut also_known_as = vec![];
+  let my_user = Some(local_user_view.clone());
+  for alias in &data.aliases {
+    let alias = alias.trim().trim_start_matches('@');
+    let person =
+      resolve_ap_identifier::<ApubPerson, Person>(alias, &context, &my_user, false).await?;
+    if person.id != local_user_view.person.id && !also_known_as.contains(&person.ap_id) {
+      also_known_as.push(person.ap_id.clone());
+    }
+  }
+
+  let form = PersonUpdateForm {
+    also_known_as: Some(also_known_as),
+    ..Default::default()
+  };
+  Person::update(&mut context.pool(), local_user_view.person.id, &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+pub async fn move_account(
+  Json(data): Json<MoveAccount>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+
+  // Verify the password
+  let valid: bool = local_user_view
+    .local_user
+    .password_encrypted
+    .as_ref()
+    .and_then(|password_encrypted| verify(&data.password, password_encrypted).ok())
+    .unwrap_or(false);
+  if !valid {
+    return Err(LemmyErrorType::IncorrectLogin.into());
+  }
+
+  let person = &local_user_view.person;
+  if person.moved_to.is_some() {
+    return Err(LemmyErrorType::AccountAlreadyMoved.into());
+  }
+
+  let target = data.target.trim().trim_start_matches('@');
+  let my_user = Some(local_user_view.clone());
+  let target =
+    resolve_ap_identifier::<ApubPerson, Person>(target, &context, &my_user, false).await?;
+  // Refetch remote accounts, as the alias was likely added just before
+  let target = if target.local {
+    target
+  } else {
+    ObjectId::<ApubPerson>::from(target.ap_id.clone())
+      .dereference_forced(&context)
+      .await?
+  };
+  if target.id == person.id || !target.also_known_as.contains(&person.ap_id) {
+    return Err(LemmyErrorType::AccountMoveAliasMissing.into());
+  }
+
+  let form = PersonUpdateForm {
+    moved_to: Some(Some(target.ap_id.clone())),
+    ..Default::default()
+  };
+  let person = Person::update(&mut context.pool(), person.id, &form).await?;
+  let remote_communities =
+    Person::migrate_actions(&mut context.pool(), person.id, target.id).await?;
+
+  // Follows of remote communities are moved by sending new follows from the new account. This is
+  // only possible if it is local, otherwise the instance of the new account has to do it.
+  if target.local {
+    for community in remote_communities {
+      let follow_state = if community.visibility == CommunityVisibility::Private {
+        CommunityFollowerState::ApprovalRequired
+      } else {
+        CommunityFollowerState::Pending
+      };
+      let form = CommunityFollowerForm::new(community.id, target.id, follow_state);
+      CommunityActions::follow(&mut context.pool(), &form).await?;
+      ActivityChannel::submit_activity(
+        SendActivityData::FollowCommunity(community, target.0.clone(), true),
+        &context,
+      )?;
+    }
+  }
+
+  ActivityChannel::submit_activity(SendActivityData::MoveUser(person, target.0), &context)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
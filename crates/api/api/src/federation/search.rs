@@ -1,6 +1,7 @@
 use crate::federation::{
   fetcher::{resolve_community_identifier, resolve_person_identifier},
   resolve_object::resolve_object_internal,
+  search_operators::SearchOperators,
 };
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
@@ -12,9 +13,10 @@ use lemmy_db_schema::{
   MultiCommunitySortType,
   PersonListingType,
   PersonSortType,
+  SearchSortType,
   SearchType,
 };
-use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
+use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
 use lemmy_db_views_comment::impls::CommentQuery;
 use lemmy_db_views_community::impls::{CommunityQuery, MultiCommunityQuery};
 use lemmy_db_views_local_user::LocalUserView;
@@ -25,7 +27,7 @@ use lemmy_db_views_site::{
   api::{Search, SearchResponse},
 };
 use lemmy_diesel_utils::pagination::PaginationCursor;
-use lemmy_utils::error::LemmyResult;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub async fn search(
   Query(data): Query<Search>,
@@ -42,27 +44,64 @@ pub async fn search(
     .await
     .ok();
 
-  let search_term = Some(data.search_term);
   let search_type = data.type_.unwrap_or_default();
+  let operators = match SearchOperators::parse(&data.search_term) {
+    Ok(operators) => operators,
+    // Don't fail if the search term could be resolved as object id anyway
+    Err(_) if resolve.is_some() => {
+      return Ok(Json(SearchResponse {
+        resolve,
+        comments: vec![],
+        posts: vec![],
+        communities: vec![],
+        persons: vec![],
+        multi_communities: vec![],
+        prev_page: None,
+        next_page: None,
+      }));
+    }
+    Err(e) => return Err(e),
+  };
+  if !operators.supports(search_type) {
+    return Err(LemmyErrorType::SearchOperatorNotSupported.into());
+  }
+  let search_term = Some(operators.search_term.clone());
+  let search_sort = data.sort.unwrap_or_default();
+  let sort_by_relevance = Some(search_sort == SearchSortType::Relevance);
+  let old = search_sort == SearchSortType::Old;
   let listing_type = Some(ListingType::All);
   let search_title_only = data.title_only;
   let time_range_seconds = data.time_range_seconds;
   let search_url_only = data.post_url_only;
-  let show_nsfw = data.show_nsfw;
+  let show_nsfw = data.show_nsfw.or(operators.nsfw);
+  let nsfw_only = operators.nsfw;
   let page_cursors = from_single_cursor(data.page_cursor.clone(), search_type);
   let limit = data.limit;
 
+  // Explicit parameters take precedence over operators in the search term
+  let community_name = data.community_name.clone().or(
+    operators
+      .community
+      .clone()
+      .filter(|_| data.community_id.is_none()),
+  );
   let community_id = resolve_community_identifier(
-    &data.community_name,
+    &community_name,
     data.community_id,
     &context,
     &local_user_view,
   )
   .await?;
 
+  let creator_username = data.creator_username.clone().or(
+    operators
+      .author
+      .clone()
+      .filter(|_| data.creator_id.is_none()),
+  );
   let creator_id = resolve_person_identifier(
     data.creator_id,
-    &data.creator_username,
+    &creator_username,
     &context,
     &local_user_view,
   )
@@ -80,7 +119,20 @@ pub async fn search(
     creator_id,
     time_range_seconds,
     search_url_only,
+    sort_by_relevance,
+    published_after: operators.after,
+    published_before: operators.before,
+    url_domain: operators.domain.clone(),
+    has_url: Some(operators.has_url),
+    has_image: Some(operators.has_image),
+    instance_domain: operators.site.clone(),
+    sort: match search_sort {
+      SearchSortType::Relevance => None,
+      SearchSortType::New => Some(PostSortType::New),
+      SearchSortType::Old => Some(PostSortType::Old),
+    },
     show_nsfw,
+    nsfw_only,
     page_cursor: page_cursors[0].clone(),
     limit,
     ..Default::default()
@@ -95,7 +147,15 @@ pub async fn search(
     community_id,
     creator_id,
     time_range_seconds,
-    sort: Some(CommentSortType::New),
+    sort_by_relevance,
+    published_after: operators.after,
+    published_before: operators.before,
+    instance_domain: operators.site.clone(),
+    sort: Some(if old {
+      CommentSortType::Old
+    } else {
+      CommentSortType::New
+    }),
     page_cursor: page_cursors[1].clone(),
     limit,
     ..Default::default()
@@ -109,7 +169,11 @@ pub async fn search(
     local_user,
     listing_type: Some(PersonListingType::All),
     community_id: None,
-    sort: Some(PersonSortType::New),
+    sort: Some(if old {
+      PersonSortType::Old
+    } else {
+      PersonSortType::New
+    }),
     page_cursor: page_cursors[2].clone(),
     limit,
   }
@@ -123,7 +187,13 @@ pub async fn search(
     listing_type,
     time_range_seconds,
     show_nsfw,
-    sort: Some(CommunitySortType::New),
+    nsfw_only,
+    sort_by_relevance,
+    sort: Some(if old {
+      CommunitySortType::Old
+    } else {
+      CommunitySortType::New
+    }),
     page_cursor: page_cursors[3].clone(),
     limit,
     ..Default::default()
@@ -138,7 +208,11 @@ pub async fn search(
     local_user,
     time_range_seconds,
     listing_type: Some(MultiCommunityListingType::All),
-    sort: Some(MultiCommunitySortType::New),
+    sort: Some(if old {
+      MultiCommunitySortType::Old
+    } else {
+      MultiCommunitySortType::New
+    }),
     page_cursor: page_cursors[4].clone(),
     limit,
     ..Default::default()
@@ -154,14 +228,15 @@ pub async fn search(
   let mut next_page: [_; 5] = Default::default();
   let mut prev_page: [_; 5] = Default::default();
 
+  // An All search only includes the types which support the given operators. If the community or
+  // creator is included, only posts and comments are searched.
   let search_all = search_type == SearchType::All;
+  let community_or_creator_included = community_id.is_some() || creator_id.is_some();
+  let include = |t: SearchType| search_type == t || (search_all && operators.supports(t));
+  let include_other =
+    |t: SearchType| include(t) && (search_type == t || !community_or_creator_included);
 
-  // If the community or creator is included and it's All search, only search posts and comments
-  let community_or_creator_included =
-    data.community_id.is_some() || data.community_name.is_some() || data.creator_id.is_some();
-  let search_all_no_community_or_creator = search_all && !community_or_creator_included;
-
-  if (search_type == SearchType::Posts || search_all)
+  if include(SearchType::Posts)
     && (data.page_cursor.is_none() || page_cursors[0].is_some())
     && let Ok(x) = posts_query.await
   {
@@ -169,7 +244,7 @@ pub async fn search(
     next_page[0] = x.next_page;
     prev_page[0] = x.prev_page;
   }
-  if (search_type == SearchType::Comments || search_all)
+  if include(SearchType::Comments)
     && (data.page_cursor.is_none() || page_cursors[1].is_some())
     && let Ok(x) = comments_query.await
   {
@@ -177,7 +252,7 @@ pub async fn search(
     next_page[1] = x.next_page;
     prev_page[1] = x.prev_page;
   }
-  if (search_type == SearchType::Users || search_all_no_community_or_creator)
+  if include_other(SearchType::Users)
     && (data.page_cursor.is_none() || page_cursors[2].is_some())
     && let Ok(x) = persons_query.await
   {
@@ -185,7 +260,7 @@ pub async fn search(
     next_page[2] = x.next_page;
     prev_page[2] = x.prev_page;
   }
-  if (search_type == SearchType::Communities || search_all_no_community_or_creator)
+  if include_other(SearchType::Communities)
     && (data.page_cursor.is_none() || page_cursors[3].is_some())
     && let Ok(x) = communities_query.await
   {
@@ -193,7 +268,7 @@ pub async fn search(
     next_page[3] = x.next_page;
     prev_page[3] = x.prev_page;
   }
-  if (search_type == SearchType::MultiCommunities || search_all_no_community_or_creator)
+  if include_other(SearchType::MultiCommunities)
     && (data.page_cursor.is_none() || page_cursors[4].is_some())
     && let Ok(x) = multi_communities_query.await
   {
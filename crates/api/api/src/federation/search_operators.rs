@@ -0,0 +1,208 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use lemmy_db_schema::SearchType;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// A search term with optional filter operators, for example
+/// `rust async author:alice@lemmy.ml community:rust after:2026-01-01 has:url nsfw:no`.
+///
+/// Operators are removed from the search term and returned as separate filters. Text inside quotes
+/// is never treated as an operator, and words with an unknown prefix (eg `https://example.com` or
+/// `c++:`) are left in the search term.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct SearchOperators {
+  /// The remaining text, which is used for full-text search.
+  pub search_term: String,
+  /// `author:name` or `author:name@instance`
+  pub author: Option<String>,
+  /// `community:name` or `community:name@instance`
+  pub community: Option<String>,
+  /// `site:lemmy.ml`, only content in communities of this instance.
+  pub site: Option<String>,
+  /// `domain:github.com`, only posts linking to this domain or its subdomains.
+  pub domain: Option<String>,
+  /// `after:YYYY-MM-DD`
+  pub after: Option<DateTime<Utc>>,
+  /// `before:YYYY-MM-DD`
+  pub before: Option<DateTime<Utc>>,
+  /// `has:url`
+  pub has_url: bool,
+  /// `has:image`
+  pub has_image: bool,
+  /// `nsfw:yes` for only nsfw content, or `nsfw:no` for only sfw content. Applies to posts and
+  /// communities.
+  pub nsfw: Option<bool>,
+}
+
+impl SearchOperators {
+  pub(super) fn parse(input: &str) -> LemmyResult<Self> {
+    let mut res = SearchOperators::default();
+    let mut words = vec![];
+
+    for word in split_words(input) {
+      let Some((operator, value)) = word.split_once(':') else {
+        words.push(word);
+        continue;
+      };
+      let invalid = || LemmyErrorType::InvalidSearchOperator(word.to_string());
+      let value = Some(value).filter(|v| !v.is_empty());
+      match operator {
+        "author" => res.author = Some(value.ok_or_else(invalid)?.to_string()),
+        "community" => res.community = Some(value.ok_or_else(invalid)?.to_string()),
+        "site" => res.site = Some(value.and_then(parse_domain).ok_or_else(invalid)?),
+        "domain" => res.domain = Some(value.and_then(parse_domain).ok_or_else(invalid)?),
+        "after" => res.after = Some(value.and_then(parse_date).ok_or_else(invalid)?),
+        "before" => res.before = Some(value.and_then(parse_date).ok_or_else(invalid)?),
+        "has" => match value {
+          Some("url") => res.has_url = true,
+          Some("image") => res.has_image = true,
+          _ => return Err(invalid().into()),
+        },
+        "nsfw" => match value {
+          Some("yes") => res.nsfw = Some(true),
+          Some("no") => res.nsfw = Some(false),
+          _ => return Err(invalid().into()),
+        },
+        _ => words.push(word),
+      }
+    }
+
+    res.search_term = words.join(" ");
+    Ok(res)
+  }
+
+  /// Operators which only apply to posts.
+  pub(super) fn post_only(&self) -> bool {
+    self.domain.is_some() || self.has_url || self.has_image
+  }
+
+  /// Operators which only apply to posts and comments.
+  pub(super) fn post_or_comment_only(&self) -> bool {
+    self.post_only()
+      || self.author.is_some()
+      || self.community.is_some()
+      || self.site.is_some()
+      || self.after.is_some()
+      || self.before.is_some()
+  }
+
+  /// Whether all operators can be applied to results of the given type. An `All` search only
+  /// includes the types which support them.
+  pub(super) fn supports(&self, search_type: SearchType) -> bool {
+    match search_type {
+      SearchType::All | SearchType::Posts => true,
+      SearchType::Comments => !self.post_only() && self.nsfw.is_none(),
+      SearchType::Communities => !self.post_or_comment_only(),
+      SearchType::Users | SearchType::MultiCommunities => {
+        !self.post_or_comment_only() && self.nsfw.is_none()
+      }
+    }
+  }
+}
+
+/// Splits the input at whitespace, except inside quotes.
+fn split_words(input: &str) -> Vec<&str> {
+  let mut words = vec![];
+  let mut start = None;
+  let mut in_quotes = false;
+  for (i, c) in input.char_indices() {
+    if c.is_whitespace() && !in_quotes {
+      if let Some(word) = start.take().and_then(|s| input.get(s..i)) {
+        words.push(word);
+      }
+      continue;
+    }
+    if c == '"' {
+      in_quotes = !in_quotes;
+    }
+    start.get_or_insert(i);
+  }
+  if let Some(word) = start.and_then(|s| input.get(s..)) {
+    words.push(word);
+  }
+  words
+}
+
+fn parse_domain(value: &str) -> Option<String> {
+  value
+    .chars()
+    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    .then(|| value.to_lowercase())
+}
+
+/// Parses a date in the format `YYYY-MM-DD`, as midnight UTC.
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+  NaiveDate::parse_from_str(value, "%Y-%m-%d")
+    .ok()?
+    .and_hms_opt(0, 0, 0)
+    .map(|d| d.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_search_operators() -> LemmyResult<()> {
+    let parsed = SearchOperators::parse(
+      "rust async author:alice@lemmy.ml community:rust after:2026-01-01 has:url domain:GitHub.com nsfw:no",
+    )?;
+    assert_eq!(
+      SearchOperators {
+        search_term: "rust async".to_string(),
+        author: Some("alice@lemmy.ml".to_string()),
+        community: Some("rust".to_string()),
+        domain: Some("github.com".to_string()),
+        after: NaiveDate::from_ymd_opt(2026, 1, 1)
+          .and_then(|d| d.and_hms_opt(0, 0, 0))
+          .map(|d| d.and_utc()),
+        has_url: true,
+        nsfw: Some(false),
+        ..Default::default()
+      },
+      parsed
+    );
+    assert!(parsed.post_only());
+    assert!(parsed.supports(SearchType::Posts));
+    assert!(!parsed.supports(SearchType::Comments));
+    assert!(!parsed.supports(SearchType::Communities));
+    Ok(())
+  }
+
+  #[test]
+  fn test_search_operators_supports() -> LemmyResult<()> {
+    let parsed = SearchOperators::parse("rust author:alice")?;
+    assert!(parsed.supports(SearchType::Comments));
+    assert!(!parsed.supports(SearchType::Users));
+
+    let parsed = SearchOperators::parse("rust nsfw:yes")?;
+    assert!(parsed.supports(SearchType::Communities));
+    assert!(!parsed.supports(SearchType::Comments));
+    assert!(!parsed.supports(SearchType::MultiCommunities));
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_search_operators_plain_text() -> LemmyResult<()> {
+    // Quoted text, urls and unknown prefixes stay in the search term
+    let input = "\"site:lemmy.ml is down\" https://lemmy.ml/post/1 c++: -rust";
+    let parsed = SearchOperators::parse(input)?;
+    assert_eq!(input, parsed.search_term);
+    assert!(!parsed.post_or_comment_only());
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_search_operators_invalid() {
+    for input in [
+      "after:yesterday",
+      "has:video",
+      "nsfw:maybe",
+      "author:",
+      "rust before:",
+      "domain:%.com",
+    ] {
+      assert!(SearchOperators::parse(input).is_err(), "{input}");
+    }
+  }
+}
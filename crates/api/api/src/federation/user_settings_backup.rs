@@ -29,7 +29,7 @@ use lemmy_db_views_site::{
   api::{SuccessResponse, UserSettingsBackup},
   impls::user_backup_list_to_user_settings_backup,
 };
-use lemmy_diesel_utils::traits::Crud;
+use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
 use lemmy_utils::{
   error::LemmyResult,
   spawn_try_task,
@@ -75,11 +75,24 @@ pub async fn import_user_settings(
     is_valid_matrix_id(matrix_user_id)?;
   }
 
+  // Allow moving the exported account to this account
+  let exported_ap_id: Option<DbUrl> = data.ap_id.clone().map(Into::into);
+  let also_known_as = exported_ap_id
+    .filter(|ap_id| {
+      ap_id != &local_user_view.person.ap_id
+        && !local_user_view.person.also_known_as.contains(ap_id)
+    })
+    .map(|ap_id| {
+      let mut also_known_as = local_user_view.person.also_known_as.clone();
+      also_known_as.push(ap_id);
+      also_known_as
+    });
   let person_form = PersonUpdateForm {
     display_name: data.display_name.clone().map(Some),
     bio: data.bio.clone().map(Some),
     matrix_user_id: data.matrix_id.clone().map(Some),
     bot_account: data.bot_account,
+    also_known_as,
     ..Default::default()
   };
   // ignore error in case form is empty
@@ -111,6 +124,7 @@ pub async fn import_user_settings(
     show_upvotes: data.settings.as_ref().map(|s| s.show_upvotes),
     show_downvotes: data.settings.as_ref().map(|s| s.show_downvotes),
     show_upvote_percentage: data.settings.as_ref().map(|s| s.show_upvote_percentage),
+    email_digest: data.settings.as_ref().map(|s| s.email_digest),
     ..Default::default()
   };
   let local_user_id = local_user_view.local_user.id;
@@ -390,6 +404,10 @@ pub(crate) mod tests {
       import_user_updated.person.display_name
     );
     assert_eq!(export_user.person.bio, import_user_updated.person.bio);
+    assert_eq!(
+      vec![export_user.person.ap_id.clone()],
+      import_user_updated.person.also_known_as
+    );
 
     let follows = CommunityFollowerView::for_person(pool, import_user.person.id).await?;
     assert_eq!(follows.len(), 1);
@@ -1,6 +1,10 @@
 use lemmy_api_utils::{context::LemmyContext, utils::is_mod_or_admin_opt};
-use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, RuleId},
+  source::rule::Rule,
+};
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::slurs::check_slurs,
@@ -29,6 +33,26 @@ pub(crate) fn check_report_reason(reason: &str, slur_regex: &Regex) -> LemmyResu
   }
 }
 
+/// Reads the rule referenced by a report or mod template. It must be a site rule, or a rule of the
+/// community which the item belongs to.
+pub(crate) async fn check_report_rule(
+  rule_id: Option<RuleId>,
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> LemmyResult<Option<Rule>> {
+  let Some(rule_id) = rule_id else {
+    return Ok(None);
+  };
+  let rule = Rule::read(&mut context.pool(), rule_id).await?;
+  if rule.deleted {
+    Err(LemmyErrorType::NotFound.into())
+  } else if rule.community_id.is_some_and(|id| id != community_id) {
+    Err(LemmyErrorType::RuleNotInCommunity.into())
+  } else {
+    Ok(Some(rule))
+  }
+}
+
 pub(crate) fn check_totp_2fa_valid(
   local_user_view: &LocalUserView,
   totp_token: &Option<String>,
@@ -8,7 +8,11 @@ use lemmy_api_utils::{
   context::LemmyContext,
   utils::{check_local_user_banned_or_deleted, password_length_check},
 };
-use lemmy_db_schema::source::{local_user::LocalUser, login_token::LoginToken};
+use lemmy_db_schema::source::{
+  api_token::ApiToken,
+  local_user::LocalUser,
+  login_token::LoginToken,
+};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{ChangePassword, LoginResponse};
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
@@ -45,6 +49,7 @@ pub async fn change_password(
     LocalUser::update_password(&mut context.pool(), local_user_id, &new_password).await?;
 
   LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+  ApiToken::delete_all(&mut context.pool(), local_user_view.local_user.id).await?;
 
   // Return the jwt
   Ok(Json(LoginResponse {
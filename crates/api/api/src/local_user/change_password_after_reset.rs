@@ -1,6 +1,7 @@
 use actix_web::web::{Data, Json};
 use lemmy_api_utils::{context::LemmyContext, utils::password_length_check};
 use lemmy_db_schema::source::{
+  api_token::ApiToken,
   local_user::LocalUser,
   login_token::LoginToken,
   password_reset_request::PasswordResetRequest,
@@ -30,6 +31,7 @@ pub async fn change_password_after_reset(
   LocalUser::update_password(&mut context.pool(), local_user_id, &password).await?;
 
   LoginToken::invalidate_all(&mut context.pool(), local_user_id).await?;
+  ApiToken::delete_all(&mut context.pool(), local_user_id).await?;
 
   Ok(Json(SuccessResponse::default()))
 }
@@ -0,0 +1,303 @@
+use crate::local_user::export_data::build_export_data;
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::CreateType, collection::OrderedCollectionType},
+  traits::Object,
+};
+use actix_web::{HttpResponse, http::header::CONTENT_DISPOSITION, web::Json};
+use chrono::{DateTime, Duration, Utc};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::{
+  objects::{comment::ApubComment, person::ApubPerson, post::ApubPost},
+  protocol::{note::Note, page::Page},
+};
+use lemmy_db_schema::{newtypes::DataExportId, source::data_export::DataExport};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DataArchiveResponse, PostOrCommentOrPrivateMessage};
+use lemmy_utils::{
+  FEDERATION_CONTEXT,
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  settings::structs::Settings,
+  spawn_try_task,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+  fs::File,
+  io::Write,
+  path::{Path, PathBuf},
+};
+use tokio::task::spawn_blocking;
+use tokio_util::io::ReaderStream;
+use tracing::warn;
+use url::Url;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// Version of the archive layout, increase it when making incompatible changes.
+const ARCHIVE_VERSION: i32 = 1;
+
+/// Exports which would get larger than this fail, so that a single user can't fill up the disk.
+const MAX_ARCHIVE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Archives are stored in the configured directory until they expire after a week.
+fn archive_path(export_id: DataExportId, settings: &Settings) -> PathBuf {
+  Path::new(&settings.data_export_dir).join(format!("{}.zip", export_id.0))
+}
+
+/// Requests a new data archive, which is built in the background.
+pub async fn create_data_archive(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DataArchiveResponse>> {
+  let local_user_id = local_user_view.local_user.id;
+
+  // Only allow a single export at a time. If building the previous one got stuck for some reason,
+  // allow starting a new one after an hour.
+  if let Some(latest) = DataExport::read_latest(&mut context.pool(), local_user_id).await?
+    && latest.finished_at.is_none()
+    && !latest.failed
+    && latest.published_at > Utc::now() - Duration::hours(1)
+  {
+    return Err(LemmyErrorType::DataExportInProgress.into());
+  }
+
+  let data_export = DataExport::create(&mut context.pool(), local_user_id).await?;
+
+  let export_id = data_export.id;
+  let context_ = context.clone();
+  spawn_try_task(async move {
+    let path = archive_path(export_id, context_.settings());
+    let res = build_data_archive(export_id, &path, &local_user_view, &context_).await;
+    if res.is_err() {
+      tokio::fs::remove_file(&path).await.ok();
+      DataExport::mark_failed(&mut context_.pool(), export_id).await?;
+    }
+    res
+  });
+
+  Ok(Json(DataArchiveResponse {
+    data_export: Some(data_export),
+  }))
+}
+
+pub async fn get_data_archive(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DataArchiveResponse>> {
+  let data_export =
+    DataExport::read_latest(&mut context.pool(), local_user_view.local_user.id).await?;
+  Ok(Json(DataArchiveResponse { data_export }))
+}
+
+pub async fn download_data_archive(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  let path =
+    DataExport::read_archive_path(&mut context.pool(), local_user_view.local_user.id).await?;
+  let archive = tokio::fs::File::open(path)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+  let filename = format!("lemmy-export-{}.zip", local_user_view.person.name);
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type("application/zip")
+      .insert_header((
+        CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{filename}\""),
+      ))
+      .streaming(ReaderStream::new(archive)),
+  )
+}
+
+#[derive(Serialize)]
+struct Manifest {
+  version: i32,
+  created: DateTime<Utc>,
+  actor: Url,
+  files: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Outbox {
+  #[serde(rename = "@context")]
+  context: Value,
+  #[serde(rename = "type")]
+  kind: OrderedCollectionType,
+  total_items: usize,
+  ordered_items: Vec<OutboxItem>,
+}
+
+/// Wraps each post or comment in a `Create` activity, the same way as Mastodon and other
+/// platforms lay out their outbox exports.
+#[derive(Serialize)]
+struct OutboxItem {
+  id: Url,
+  #[serde(rename = "type")]
+  kind: CreateType,
+  actor: Url,
+  published: DateTime<Utc>,
+  object: OutboxObject,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum OutboxObject {
+  Page(Box<Page>),
+  Note(Box<Note>),
+}
+
+async fn build_data_archive(
+  export_id: DataExportId,
+  path: &Path,
+  local_user_view: &LocalUserView,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let data = build_export_data(local_user_view, context).await?;
+  let actor_id: Url = data.person.ap_id.clone().into();
+
+  let mut ordered_items = vec![];
+  for item in &data.content {
+    let (object_id, published, object) = match item {
+      PostOrCommentOrPrivateMessage::Post(p) => (
+        p.ap_id.clone(),
+        p.published_at,
+        OutboxObject::Page(Box::new(ApubPost(p.clone()).into_json(context).await?)),
+      ),
+      PostOrCommentOrPrivateMessage::Comment(c) => (
+        c.ap_id.clone(),
+        c.published_at,
+        OutboxObject::Note(Box::new(ApubComment(c.clone()).into_json(context).await?)),
+      ),
+      // Private messages are not part of the public outbox
+      PostOrCommentOrPrivateMessage::PrivateMessage(_) => continue,
+    };
+    let mut id: Url = object_id.into();
+    id.set_fragment(Some("create"));
+    ordered_items.push(OutboxItem {
+      id,
+      kind: CreateType::Create,
+      actor: actor_id.clone(),
+      published,
+      object,
+    });
+  }
+  let outbox = Outbox {
+    context: FEDERATION_CONTEXT.clone(),
+    kind: OrderedCollectionType::OrderedCollection,
+    total_items: ordered_items.len(),
+    ordered_items,
+  };
+  let actor = ApubPerson(data.person.clone()).into_json(context).await?;
+
+  let mut archive = ArchiveWriter::create(path.to_path_buf()).await?;
+  archive = archive
+    .add("data.json", serde_json::to_vec_pretty(&data)?)
+    .await?;
+  archive = archive
+    .add("actor.json", serde_json::to_vec_pretty(&actor)?)
+    .await?;
+  archive = archive
+    .add("outbox.json", serde_json::to_vec_pretty(&outbox)?)
+    .await?;
+
+  let pictrs_url = &context.settings().pictrs()?.url;
+  for image in &data.uploads {
+    let url = format!("{pictrs_url}image/original/{}", image.pictrs_alias);
+    let bytes = async {
+      let res = context
+        .pictrs_client()
+        .get(&url)
+        .timeout(REQWEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+      LemmyResult::Ok(res.bytes().await?)
+    };
+    match bytes.await {
+      Ok(bytes) => {
+        archive = archive
+          .add(&format!("media/{}", image.pictrs_alias), bytes.to_vec())
+          .await?;
+      }
+      Err(e) => warn!(
+        "Failed to include image {} in data export: {e}",
+        image.pictrs_alias
+      ),
+    }
+  }
+
+  let manifest = Manifest {
+    version: ARCHIVE_VERSION,
+    created: Utc::now(),
+    actor: actor_id,
+    files: archive.files.clone(),
+  };
+  archive = archive
+    .add("manifest.json", serde_json::to_vec_pretty(&manifest)?)
+    .await?;
+  archive.finish().await?;
+
+  let path = path.to_string_lossy();
+  DataExport::finish(&mut context.pool(), export_id, &path).await?;
+  Ok(())
+}
+
+/// Writes the files of the archive to disk one by one, so that the archive is never held in
+/// memory as a whole.
+struct ArchiveWriter {
+  zip: ZipWriter<File>,
+  size: u64,
+  files: Vec<String>,
+}
+
+impl ArchiveWriter {
+  async fn create(path: PathBuf) -> LemmyResult<Self> {
+    spawn_blocking(move || {
+      if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+      }
+      Ok(Self {
+        zip: ZipWriter::new(File::create(path)?),
+        size: 0,
+        files: vec![],
+      })
+    })
+    .await?
+  }
+
+  async fn add(mut self, name: &str, content: Vec<u8>) -> LemmyResult<Self> {
+    self.size += u64::try_from(content.len())?;
+    if self.size > MAX_ARCHIVE_SIZE {
+      return Err(LemmyErrorType::DataExportTooLarge.into());
+    }
+    let name = name.to_string();
+    spawn_blocking(move || {
+      // Images are already compressed, so only compress the json files
+      let compression = if name.starts_with("media/") {
+        CompressionMethod::Stored
+      } else {
+        CompressionMethod::Deflated
+      };
+      self.zip.start_file(
+        &name,
+        SimpleFileOptions::default().compression_method(compression),
+      )?;
+      self.zip.write_all(&content)?;
+      self.files.push(name);
+      Ok(self)
+    })
+    .await?
+  }
+
+  async fn finish(self) -> LemmyResult<()> {
+    spawn_blocking(move || {
+      self.zip.finish()?;
+      Ok(())
+    })
+    .await?
+  }
+}
@@ -1,11 +1,14 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::private_message;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_image::LocalImageView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_notification::{NotificationData, impls::NotificationQuery};
 use lemmy_db_views_person_content_combined::impls::PersonContentCombinedQuery;
 use lemmy_db_views_person_liked_combined::impls::PersonLikedCombinedQuery;
+use lemmy_db_views_person_saved_combined::impls::PersonSavedCombinedQuery;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_post_comment_combined::PostCommentCombinedView;
 use lemmy_db_views_site::{
@@ -18,6 +21,14 @@ pub async fn export_user_data(
   context: Data<LemmyContext>,
   local_user_view: LocalUserView,
 ) -> LemmyResult<Json<ExportDataResponse>> {
+  Ok(Json(build_export_data(&local_user_view, &context).await?))
+}
+
+/// Collects all data of the user. This is also the main content of the data archive.
+pub(crate) async fn build_export_data(
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<ExportDataResponse> {
   use PostOrCommentOrPrivateMessage::*;
 
   let local_instance_id = local_user_view.person.instance_id;
@@ -34,7 +45,7 @@ pub async fn export_user_data(
       ..Default::default()
     }
   }
-  .list(pool, Some(&local_user_view), local_instance_id)
+  .list(pool, Some(local_user_view), local_instance_id)
   .await?
   .into_iter()
   .map(|u| match u {
@@ -64,7 +75,7 @@ pub async fn export_user_data(
     no_limit: Some(true),
     ..PersonLikedCombinedQuery::default()
   }
-  .list(pool, &local_user_view)
+  .list(pool, local_user_view)
   .await?
   .into_iter()
   .map(|u| {
@@ -88,15 +99,41 @@ pub async fn export_user_data(
     .map(|cv| cv.community.ap_id.into())
     .collect();
 
+  let saved = PersonSavedCombinedQuery {
+    no_limit: Some(true),
+    ..PersonSavedCombinedQuery::default()
+  }
+  .list(pool, local_user_view)
+  .await?
+  .into_iter()
+  .map(|u| match u {
+    PostCommentCombinedView::Post(pv) => Post(pv.post),
+    PostCommentCombinedView::Comment(cv) => Comment(cv.comment),
+  })
+  .collect();
+
+  let private_messages_sent =
+    private_message::PrivateMessage::list_sent(pool, my_person_id).await?;
+
+  let uploads = LocalImageView::get_all_by_person_id(pool, my_person_id)
+    .await?
+    .into_iter()
+    .map(|i| i.local_image)
+    .collect();
+
   let settings =
-    user_backup_list_to_user_settings_backup(local_user_view, &mut context.pool()).await?;
+    user_backup_list_to_user_settings_backup(local_user_view.clone(), &mut context.pool()).await?;
 
-  Ok(Json(ExportDataResponse {
+  Ok(ExportDataResponse {
     notifications,
     content,
     liked,
     read_posts,
     moderates,
     settings,
-  }))
+    saved,
+    private_messages_sent,
+    uploads,
+    person: my_person.clone(),
+  })
 }
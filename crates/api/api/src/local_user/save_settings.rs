@@ -18,6 +18,7 @@ use lemmy_db_schema::{
   },
   utils::limit_fetch_check,
 };
+use lemmy_db_schema_file::enums::EmailDigestFrequency;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::{
   SiteView,
@@ -110,6 +111,14 @@ pub async fn save_user_settings(
     return Err(LemmyErrorType::EmailNotificationsDisabled.into());
   }
 
+  if data
+    .email_digest
+    .is_some_and(|d| d != EmailDigestFrequency::Never)
+    && local_site.email_notifications_disabled
+  {
+    return Err(LemmyErrorType::EmailNotificationsDisabled.into());
+  }
+
   let local_user_id = local_user_view.local_user.id;
   let person_id = local_user_view.person.id;
   let default_listing_type = data.default_listing_type;
@@ -162,6 +171,7 @@ pub async fn save_user_settings(
     show_media: data.show_media,
     show_read_posts: data.show_read_posts,
     send_notifications_to_email: data.send_notifications_to_email,
+    email_digest: data.email_digest,
     show_nsfw: data.show_nsfw,
     blur_nsfw: data.blur_nsfw,
     show_bot_accounts: data.show_bot_accounts,
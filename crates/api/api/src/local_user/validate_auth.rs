@@ -3,6 +3,7 @@ use actix_web::{
   web::{Data, Json},
 };
 use lemmy_api_utils::{
+  api_token::{is_api_token, local_user_view_from_api_token},
   context::LemmyContext,
   utils::{local_user_view_from_jwt, read_auth_token},
 };
@@ -17,7 +18,11 @@ pub async fn validate_auth(
 ) -> LemmyResult<Json<SuccessResponse>> {
   let jwt = read_auth_token(&req)?;
   if let Some(jwt) = jwt {
-    local_user_view_from_jwt(&jwt, &context).await?;
+    if is_api_token(&jwt) {
+      local_user_view_from_api_token(&jwt, &context).await?;
+    } else {
+      local_user_view_from_jwt(&jwt, &context).await?;
+    }
   } else {
     return Err(LemmyErrorType::NotLoggedIn.into());
   }
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_mod_or_admin};
+use lemmy_db_schema::source::{post::Post, post_revision::PostRevision};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{GetPostHistory, GetPostHistoryResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_post_history(
+  Query(data): Query<GetPostHistory>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetPostHistoryResponse>> {
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+
+  // Only the creator, mods and admins can see previous versions
+  if post.creator_id != local_user_view.person.id {
+    is_mod_or_admin(&mut context.pool(), &local_user_view, post.community_id).await?;
+  }
+
+  let revisions = PostRevision::list(&mut context.pool(), post.id).await?;
+  Ok(Json(GetPostHistoryResponse { revisions }))
+}
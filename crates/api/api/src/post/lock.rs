@@ -3,11 +3,13 @@ use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_post_response,
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
 };
 use lemmy_db_schema::source::{
+  mod_template::ModTemplate,
   modlog::{Modlog, ModlogInsertForm},
   post::{Post, PostUpdateForm},
 };
@@ -44,6 +46,14 @@ pub async fn lock_post(
   )
   .await?;
 
+  let mod_template = ModTemplate::read_for_action(
+    &mut context.pool(),
+    data.mod_template_id,
+    orig_post.community.id,
+  )
+  .await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
   // Update the post
   let post_id = data.post_id;
   let locked = data.locked;
@@ -58,22 +68,26 @@ pub async fn lock_post(
   .await?;
 
   // Mod tables
-  let form = ModlogInsertForm::mod_lock_post(
-    local_user_view.person.id,
-    &orig_post.post,
-    locked,
-    &data.reason,
-  );
+  let mut form =
+    ModlogInsertForm::mod_lock_post(local_user_view.person.id, &orig_post.post, locked, &reason);
+  form.mod_template_id = data.mod_template_id;
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(action.clone(), &context);
 
+  ModActionReply {
+    post: &post,
+    comment: None,
+    community: &orig_post.community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
-    SendActivityData::LockPost(
-      post,
-      local_user_view.person.clone(),
-      data.locked,
-      data.reason.clone(),
-    ),
+    SendActivityData::LockPost(post, local_user_view.person.clone(), data.locked, reason),
     &context,
   )?;
 
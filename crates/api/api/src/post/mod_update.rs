@@ -8,7 +8,10 @@ use lemmy_api_utils::{
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_community_user_action, check_nsfw_allowed, is_mod_or_admin, update_post_tags},
 };
-use lemmy_db_schema::source::post::{Post, PostUpdateForm};
+use lemmy_db_schema::source::{
+  post::{Post, PostUpdateForm},
+  post_revision::PostRevision,
+};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::{
   PostView,
@@ -52,6 +55,7 @@ pub async fn mod_edit_post(
 
   let post_id = data.post_id;
   let updated_post = Post::update(&mut context.pool(), post_id, &post_form).await?;
+  PostRevision::create_if_changed(&mut context.pool(), &orig_post.post, &updated_post).await?;
   plugin_hook_after("local_post_after_vote", &post_form);
 
   if let Some(tags) = &data.tags {
@@ -0,0 +1,128 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    check_bot_account,
+    check_community_user_action,
+    check_post_deleted_or_removed,
+    check_private_instance,
+  },
+};
+use lemmy_db_schema::source::post_poll::{PostPoll, PostPollOption, PostPollVote};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{
+  PostPollView,
+  PostView,
+  api::{GetPostPoll, PostPollResponse, VotePostPoll},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::collections::HashSet;
+
+pub async fn get_post_poll(
+  Query(data): Query<GetPostPoll>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<PostPollResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  // Ensures that the post is visible to the user
+  let post_view = PostView::read(
+    &mut context.pool(),
+    data.post_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    site.instance_id,
+    false,
+  )
+  .await?;
+
+  let person_id = local_user_view.as_ref().map(|l| l.person.id);
+  let poll_view = PostPollView::read(&mut context.pool(), &post_view.post, person_id)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+
+  Ok(Json(PostPollResponse { poll_view }))
+}
+
+pub async fn vote_post_poll(
+  Json(data): Json<VotePostPoll>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostPollResponse>> {
+  let post_id = data.post_id;
+  let my_person_id = local_user_view.person.id;
+  check_bot_account(&local_user_view.person)?;
+
+  let post_view = PostView::read(
+    &mut context.pool(),
+    post_id,
+    Some(&local_user_view.local_user),
+    local_user_view.person.instance_id,
+    false,
+  )
+  .await?;
+  check_community_user_action(&local_user_view, &post_view.community, &mut context.pool()).await?;
+  check_post_deleted_or_removed(&post_view.post)?;
+
+  let poll = PostPoll::read_for_post(&mut context.pool(), post_id)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  if poll.is_closed() {
+    return Err(LemmyErrorType::PollClosed.into());
+  }
+
+  // All options must belong to this poll, and only one is allowed for single choice polls
+  let options = PostPollOption::read_for_post(&mut context.pool(), post_id).await?;
+  let option_ids: HashSet<_> = data.option_ids.iter().collect();
+  let all_valid = data
+    .option_ids
+    .iter()
+    .all(|id| options.iter().any(|o| o.id == *id));
+  if data.option_ids.is_empty()
+    || option_ids.len() != data.option_ids.len()
+    || !all_valid
+    || (!poll.multiple_choice && data.option_ids.len() > 1)
+  {
+    return Err(LemmyErrorType::InvalidPollVote.into());
+  }
+
+  let previous_votes =
+    PostPollVote::read_for_person(&mut context.pool(), post_id, my_person_id).await?;
+  PostPollVote::vote(&mut context.pool(), post_id, my_person_id, &data.option_ids).await?;
+
+  if post_view.post.local {
+    let poll = PostPoll::update_counts(&mut context.pool(), post_id).await?;
+    if !poll.results_hidden() {
+      ActivityChannel::submit_activity(
+        SendActivityData::UpdatePost(post_view.post.clone()),
+        &context,
+      )?;
+    }
+  } else {
+    // Votes can't be retracted over federation, so only send the new ones
+    let new_options = options
+      .into_iter()
+      .filter(|o| option_ids.contains(&o.id) && !previous_votes.contains(&o.id))
+      .collect::<Vec<_>>();
+    if !new_options.is_empty() {
+      ActivityChannel::submit_activity(
+        SendActivityData::VotePostPoll {
+          post: post_view.post.clone(),
+          actor: local_user_view.person.clone(),
+          options: new_options,
+        },
+        &context,
+      )?;
+    }
+  }
+
+  let poll_view = PostPollView::read(&mut context.pool(), &post_view.post, Some(my_person_id))
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  Ok(Json(PostPollResponse { poll_view }))
+}
@@ -4,11 +4,15 @@ use either::Either;
 use lemmy_api_utils::{
   build_response::build_post_response,
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
 };
-use lemmy_db_schema::source::modlog::{Modlog, ModlogInsertForm};
+use lemmy_db_schema::source::{
+  mod_template::ModTemplate,
+  modlog::{Modlog, ModlogInsertForm},
+};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::{
   PostView,
@@ -42,19 +46,37 @@ pub async fn create_post_warning(
   )
   .await?;
 
+  let mod_template = ModTemplate::read_for_action(
+    &mut context.pool(),
+    data.mod_template_id,
+    orig_post.community.id,
+  )
+  .await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
   // Mod tables
-  let form = ModlogInsertForm::mod_create_post_warning(
-    local_user_view.person.id,
-    &orig_post.post,
-    &data.reason,
-  );
+  let mut form =
+    ModlogInsertForm::mod_create_post_warning(local_user_view.person.id, &orig_post.post, &reason);
+  form.mod_template_id = data.mod_template_id;
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(action, &context);
 
+  ModActionReply {
+    post: &orig_post.post,
+    comment: None,
+    community: &orig_post.community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
     SendActivityData::Warning(
       Box::new(Either::Left(orig_post.clone())),
-      data.reason,
+      reason,
       local_user_view.person.clone(),
     ),
     &context,
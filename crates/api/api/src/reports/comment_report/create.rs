@@ -1,4 +1,4 @@
-use crate::check_report_reason;
+use crate::{check_report_reason, check_report_rule};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use either::Either;
@@ -12,11 +12,13 @@ use lemmy_api_utils::{
     check_local_user_banned_or_deleted,
     slur_regex,
   },
+  webhooks::{WebhookReport, send_webhooks},
 };
 use lemmy_db_schema::{
   source::comment_report::{CommentReport, CommentReportForm},
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEventType;
 use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
@@ -59,12 +61,18 @@ pub async fn create_comment_report(
   // Don't allow creating reports for removed / deleted comments
   check_comment_deleted_or_removed(&comment_view.comment)?;
 
+  let rule = check_report_rule(data.rule_id, comment_view.community.id, &context).await?;
+  // Site rules are enforced by admins, so these reports are not shown to community mods
+  let violates_instance_rules = data.violates_instance_rules.unwrap_or_default()
+    || rule.as_ref().is_some_and(|r| r.community_id.is_none());
+
   let report_form = CommentReportForm {
     creator_id: person.id,
     comment_id,
     original_comment_text: comment_view.comment.content,
     reason,
-    violates_instance_rules: data.violates_instance_rules.unwrap_or_default(),
+    violates_instance_rules,
+    rule_id: rule.as_ref().map(|r| r.id),
   };
 
   let report = CommentReport::report(&mut context.pool(), &report_form).await?;
@@ -72,6 +80,13 @@ pub async fn create_comment_report(
   let comment_report_view =
     ReportCombinedViewInternal::read_comment_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("comment_report_after_create", &comment_report_view);
+  // Reports for instance rules are only visible to admins
+  send_webhooks(
+    WebhookEventType::ReportCreated,
+    (!report.violates_instance_rules).then_some(comment_view.community.id),
+    &WebhookReport::CommentReport(&report),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -92,6 +107,7 @@ pub async fn create_comment_report(
         actor: local_user_view.person,
         receiver: Either::Right(comment_view.community),
         reason: data.reason.clone(),
+        rule: rule.map(|r| r.ap_id),
       },
       &context,
     )?;
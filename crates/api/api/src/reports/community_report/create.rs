@@ -7,6 +7,7 @@ use lemmy_api_utils::{
   plugins::plugin_hook_after,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_community_user_action, check_local_user_banned_or_deleted, slur_regex},
+  webhooks::{WebhookReport, send_webhooks},
 };
 use lemmy_db_schema::{
   source::{
@@ -16,6 +17,7 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEventType;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
   ReportCombinedViewInternal,
@@ -60,6 +62,13 @@ pub async fn create_community_report(
     ReportCombinedViewInternal::read_community_report(&mut context.pool(), report.id, person)
       .await?;
   plugin_hook_after("community_report_after_create", &community_report_view);
+  // Reports about a community are handled by admins, not by its mods
+  send_webhooks(
+    WebhookEventType::ReportCreated,
+    None,
+    &WebhookReport::CommunityReport(&report),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -82,6 +91,7 @@ pub async fn create_community_report(
       actor: local_user_view.person,
       receiver: Either::Left(site),
       reason: data.reason.clone(),
+      rule: None,
     },
     &context,
   )?;
@@ -1,4 +1,4 @@
-use crate::check_report_reason;
+use crate::{check_report_reason, check_report_rule};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use either::Either;
@@ -12,11 +12,13 @@ use lemmy_api_utils::{
     check_post_deleted_or_removed,
     slur_regex,
   },
+  webhooks::{WebhookReport, send_webhooks},
 };
 use lemmy_db_schema::{
   source::post_report::{PostReport, PostReportForm},
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEventType;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_report_combined::{
@@ -54,6 +56,11 @@ pub async fn create_post_report(
 
   check_post_deleted_or_removed(&orig_post.post)?;
 
+  let rule = check_report_rule(data.rule_id, orig_post.community.id, &context).await?;
+  // Site rules are enforced by admins, so these reports are not shown to community mods
+  let violates_instance_rules = data.violates_instance_rules.unwrap_or_default()
+    || rule.as_ref().is_some_and(|r| r.community_id.is_none());
+
   let report_form = PostReportForm {
     creator_id: person.id,
     post_id,
@@ -61,7 +68,8 @@ pub async fn create_post_report(
     original_post_url: orig_post.post.url,
     original_post_body: orig_post.post.body,
     reason,
-    violates_instance_rules: data.violates_instance_rules.unwrap_or_default(),
+    violates_instance_rules,
+    rule_id: rule.as_ref().map(|r| r.id),
   };
 
   let report = PostReport::report(&mut context.pool(), &report_form).await?;
@@ -69,6 +77,13 @@ pub async fn create_post_report(
   let post_report_view =
     ReportCombinedViewInternal::read_post_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("post_report_after_create", &post_report_view);
+  // Reports for instance rules are only visible to admins
+  send_webhooks(
+    WebhookEventType::ReportCreated,
+    (!report.violates_instance_rules).then_some(orig_post.community.id),
+    &WebhookReport::PostReport(&report),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -89,6 +104,7 @@ pub async fn create_post_report(
         actor: local_user_view.person,
         receiver: Either::Right(orig_post.community),
         reason: data.reason.clone(),
+        rule: rule.map(|r| r.ap_id),
       },
       &context,
     )?;
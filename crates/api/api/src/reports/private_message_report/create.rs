@@ -7,6 +7,7 @@ use lemmy_api_utils::{
   plugins::plugin_hook_after,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_local_user_banned_or_deleted, slur_regex},
+  webhooks::{WebhookReport, send_webhooks},
 };
 use lemmy_db_schema::{
   source::{
@@ -16,6 +17,7 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEventType;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
   ReportCombinedViewInternal,
@@ -61,6 +63,12 @@ pub async fn create_pm_report(
     "private_message_report_after_create",
     &private_message_report_view,
   );
+  send_webhooks(
+    WebhookEventType::ReportCreated,
+    None,
+    &WebhookReport::PrivateMessageReport(&report),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -91,6 +99,7 @@ pub async fn create_pm_report(
       actor: private_message_report_view.creator.clone(),
       receiver: Either::Left(site),
       reason: data.reason.clone(),
+      rule: None,
     },
     &context,
   )?;
@@ -26,6 +26,7 @@ pub async fn list_reports(
     limit,
     show_community_rule_violations,
     my_reports_only,
+    rule_id,
   } = data;
 
   // Only check mod or admin status when not viewing my reports
@@ -40,6 +41,7 @@ pub async fn list_reports(
     unresolved_only,
     show_community_rule_violations,
     my_reports_only,
+    rule_id,
     sort,
     page_cursor,
     limit,
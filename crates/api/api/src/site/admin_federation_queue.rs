@@ -0,0 +1,171 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::{
+  activity::SentActivity,
+  federation_queue_state::FederationQueueState,
+  instance::{Instance, InstanceForm},
+  modlog::{Modlog, ModlogInsertForm},
+};
+use lemmy_db_schema_file::InstanceId;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  FederatedInstanceView,
+  api::{
+    AdminFederationQueueView,
+    AdminListFederationQueues,
+    AdminMarkInstanceDead,
+    AdminResetFederationQueue,
+    AdminSkipFederationActivity,
+    GetFederatedInstances,
+    GetFederatedInstancesKind,
+    PendingActivity,
+  },
+};
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::collections::HashMap;
+
+/// How many of the next activities in the queue are returned for each instance.
+const PENDING_ACTIVITIES_SAMPLE: i64 = 5;
+
+pub async fn admin_list_federation_queues(
+  Query(data): Query<AdminListFederationQueues>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<PagedResponse<AdminFederationQueueView>>> {
+  is_admin(&local_user_view)?;
+
+  let kind = if data.failing_only.unwrap_or_default() {
+    GetFederatedInstancesKind::Failing
+  } else {
+    GetFederatedInstancesKind::All
+  };
+  let instances = FederatedInstanceView::list(
+    &mut context.pool(),
+    GetFederatedInstances {
+      domain_filter: data.domain_filter,
+      kind,
+      page_cursor: data.page_cursor,
+      limit: data.limit,
+    },
+  )
+  .await?;
+
+  // Load the pending activities of all instances at once
+  let instance_ids = instances.items.iter().map(|i| i.instance.id).collect();
+  let mut pending_activities: HashMap<InstanceId, Vec<PendingActivity>> = HashMap::new();
+  for (instance_id, a) in
+    SentActivity::list_pending(&mut context.pool(), instance_ids, PENDING_ACTIVITIES_SAMPLE).await?
+  {
+    pending_activities
+      .entry(instance_id)
+      .or_default()
+      .push(PendingActivity {
+        id: a.id,
+        ap_id: a.ap_id,
+        actor_apub_id: a.actor_apub_id,
+        published_at: a.published_at,
+      });
+  }
+  let items = instances
+    .items
+    .into_iter()
+    .map(|instance| AdminFederationQueueView {
+      pending_activities: pending_activities
+        .remove(&instance.instance.id)
+        .unwrap_or_default(),
+      instance,
+    })
+    .collect();
+
+  Ok(Json(PagedResponse {
+    items,
+    next_page: instances.next_page,
+    prev_page: instances.prev_page,
+  }))
+}
+
+pub async fn admin_reset_federation_queue(
+  Json(data): Json<AdminResetFederationQueue>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<FederatedInstanceView>> {
+  is_admin(&local_user_view)?;
+
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+  FederationQueueState::request_reset(&mut context.pool(), instance.id).await?;
+
+  let form = ModlogInsertForm::admin_reset_federation_queue(
+    local_user_view.person.id,
+    instance.id,
+    data.reason.as_deref(),
+  );
+  Modlog::create(&mut context.pool(), &[form]).await?;
+
+  Ok(Json(
+    FederatedInstanceView::read(&mut context.pool(), instance.id).await?,
+  ))
+}
+
+pub async fn admin_skip_federation_activity(
+  Json(data): Json<AdminSkipFederationActivity>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<FederatedInstanceView>> {
+  is_admin(&local_user_view)?;
+
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+  let activity = SentActivity::read(&mut context.pool(), data.activity_id).await?;
+  let state = FederationQueueState::load(&mut context.pool(), instance.id).await?;
+  if state
+    .last_successful_id
+    .is_some_and(|last| activity.id <= last)
+  {
+    return Err(LemmyErrorType::ActivityAlreadySent.into());
+  }
+  FederationQueueState::request_skip(&mut context.pool(), instance.id, activity.id).await?;
+
+  let form = ModlogInsertForm::admin_skip_federation_activity(
+    local_user_view.person.id,
+    instance.id,
+    data.reason.as_deref(),
+  );
+  Modlog::create(&mut context.pool(), &[form]).await?;
+
+  Ok(Json(
+    FederatedInstanceView::read(&mut context.pool(), instance.id).await?,
+  ))
+}
+
+pub async fn admin_mark_instance_dead(
+  Json(data): Json<AdminMarkInstanceDead>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<FederatedInstanceView>> {
+  is_admin(&local_user_view)?;
+
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+  FederationQueueState::set_marked_dead(&mut context.pool(), instance.id, data.dead).await?;
+  if !data.dead {
+    // Otherwise the instance would still be considered dead if it hasn't been seen for a while
+    let form = InstanceForm {
+      updated_at: Some(Utc::now()),
+      ..InstanceForm::new(instance.domain.clone())
+    };
+    Instance::update(&mut context.pool(), instance.id, form).await?;
+  }
+
+  let form = ModlogInsertForm::admin_mark_instance_dead(
+    local_user_view.person.id,
+    instance.id,
+    data.dead,
+    data.reason.as_deref(),
+  );
+  Modlog::create(&mut context.pool(), &[form]).await?;
+
+  Ok(Json(
+    FederatedInstanceView::read(&mut context.pool(), instance.id).await?,
+  ))
+}
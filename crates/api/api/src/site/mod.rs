@@ -1,5 +1,6 @@
 pub mod admin_allow_instance;
 pub mod admin_block_instance;
+pub mod admin_federation_queue;
 pub mod admin_list_users;
 pub mod federated_instances;
 pub mod list_all_media;
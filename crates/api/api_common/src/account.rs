@@ -1,3 +1,4 @@
+pub use lemmy_db_schema_file::enums::EmailDigestFrequency;
 pub use lemmy_db_views_person_content_combined::api::{ListPersonHidden, ListPersonRead};
 pub use lemmy_db_views_person_liked_combined::ListPersonLiked;
 pub use lemmy_db_views_person_saved_combined::ListPersonSaved;
@@ -24,6 +24,8 @@ pub use lemmy_db_views_community::{
     GetRandomCommunity,
     ListCommunities,
     ListMultiCommunities,
+    ListRecommendedCommunities,
+    ListRecommendedCommunitiesResponse,
   },
 };
 pub use lemmy_db_views_community_follower_approval::PendingFollowerView;
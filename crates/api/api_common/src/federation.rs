@@ -20,5 +20,14 @@ pub use lemmy_db_views_site::{
 };
 
 pub mod administration {
-  pub use lemmy_db_views_site::api::{AdminAllowInstanceParams, AdminBlockInstanceParams};
+  pub use lemmy_db_views_site::api::{
+    AdminAllowInstanceParams,
+    AdminBlockInstanceParams,
+    AdminFederationQueueView,
+    AdminListFederationQueues,
+    AdminMarkInstanceDead,
+    AdminResetFederationQueue,
+    AdminSkipFederationActivity,
+    PendingActivity,
+  };
 }
@@ -0,0 +1,46 @@
+use super::{check_api_token_expiry, check_api_token_scopes};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{
+  api_token::{API_TOKEN_PREFIX, hash_api_token},
+  context::LemmyContext,
+};
+use lemmy_db_schema::source::api_token::{ApiToken, ApiTokenInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateApiToken, CreateApiTokenResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_api_token_name};
+use rand::{RngExt, distr::Alphanumeric};
+
+pub async fn create_api_token(
+  Json(data): Json<CreateApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateApiTokenResponse>> {
+  is_valid_api_token_name(&data.name)?;
+  check_api_token_scopes(&data.scopes, &local_user_view, &context).await?;
+  check_api_token_expiry(data.expires_at)?;
+
+  let token = generate_api_token();
+  let form = ApiTokenInsertForm {
+    local_user_id: local_user_view.local_user.id,
+    name: data.name.trim().to_string(),
+    token_hash: hash_api_token(&token),
+    scopes: data.scopes,
+    expires_at: data.expires_at,
+  };
+  let api_token = ApiToken::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateApiTokenResponse {
+    api_token,
+    token: token.into(),
+  }))
+}
+
+fn generate_api_token() -> String {
+  let random: String = rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(40)
+    .map(char::from)
+    .collect();
+  format!("{API_TOKEN_PREFIX}{random}")
+}
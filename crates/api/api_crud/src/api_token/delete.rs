@@ -0,0 +1,22 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::api_token::ApiToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteApiToken, SuccessResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_api_token(
+  Json(data): Json<DeleteApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let api_token = ApiToken::read(&mut context.pool(), data.id).await?;
+  if api_token.local_user_id != local_user_view.local_user.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  ApiToken::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{is_admin, is_mod_or_admin},
+};
+use lemmy_db_schema::source::api_token::ApiTokenScope;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Tokens can't have more permissions than the user, so the `admin` scope is only available for
+/// admins, and `moderate:community/<id>` for the mods of that community.
+async fn check_api_token_scopes(
+  scopes: &[ApiTokenScope],
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if scopes.is_empty() {
+    return Err(LemmyErrorType::InvalidApiTokenScopes.into());
+  }
+  for scope in scopes {
+    match scope {
+      ApiTokenScope::Admin => is_admin(local_user_view)?,
+      ApiTokenScope::ModerateCommunity(community_id) => {
+        is_mod_or_admin(&mut context.pool(), local_user_view, *community_id).await?
+      }
+      ApiTokenScope::Read | ApiTokenScope::Post | ApiTokenScope::Vote => {}
+    }
+  }
+  Ok(())
+}
+
+fn check_api_token_expiry(expires_at: Option<DateTime<Utc>>) -> LemmyResult<()> {
+  if expires_at.is_some_and(|e| e < Utc::now()) {
+    return Err(LemmyErrorType::InvalidUnixTime.into());
+  }
+  Ok(())
+}
@@ -0,0 +1,39 @@
+use super::{check_api_token_expiry, check_api_token_scopes};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::api_token::{ApiToken, ApiTokenUpdateForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ApiTokenResponse, EditApiToken};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_api_token_name,
+};
+
+pub async fn edit_api_token(
+  Json(data): Json<EditApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ApiTokenResponse>> {
+  let api_token = ApiToken::read(&mut context.pool(), data.id).await?;
+  if api_token.local_user_id != local_user_view.local_user.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  if let Some(name) = &data.name {
+    is_valid_api_token_name(name)?;
+  }
+  if let Some(scopes) = &data.scopes {
+    check_api_token_scopes(scopes, &local_user_view, &context).await?;
+  }
+  check_api_token_expiry(data.expires_at)?;
+
+  let form = ApiTokenUpdateForm {
+    name: data.name.map(|n| n.trim().to_string()),
+    scopes: data.scopes,
+    expires_at: data.expires_at.map(Some),
+  };
+  let api_token = ApiToken::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(ApiTokenResponse { api_token }))
+}
@@ -0,0 +1,62 @@
+use super::{AutomodRuleCheck, check_automod_permission, check_automod_rule};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::{AutomodRule, AutomodRuleInsertForm};
+use lemmy_db_views_community::api::{AutomodRuleResponse, CreateAutomodRule};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_automod_rule_name};
+
+pub async fn create_automod_rule(
+  Json(data): Json<CreateAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  check_automod_permission(data.community_id, &local_user_view, &context).await?;
+  is_valid_automod_rule_name(&data.name)?;
+
+  // Empty strings are treated like missing values, same as for edits
+  let title_regex = diesel_string_update(data.title_regex.as_deref()).flatten();
+  let body_regex = diesel_string_update(data.body_regex.as_deref()).flatten();
+  let url_regex = diesel_string_update(data.url_regex.as_deref()).flatten();
+  let reply = diesel_string_update(data.reply.as_deref()).flatten();
+  let domains = data.domains.unwrap_or_default();
+  let language_ids = data.language_ids.unwrap_or_default();
+  check_automod_rule(
+    AutomodRuleCheck {
+      community_id: data.community_id,
+      regexes: [
+        title_regex.as_deref(),
+        body_regex.as_deref(),
+        url_regex.as_deref(),
+      ],
+      has_other_condition: !domains.is_empty()
+        || !language_ids.is_empty()
+        || data.max_account_age_days.is_some()
+        || data.max_person_post_score.is_some(),
+      actions: &data.actions,
+      community_tag_id: data.community_tag_id,
+      reply: reply.as_deref(),
+    },
+    &context,
+  )
+  .await?;
+
+  let form = AutomodRuleInsertForm {
+    enabled: data.enabled,
+    title_regex,
+    body_regex,
+    url_regex,
+    domains: Some(domains),
+    language_ids: Some(language_ids),
+    max_account_age_days: data.max_account_age_days,
+    max_person_post_score: data.max_person_post_score,
+    community_tag_id: data.community_tag_id,
+    reply,
+    ..AutomodRuleInsertForm::new(data.community_id, data.name, data.actions)
+  };
+  let rule = AutomodRule::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
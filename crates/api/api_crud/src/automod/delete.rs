@@ -0,0 +1,23 @@
+use super::check_automod_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::AutomodRule;
+use lemmy_db_views_community::api::DeleteAutomodRule;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_automod_rule(
+  Json(data): Json<DeleteAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let rule = AutomodRule::read(&mut context.pool(), data.id).await?;
+  check_automod_permission(rule.community_id, &local_user_view, &context).await?;
+
+  AutomodRule::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,19 @@
+use super::check_automod_permission;
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::AutomodRule;
+use lemmy_db_views_community::api::{ListAutomodRules, ListAutomodRulesResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_automod_rules(
+  Query(data): Query<ListAutomodRules>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListAutomodRulesResponse>> {
+  check_automod_permission(data.community_id, &local_user_view, &context).await?;
+
+  let rules = AutomodRule::list(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListAutomodRulesResponse { rules }))
+}
@@ -0,0 +1,62 @@
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, CommunityTagId},
+  source::{community::Community, community_tag::CommunityTag},
+};
+use lemmy_db_schema_file::enums::AutomodAction;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::{build_and_check_regex, is_valid_body_field},
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+async fn check_automod_permission(
+  community_id: CommunityId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await
+}
+
+/// The complete content of a rule, after applying an edit.
+struct AutomodRuleCheck<'a> {
+  community_id: CommunityId,
+  regexes: [Option<&'a str>; 3],
+  has_other_condition: bool,
+  actions: &'a [AutomodAction],
+  community_tag_id: Option<CommunityTagId>,
+  reply: Option<&'a str>,
+}
+
+/// A rule without conditions would match every post, so at least one is required.
+async fn check_automod_rule(rule: AutomodRuleCheck<'_>, context: &LemmyContext) -> LemmyResult<()> {
+  let regexes: Vec<&str> = rule.regexes.iter().flatten().copied().collect();
+  for regex in &regexes {
+    build_and_check_regex(Some(*regex))?;
+  }
+  if (regexes.is_empty() && !rule.has_other_condition) || rule.actions.is_empty() {
+    return Err(LemmyErrorType::InvalidAutomodRule.into());
+  }
+
+  if rule.actions.contains(&AutomodAction::ApplyTag) {
+    let tag_id = rule
+      .community_tag_id
+      .ok_or(LemmyErrorType::InvalidAutomodRule)?;
+    let tags = CommunityTag::read_for_community(&mut context.pool(), rule.community_id).await?;
+    if !tags.iter().any(|t| t.id == tag_id) {
+      return Err(LemmyErrorType::TagNotInCommunity.into());
+    }
+  }
+  if rule.actions.contains(&AutomodAction::Reply) {
+    let reply = rule.reply.ok_or(LemmyErrorType::InvalidAutomodRule)?;
+    is_valid_body_field(reply, false)?;
+  }
+  Ok(())
+}
@@ -0,0 +1,79 @@
+use super::{AutomodRuleCheck, check_automod_permission, check_automod_rule};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::{AutomodRule, AutomodRuleUpdateForm};
+use lemmy_db_views_community::api::{AutomodRuleResponse, EditAutomodRule};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{
+  traits::Crud,
+  utils::{diesel_opt_number_update, diesel_string_update},
+};
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_automod_rule_name};
+
+pub async fn edit_automod_rule(
+  Json(data): Json<EditAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  let rule = AutomodRule::read(&mut context.pool(), data.id).await?;
+  check_automod_permission(rule.community_id, &local_user_view, &context).await?;
+  if let Some(name) = &data.name {
+    is_valid_automod_rule_name(name)?;
+  }
+
+  let form = AutomodRuleUpdateForm {
+    name: data.name,
+    enabled: data.enabled,
+    title_regex: diesel_string_update(data.title_regex.as_deref()),
+    body_regex: diesel_string_update(data.body_regex.as_deref()),
+    url_regex: diesel_string_update(data.url_regex.as_deref()),
+    domains: data.domains,
+    language_ids: data.language_ids,
+    max_account_age_days: diesel_opt_number_update(data.max_account_age_days),
+    max_person_post_score: data.max_person_post_score.map(Some),
+    actions: data.actions,
+    community_tag_id: data.community_tag_id.map(Some),
+    reply: diesel_string_update(data.reply.as_deref()),
+    updated_at: Some(Some(Utc::now())),
+  };
+
+  // Check the rule as it will be after the edit
+  let title_regex = form.title_regex.clone().unwrap_or(rule.title_regex);
+  let body_regex = form.body_regex.clone().unwrap_or(rule.body_regex);
+  let url_regex = form.url_regex.clone().unwrap_or(rule.url_regex);
+  let reply = form.reply.clone().unwrap_or(rule.reply);
+  let domains = form.domains.as_ref().unwrap_or(&rule.domains);
+  let language_ids = form.language_ids.as_ref().unwrap_or(&rule.language_ids);
+  let actions = form.actions.as_ref().unwrap_or(&rule.actions);
+  check_automod_rule(
+    AutomodRuleCheck {
+      community_id: rule.community_id,
+      regexes: [
+        title_regex.as_deref(),
+        body_regex.as_deref(),
+        url_regex.as_deref(),
+      ],
+      has_other_condition: !domains.is_empty()
+        || !language_ids.is_empty()
+        || form
+          .max_account_age_days
+          .unwrap_or(rule.max_account_age_days)
+          .is_some()
+        || form
+          .max_person_post_score
+          .unwrap_or(rule.max_person_post_score)
+          .is_some(),
+      actions,
+      community_tag_id: form.community_tag_id.unwrap_or(rule.community_tag_id),
+      reply: reply.as_deref(),
+    },
+    &context,
+  )
+  .await?;
+
+  let rule = AutomodRule::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
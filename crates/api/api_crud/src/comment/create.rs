@@ -95,6 +95,10 @@ pub async fn create_comment(
   if locked && !is_mod_or_admin {
     return Err(LemmyErrorType::Locked.into());
   }
+  // The post isn't federated yet, so comments couldn't be federated either
+  if post.approval_pending {
+    return Err(LemmyErrorType::PostPendingApproval.into());
+  }
 
   // If there's a parent_id, check to make sure that comment is in that post
   // Strange issue where sometimes the post ID of the parent comment is incorrect
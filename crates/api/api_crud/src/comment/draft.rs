@@ -0,0 +1,130 @@
+use crate::comment::create::create_comment;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::{
+  newtypes::CommentDraftId,
+  source::comment_draft::{CommentDraft, CommentDraftInsertForm, CommentDraftUpdateForm},
+};
+use lemmy_db_views_comment::api::{
+  CommentDraftResponse,
+  CommentResponse,
+  CreateComment,
+  CreateCommentDraft,
+  DeleteCommentDraft,
+  EditCommentDraft,
+  ListCommentDraftsResponse,
+  PublishCommentDraft,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+const MAX_COMMENT_DRAFTS: i64 = 50;
+
+pub async fn create_comment_draft(
+  Json(data): Json<CreateCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentDraftResponse>> {
+  let content = data.content.unwrap_or_default();
+  is_valid_body_field(&content, false)?;
+  let count = CommentDraft::count(&mut context.pool(), local_user_view.person.id).await?;
+  if count >= MAX_COMMENT_DRAFTS {
+    return Err(LemmyErrorType::TooManyDrafts.into());
+  }
+
+  let form = CommentDraftInsertForm {
+    parent_id: data.parent_id,
+    language_id: data.language_id,
+    ..CommentDraftInsertForm::new(local_user_view.person.id, data.post_id, content)
+  };
+  let draft = CommentDraft::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CommentDraftResponse { draft }))
+}
+
+pub async fn edit_comment_draft(
+  Json(data): Json<EditCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentDraftResponse>> {
+  read_comment_draft(data.draft_id, &local_user_view, &context).await?;
+  if let Some(content) = &data.content {
+    is_valid_body_field(content, false)?;
+  }
+
+  let form = CommentDraftUpdateForm {
+    content: data.content,
+    language_id: data.language_id.map(Some),
+    updated_at: Some(Some(Utc::now())),
+  };
+  let draft = CommentDraft::update(&mut context.pool(), data.draft_id, &form).await?;
+
+  Ok(Json(CommentDraftResponse { draft }))
+}
+
+pub async fn delete_comment_draft(
+  Json(data): Json<DeleteCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  read_comment_draft(data.draft_id, &local_user_view, &context).await?;
+  CommentDraft::delete(&mut context.pool(), data.draft_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+pub async fn list_comment_drafts(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListCommentDraftsResponse>> {
+  let drafts = CommentDraft::list(&mut context.pool(), local_user_view.person.id).await?;
+
+  Ok(Json(ListCommentDraftsResponse { drafts }))
+}
+
+/// Creates a comment from the draft through [create_comment], so that it gets the same validation,
+/// plugin hooks and federation as any other new comment.
+pub async fn publish_comment_draft(
+  Json(data): Json<PublishCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let draft = read_comment_draft(data.draft_id, &local_user_view, &context).await?;
+  let draft_id = draft.id;
+
+  let create = CreateComment {
+    content: draft.content,
+    post_id: draft.post_id,
+    parent_id: draft.parent_id,
+    language_id: draft.language_id,
+  };
+  let res = Box::pin(create_comment(
+    Json(create),
+    context.clone(),
+    local_user_view,
+  ))
+  .await?;
+  CommentDraft::delete(&mut context.pool(), draft_id).await?;
+
+  Ok(res)
+}
+
+/// Reads a draft of the current user. Drafts of other users are treated as nonexistent.
+async fn read_comment_draft(
+  draft_id: CommentDraftId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<CommentDraft> {
+  let draft = CommentDraft::read(&mut context.pool(), draft_id).await?;
+  if draft.creator_id != local_user_view.person.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  Ok(draft)
+}
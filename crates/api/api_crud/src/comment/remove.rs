@@ -3,6 +3,7 @@ use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_comment_response,
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
@@ -12,6 +13,7 @@ use lemmy_db_schema::{
     comment::{Comment, CommentUpdateForm},
     comment_report::CommentReport,
     local_user::LocalUser,
+    mod_template::ModTemplate,
     modlog::{Modlog, ModlogInsertForm},
   },
   traits::Reportable,
@@ -61,6 +63,14 @@ pub async fn remove_comment(
     return Err(LemmyErrorType::CouldntUpdate.into());
   }
 
+  let mod_template = ModTemplate::read_for_action(
+    &mut context.pool(),
+    data.mod_template_id,
+    orig_comment.community.id,
+  )
+  .await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
   let (updated_comment, forms) = if let Some(remove_children) = data.remove_children {
     let updated_comments: Vec<Comment> = Comment::update_removed_for_comment_and_children(
       &mut context.pool(),
@@ -80,14 +90,16 @@ pub async fn remove_comment(
       // Filter out deleted comments here so their content doesn't show up in the modlog.
       .filter(|c| !c.deleted)
       .map(|comment| {
-        ModlogInsertForm::mod_remove_comment(
+        let mut form = ModlogInsertForm::mod_remove_comment(
           local_user_view.person.id,
           comment,
           orig_comment.community.id,
           remove_children,
-          &data.reason,
+          &reason,
           None,
-        )
+        );
+        form.mod_template_id = data.mod_template_id;
+        form
       })
       .collect();
 
@@ -120,14 +132,15 @@ pub async fn remove_comment(
     .await?;
 
     // Mod tables
-    let form = ModlogInsertForm::mod_remove_comment(
+    let mut form = ModlogInsertForm::mod_remove_comment(
       local_user_view.person.id,
       &orig_comment.comment,
       orig_comment.community.id,
       removed,
-      &data.reason,
+      &reason,
       None,
     );
+    form.mod_template_id = data.mod_template_id;
 
     (updated_comment, vec![form])
   };
@@ -137,12 +150,24 @@ pub async fn remove_comment(
 
   let updated_comment_id = updated_comment.id;
 
+  ModActionReply {
+    post: &orig_comment.post,
+    comment: Some(&updated_comment),
+    community: &orig_comment.community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
     SendActivityData::RemoveComment {
       comment: updated_comment,
       moderator: local_user_view.person.clone(),
       community: orig_comment.community,
-      reason: data.reason.clone(),
+      reason,
       with_replies: data.remove_children.unwrap_or_default(),
     },
     &context,
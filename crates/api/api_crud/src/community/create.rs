@@ -114,6 +114,9 @@ pub async fn create_community(
     featured_url: Some(generate_featured_url(&ap_id)?),
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    posts_require_approval: data.posts_require_approval,
+    post_approval_max_account_age_days: data.post_approval_max_account_age_days,
+    post_approval_max_person_post_score: data.post_approval_max_person_post_score,
     ..CommunityInsertForm::new(site.instance_id, data.name.clone(), keypair.public_key)
   };
 
@@ -37,12 +37,14 @@ pub async fn list_communities(
   let res = CommunityQuery {
     listing_type,
     show_nsfw: Some(show_nsfw),
+    nsfw_only: None,
     sort,
     time_range_seconds,
     local_user: local_user.as_ref(),
     multi_community_id,
     search_term,
     search_title_only,
+    sort_by_relevance: None,
     page_cursor,
     limit,
   }
@@ -21,7 +21,10 @@ use lemmy_db_schema::source::{
 use lemmy_db_views_community::api::{CommunityResponse, EditCommunity};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_diesel_utils::{
+  traits::Crud,
+  utils::{diesel_opt_number_update, diesel_string_update},
+};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   utils::{
@@ -91,6 +94,11 @@ pub async fn edit_community(
     nsfw: data.nsfw,
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    posts_require_approval: data.posts_require_approval,
+    post_approval_max_account_age_days: diesel_opt_number_update(
+      data.post_approval_max_account_age_days,
+    ),
+    post_approval_max_person_post_score: data.post_approval_max_person_post_score.map(Some),
     updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
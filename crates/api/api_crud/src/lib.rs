@@ -1,6 +1,8 @@
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::source::community::{Community, CommunityActions};
 
+pub mod api_token;
+pub mod automod;
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
@@ -12,6 +14,7 @@ pub mod private_message;
 pub mod site;
 pub mod tagline;
 pub mod user;
+pub mod webhook;
 
 /// Only mark new posts/comments to remote community as pending if it has any local followers.
 /// Otherwise it could never get updated to be marked as published.
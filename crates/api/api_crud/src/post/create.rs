@@ -2,7 +2,10 @@ use super::convert_published_time;
 use crate::community_use_pending;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::{TimeZone, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
 use lemmy_api_utils::{
+  automod::{AutomodOutcome, run_automod},
   build_response::build_post_response,
   context::LemmyContext,
   notify::NotifyData,
@@ -14,36 +17,44 @@ use lemmy_api_utils::{
     check_nsfw_allowed,
     get_url_blocklist,
     honeypot_check,
+    is_mod_or_admin,
     process_markdown_opt,
     send_webmention,
     slur_regex,
     update_post_tags,
   },
+  webhooks::send_webhooks,
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
-  source::post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  source::{
+    post::{Post, PostActions, PostInsertForm, PostLikeForm},
+    post_poll::{PostPoll, PostPollInsertForm},
+  },
   traits::Likeable,
 };
+use lemmy_db_schema_file::enums::WebhookEventType;
 use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::api::{CreatePost, PostResponse};
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::{traits::Crud, utils::diesel_url_create};
+use lemmy_diesel_utils::{connection::get_conn, traits::Crud, utils::diesel_url_create};
 use lemmy_utils::{
-  error::LemmyResult,
+  error::{LemmyErrorType, LemmyResult},
   utils::{
     slurs::check_slurs,
     validation::{
       is_url_blocked,
       is_valid_alt_text_field,
       is_valid_body_field,
+      is_valid_poll_options,
       is_valid_post_title,
       is_valid_url,
     },
   },
 };
+use tracing::warn;
 
 pub async fn create_post(
   Json(data): Json<CreatePost>,
@@ -88,6 +99,25 @@ pub async fn create_post(
     is_valid_body_field(body, true)?;
   }
 
+  let poll_end_time_at = if let Some(poll) = &data.poll {
+    is_valid_poll_options(&poll.options)?;
+    for option in &poll.options {
+      check_slurs(option, &slur_regex)?;
+    }
+    poll
+      .end_time_at
+      .map(|end_time_at| {
+        Utc
+          .timestamp_opt(end_time_at, 0)
+          .single()
+          .filter(|end_time_at| *end_time_at > Utc::now())
+          .ok_or(LemmyErrorType::InvalidUnixTime)
+      })
+      .transpose()?
+  } else {
+    None
+  };
+
   let community_view = CommunityView::read(
     &mut context.pool(),
     data.community_id,
@@ -117,6 +147,11 @@ pub async fn create_post(
 
   let scheduled_publish_time_at =
     convert_published_time(data.scheduled_publish_time_at, &local_user_view, &context).await?;
+  // Mods and admins don't need approval
+  let approval_pending = community.post_needs_approval(&local_user_view.person)
+    && is_mod_or_admin(&mut context.pool(), &local_user_view, community.id)
+      .await
+      .is_err();
   let mut post_form = PostInsertForm {
     url,
     body,
@@ -125,6 +160,7 @@ pub async fn create_post(
     language_id: data.language_id,
     federation_pending: Some(community_use_pending(community, &context).await),
     scheduled_publish_time_at,
+    approval_pending: Some(approval_pending),
     ..PostInsertForm::new(
       data.name.trim().to_string(),
       local_user_view.person.id,
@@ -140,7 +176,30 @@ pub async fn create_post(
   )
   .await?;
 
-  let inserted_post = Post::create(&mut context.pool(), &post_form).await?;
+  // The poll needs to be created before the post is federated. Both are created in one
+  // transaction, so that a failed poll doesn't leave behind a post without options.
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let poll = data.poll.clone();
+  let inserted_post = conn
+    .run_transaction(|conn| {
+      async move {
+        let inserted_post = Post::create(&mut conn.into(), &post_form).await?;
+        if let Some(poll) = poll {
+          let poll_form = PostPollInsertForm {
+            multiple_choice: poll.multiple_choice,
+            end_time_at: poll_end_time_at,
+            hide_results_until_closed: poll.hide_results_until_closed,
+            ..PostPollInsertForm::new(inserted_post.id)
+          };
+          let options: Vec<_> = poll.options.iter().map(|o| o.trim().to_string()).collect();
+          PostPoll::create_with_options(&mut conn.into(), &poll_form, &options).await?;
+        }
+        Ok(inserted_post)
+      }
+      .scope_boxed()
+    })
+    .await?;
 
   plugin_hook_after("local_post_after_create", &inserted_post);
 
@@ -148,8 +207,30 @@ pub async fn create_post(
     update_post_tags(&inserted_post, tags, &context).await?;
   }
 
+  // Pending posts are federated once they get approved. Automod runs first, as it may put the
+  // post into the approval queue.
+  let publish_now = scheduled_publish_time_at.is_none() && !approval_pending;
+  let automod = if publish_now {
+    run_automod(&inserted_post, community, &context)
+      .await
+      .inspect_err(|e| warn!("Failed to run automod: {e}"))
+      .unwrap_or_default()
+  } else {
+    AutomodOutcome::default()
+  };
+  let approval_pending = approval_pending || automod.held;
+  let publish_now = publish_now && !automod.held;
   let community_id = community.id;
-  let federate_post = if scheduled_publish_time_at.is_none() {
+  // Pending and scheduled posts are sent to webhooks once they get published
+  if publish_now && !automod.removed {
+    send_webhooks(
+      WebhookEventType::PostCreated,
+      Some(inserted_post.community_id),
+      &inserted_post,
+      &context,
+    );
+  }
+  let federate_post = if publish_now {
     send_webmention(inserted_post.clone(), community, context.clone());
     |post| Some(SendActivityData::CreatePost(post))
   } else {
@@ -170,15 +251,17 @@ pub async fn create_post(
 
   PostActions::like(&mut context.pool(), &like_form).await?;
 
-  NotifyData {
-    do_send_email: !local_site.email_notifications_disabled,
-    ..NotifyData::new(
-      inserted_post.clone(),
-      local_user_view.person.clone(),
-      community.clone(),
-    )
+  if !approval_pending {
+    NotifyData {
+      do_send_email: !local_site.email_notifications_disabled,
+      ..NotifyData::new(
+        inserted_post.clone(),
+        local_user_view.person.clone(),
+        community.clone(),
+      )
+    }
+    .send(&context);
   }
-  .send(&context);
 
   PostActions::mark_as_read(&mut context.pool(), person_id, &[post_id]).await?;
 
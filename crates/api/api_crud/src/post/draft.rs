@@ -0,0 +1,173 @@
+use crate::post::create::create_post;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_user_action};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PostDraftId},
+  source::{
+    community::Community,
+    post_draft::{PostDraft, PostDraftInsertForm, PostDraftUpdateForm},
+  },
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{
+  CreatePost,
+  CreatePostDraft,
+  DeletePostDraft,
+  EditPostDraft,
+  ListPostDraftsResponse,
+  PostDraftResponse,
+  PostResponse,
+  PublishPostDraft,
+};
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::{is_valid_alt_text_field, is_valid_body_field, is_valid_post_draft},
+};
+
+const MAX_POST_DRAFTS: i64 = 50;
+
+pub async fn create_post_draft(
+  Json(data): Json<CreatePostDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostDraftResponse>> {
+  let name = data.name.unwrap_or_default();
+  check_post_draft(&name, &data.url, &data.body, &data.alt_text)?;
+  check_draft_community(data.community_id, &local_user_view, &context).await?;
+  let count = PostDraft::count(&mut context.pool(), local_user_view.person.id).await?;
+  if count >= MAX_POST_DRAFTS {
+    return Err(LemmyErrorType::TooManyDrafts.into());
+  }
+
+  let form = PostDraftInsertForm {
+    url: data.url,
+    body: data.body,
+    alt_text: data.alt_text,
+    nsfw: data.nsfw,
+    language_id: data.language_id,
+    ..PostDraftInsertForm::new(local_user_view.person.id, data.community_id, name)
+  };
+  let draft = PostDraft::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(PostDraftResponse { draft }))
+}
+
+pub async fn edit_post_draft(
+  Json(data): Json<EditPostDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostDraftResponse>> {
+  read_post_draft(data.draft_id, &local_user_view, &context).await?;
+  check_post_draft(
+    data.name.as_deref().unwrap_or_default(),
+    &data.url,
+    &data.body,
+    &data.alt_text,
+  )?;
+  if let Some(community_id) = data.community_id {
+    check_draft_community(community_id, &local_user_view, &context).await?;
+  }
+
+  let form = PostDraftUpdateForm {
+    community_id: data.community_id,
+    name: data.name,
+    url: diesel_string_update(data.url.as_deref()),
+    body: diesel_string_update(data.body.as_deref()),
+    alt_text: diesel_string_update(data.alt_text.as_deref()),
+    nsfw: data.nsfw.map(Some),
+    language_id: data.language_id.map(Some),
+    updated_at: Some(Some(Utc::now())),
+  };
+  let draft = PostDraft::update(&mut context.pool(), data.draft_id, &form).await?;
+
+  Ok(Json(PostDraftResponse { draft }))
+}
+
+pub async fn delete_post_draft(
+  Json(data): Json<DeletePostDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  read_post_draft(data.draft_id, &local_user_view, &context).await?;
+  PostDraft::delete(&mut context.pool(), data.draft_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+pub async fn list_post_drafts(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListPostDraftsResponse>> {
+  let drafts = PostDraft::list(&mut context.pool(), local_user_view.person.id).await?;
+
+  Ok(Json(ListPostDraftsResponse { drafts }))
+}
+
+/// Creates a post from the draft through [create_post], so that it gets the same validation,
+/// plugin hooks and federation as any other new post.
+pub async fn publish_post_draft(
+  Json(data): Json<PublishPostDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  let draft = read_post_draft(data.draft_id, &local_user_view, &context).await?;
+  let draft_id = draft.id;
+
+  let create = CreatePost {
+    name: draft.name,
+    community_id: draft.community_id,
+    url: draft.url,
+    body: draft.body,
+    alt_text: draft.alt_text,
+    nsfw: draft.nsfw,
+    language_id: draft.language_id,
+    ..Default::default()
+  };
+  let res = Box::pin(create_post(Json(create), context.clone(), local_user_view)).await?;
+  PostDraft::delete(&mut context.pool(), draft_id).await?;
+
+  Ok(res)
+}
+
+/// Reads a draft of the current user. Drafts of other users are treated as nonexistent.
+async fn read_post_draft(
+  draft_id: PostDraftId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<PostDraft> {
+  let draft = PostDraft::read(&mut context.pool(), draft_id).await?;
+  if draft.creator_id != local_user_view.person.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  Ok(draft)
+}
+
+/// Drafts can only be saved for communities where the user is allowed to post.
+async fn check_draft_community(
+  community_id: CommunityId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  check_community_user_action(local_user_view, &community, &mut context.pool()).await
+}
+
+fn check_post_draft(
+  name: &str,
+  url: &Option<String>,
+  body: &Option<String>,
+  alt_text: &Option<String>,
+) -> LemmyResult<()> {
+  is_valid_post_draft(name, url.as_deref())?;
+  if let Some(body) = body {
+    is_valid_body_field(body, true)?;
+  }
+  if let Some(alt_text) = alt_text {
+    is_valid_alt_text_field(alt_text)?;
+  }
+  Ok(())
+}
@@ -6,6 +6,7 @@ use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub mod create;
 pub mod delete;
+pub mod draft;
 pub mod read;
 pub mod remove;
 pub mod update;
@@ -12,6 +12,7 @@ use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::{
+  PostPollView,
   PostView,
   api::{GetPost, GetPostResponse},
   impls::PostQuery,
@@ -108,11 +109,14 @@ pub async fn get_post(
     Vec::new()
   };
 
+  let poll = PostPollView::read(&mut context.pool(), &post_view.post, person_id).await?;
+
   // Return the jwt
   Ok(Json(GetPostResponse {
     post_view,
     community_view,
     cross_posts,
     moderators,
+    poll,
   }))
 }
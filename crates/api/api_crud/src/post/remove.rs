@@ -3,6 +3,7 @@ use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_post_response,
   context::LemmyContext,
+  mod_template::{ModActionReply, mod_action_reason},
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
@@ -13,6 +14,7 @@ use lemmy_db_schema::{
     comment_report::CommentReport,
     community::Community,
     local_user::LocalUser,
+    mod_template::ModTemplate,
     modlog::{Modlog, ModlogInsertForm},
     post::{Post, PostUpdateForm},
     post_report::PostReport,
@@ -48,6 +50,10 @@ pub async fn remove_post(
   )
   .await?;
 
+  let mod_template =
+    ModTemplate::read_for_action(&mut context.pool(), data.mod_template_id, community.id).await?;
+  let reason = mod_action_reason(&data.reason, mod_template.as_ref());
+
   // Update the post
   let post = Post::update(
     &mut context.pool(),
@@ -63,13 +69,9 @@ pub async fn remove_post(
     .await?;
 
   // Mod tables
-  let form = ModlogInsertForm::mod_remove_post(
-    local_user_view.person.id,
-    &post,
-    remove_post,
-    &data.reason,
-    None,
-  );
+  let mut form =
+    ModlogInsertForm::mod_remove_post(local_user_view.person.id, &post, remove_post, &reason, None);
+  form.mod_template_id = data.mod_template_id;
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(action, context.app_data());
 
@@ -82,14 +84,16 @@ pub async fn remove_post(
       // Filter out deleted comments here so their content doesn't show up in the modlog.
       .filter(|c| !c.deleted)
       .map(|comment| {
-        ModlogInsertForm::mod_remove_comment(
+        let mut form = ModlogInsertForm::mod_remove_comment(
           local_user_view.person.id,
           comment,
           community.id,
           remove_children,
-          &data.reason,
+          &reason,
           None,
-        )
+        );
+        form.mod_template_id = data.mod_template_id;
+        form
       })
       .collect();
 
@@ -100,11 +104,23 @@ pub async fn remove_post(
       .await?;
   }
 
+  ModActionReply {
+    post: &post,
+    comment: None,
+    community: &community,
+    moderator: &local_user_view.person,
+    reason: &reason,
+    reply_with_comment: data.reply_with_comment,
+    reply_with_private_message: data.reply_with_private_message,
+  }
+  .send(&context)
+  .await?;
+
   ActivityChannel::submit_activity(
     SendActivityData::RemovePost {
       post,
       moderator: local_user_view.person.clone(),
-      reason: data.reason.clone(),
+      reason,
       removed: remove_post,
       with_replies: data.remove_children.unwrap_or_default(),
     },
@@ -24,6 +24,7 @@ use lemmy_db_schema::{
   source::{
     community::Community,
     post::{Post, PostUpdateForm},
+    post_revision::PostRevision,
   },
 };
 use lemmy_db_views_local_user::LocalUserView;
@@ -163,6 +164,7 @@ pub async fn edit_post(
 
   let post_id = data.post_id;
   let updated_post = Post::update(&mut context.pool(), post_id, &post_form).await?;
+  PostRevision::create_if_changed(&mut context.pool(), &orig_post.post, &updated_post).await?;
   plugin_hook_after("local_post_after_update", &post_form);
 
   if let Some(tags) = &data.tags {
@@ -181,6 +183,16 @@ pub async fn edit_post(
     orig_post.post.scheduled_publish_time_at,
     data.scheduled_publish_time_at,
   ) {
+    // post is waiting for approval, it gets sent out once approved
+    _ if updated_post.approval_pending => {
+      generate_post_link_metadata(
+        updated_post.clone(),
+        custom_thumbnail.flatten().map(Into::into),
+        |_| None,
+        context.clone(),
+      )
+      .await?
+    }
     // schedule was removed, send create activity and webmention
     (Some(_), None) => {
       let community = Community::read(&mut context.pool(), orig_post.community.id).await?;
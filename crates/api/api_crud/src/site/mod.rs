@@ -10,8 +10,14 @@ pub fn site_default_post_listing_type_check(
   default_post_listing_type: &Option<ListingType>,
 ) -> LemmyResult<()> {
   if let Some(listing_type) = default_post_listing_type {
-    // Dont allow Subscribed or ModeratorView as default listing type
-    if [ListingType::Subscribed, ListingType::ModeratorView].contains(listing_type) {
+    // Dont allow listing types which depend on the user as default
+    if [
+      ListingType::Subscribed,
+      ListingType::ModeratorView,
+      ListingType::ForYou,
+    ]
+    .contains(listing_type)
+    {
       Err(LemmyErrorType::InvalidDefaultPostListingType.into())
     } else {
       Ok(())
@@ -40,6 +46,16 @@ pub fn application_question_check(
   }
 }
 
+/// Checks that the rate limit percentages of the trust tiers are positive. Otherwise all requests
+/// of the tier would be rejected.
+pub fn rate_limit_percent_check(percents: &[Option<i32>]) -> LemmyResult<()> {
+  if percents.iter().flatten().any(|p| *p <= 0) {
+    Err(LemmyErrorType::InvalidRateLimitPercent.into())
+  } else {
+    Ok(())
+  }
+}
+
 fn not_zero(val: Option<i32>) -> Option<i32> {
   match val {
     Some(0) => None,
@@ -50,7 +66,12 @@ fn not_zero(val: Option<i32>) -> Option<i32> {
 #[cfg(test)]
 mod tests {
 
-  use crate::site::{application_question_check, not_zero, site_default_post_listing_type_check};
+  use crate::site::{
+    application_question_check,
+    not_zero,
+    rate_limit_percent_check,
+    site_default_post_listing_type_check,
+  };
   use lemmy_db_schema_file::enums::{ListingType, RegistrationMode};
 
   #[test]
@@ -59,6 +80,14 @@ mod tests {
     assert!(site_default_post_listing_type_check(&Some(ListingType::All)).is_ok());
     assert!(site_default_post_listing_type_check(&Some(ListingType::Local)).is_ok());
     assert!(site_default_post_listing_type_check(&Some(ListingType::Subscribed)).is_err());
+    assert!(site_default_post_listing_type_check(&Some(ListingType::ForYou)).is_err());
+  }
+
+  #[test]
+  fn test_rate_limit_percent_check() {
+    assert!(rate_limit_percent_check(&[None, Some(50), Some(200)]).is_ok());
+    assert!(rate_limit_percent_check(&[Some(100), Some(0)]).is_err());
+    assert!(rate_limit_percent_check(&[Some(-10)]).is_err());
   }
 
   #[test]
@@ -1,5 +1,9 @@
 use super::not_zero;
-use crate::site::{application_question_check, site_default_post_listing_type_check};
+use crate::site::{
+  application_question_check,
+  rate_limit_percent_check,
+  site_default_post_listing_type_check,
+};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
@@ -9,6 +13,7 @@ use lemmy_api_utils::{
     get_url_blocklist,
     is_admin,
     local_site_rate_limit_to_rate_limit_config,
+    local_site_rate_limit_to_trust_tier_config,
     process_markdown_opt,
     slur_regex,
   },
@@ -164,6 +169,21 @@ pub async fn edit_site(
     import_user_settings_interval_seconds: not_zero(
       data.rate_limit_import_user_settings_interval_seconds,
     ),
+    new_account_max_age_days: data.rate_limit_new_account_max_age_days,
+    new_account_limit_percent: data.rate_limit_new_account_limit_percent,
+    established_account_limit_percent: data.rate_limit_established_account_limit_percent,
+    bot_account_limit_percent: data.rate_limit_bot_account_limit_percent,
+    moderator_limit_percent: data.rate_limit_moderator_limit_percent,
+    vote_max_requests: data.rate_limit_vote_max_requests,
+    vote_interval_seconds: not_zero(data.rate_limit_vote_interval_seconds),
+    follow_max_requests: data.rate_limit_follow_max_requests,
+    follow_interval_seconds: not_zero(data.rate_limit_follow_interval_seconds),
+    report_max_requests: data.rate_limit_report_max_requests,
+    report_interval_seconds: not_zero(data.rate_limit_report_interval_seconds),
+    private_message_max_requests: data.rate_limit_private_message_max_requests,
+    private_message_interval_seconds: not_zero(data.rate_limit_private_message_interval_seconds),
+    resolve_object_max_requests: data.rate_limit_resolve_object_max_requests,
+    resolve_object_interval_seconds: not_zero(data.rate_limit_resolve_object_interval_seconds),
     updated_at: Some(Some(Utc::now())),
   };
 
@@ -207,6 +227,11 @@ pub async fn edit_site(
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
   context.rate_limit_cell().set_config(rate_limit_config);
+  context
+    .rate_limit_cell()
+    .set_trust_tier_config(local_site_rate_limit_to_trust_tier_config(
+      &site_view.local_site_rate_limit,
+    ));
 
   Ok(Json(SiteResponse { site_view }))
 }
@@ -233,6 +258,12 @@ fn validate_update_payload(local_site: &LocalSite, edit_site: &EditSite) -> Lemm
   }
 
   site_default_post_listing_type_check(&edit_site.default_post_listing_type)?;
+  rate_limit_percent_check(&[
+    edit_site.rate_limit_new_account_limit_percent,
+    edit_site.rate_limit_established_account_limit_percent,
+    edit_site.rate_limit_bot_account_limit_percent,
+    edit_site.rate_limit_moderator_limit_percent,
+  ])?;
 
   // Ensure that the sidebar has fewer than the max num characters...
   if let Some(sidebar) = &edit_site.sidebar {
@@ -21,6 +21,7 @@ use lemmy_api_utils::{
     password_length_check,
     slur_regex,
   },
+  webhooks::send_webhooks,
 };
 use lemmy_apub_objects::objects::community::ApubCommunity;
 use lemmy_db_schema::{
@@ -40,11 +41,14 @@ use lemmy_db_schema::{
   },
   traits::{ApubActor, Likeable},
 };
-use lemmy_db_schema_file::enums::RegistrationMode;
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{RegistrationMode, WebhookEventType},
+};
 use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::PersonView;
-use lemmy_db_views_registration_applications::api::Register;
+use lemmy_db_views_registration_applications::{RegistrationApplicationView, api::Register};
 use lemmy_db_views_site::{
   SiteView,
   api::{AuthenticateWithOauth, LoginResponse},
@@ -222,6 +226,10 @@ pub async fn register(
     })
     .await?;
 
+  if local_site.site_setup && require_registration_application {
+    send_registration_application_webhook(user.person.id, &context);
+  }
+
   // Email the admins, only if email verification is not required
   if local_site.application_email_admins && !local_site.email_verification_required {
     send_new_applicant_email_to_admins(&data.username, pool, context.settings()).await?;
@@ -488,6 +496,10 @@ pub async fn authenticate_with_oauth(
         })
         .await?;
 
+      if login_response.registration_created {
+        send_registration_application_webhook(user.person.id, &context);
+      }
+
       // Check email is verified when required
       login_response.verify_email_sent = send_verification_email_if_required(
         &local_site,
@@ -754,6 +766,21 @@ fn fetch_community_list(context: Data<LemmyContext>) {
   })
 }
 
+fn send_registration_application_webhook(person_id: PersonId, context: &LemmyContext) {
+  let context = context.clone();
+  spawn_try_task(async move {
+    let application =
+      RegistrationApplicationView::read_by_person(&mut context.pool(), person_id).await?;
+    send_webhooks(
+      WebhookEventType::RegistrationApplicationCreated,
+      None,
+      &application,
+      &context,
+    );
+    Ok(())
+  })
+}
+
 fn create_welcome_post(local_user: LocalUser, context: &LemmyContext) {
   let context = context.clone();
 
@@ -8,10 +8,13 @@ use lemmy_api_utils::{
   utils::purge_user_account,
 };
 use lemmy_db_schema::source::{
+  api_token::ApiToken,
+  comment_draft::CommentDraft,
   community::CommunityActions,
   login_token::LoginToken,
   oauth_account::OAuthAccount,
   person::Person,
+  post_draft::PostDraft,
 };
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{DeleteAccount, DeleteUserForm, SuccessResponse};
@@ -68,6 +71,9 @@ pub async fn delete_account(
   plugin_hook_after("local_user_after_delete", &form);
 
   LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+  ApiToken::delete_all(&mut context.pool(), local_user_view.local_user.id).await?;
+  PostDraft::delete_all(&mut context.pool(), local_user_view.person.id).await?;
+  CommentDraft::delete_all(&mut context.pool(), local_user_view.person.id).await?;
 
   ActivityChannel::submit_activity(
     SendActivityData::DeleteUser(local_user_view.person, data.delete_content),
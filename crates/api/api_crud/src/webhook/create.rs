@@ -0,0 +1,28 @@
+use super::{check_webhook_events, check_webhook_permission, check_webhook_url};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateWebhook, WebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{error::LemmyError, utils::validation::is_valid_webhook_secret};
+
+pub async fn create_webhook(
+  Json(data): Json<CreateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> Result<Json<WebhookResponse>, LemmyError> {
+  check_webhook_permission(data.community_id, &local_user_view, &context).await?;
+  check_webhook_events(&data.events, data.community_id)?;
+  is_valid_webhook_secret(&data.secret)?;
+  let url = check_webhook_url(&data.url, &context).await?;
+
+  let form = WebhookInsertForm {
+    community_id: data.community_id,
+    ..WebhookInsertForm::new(local_user_view.person.id, url, data.secret, data.events)
+  };
+  let webhook = Webhook::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
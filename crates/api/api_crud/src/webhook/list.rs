@@ -0,0 +1,19 @@
+use super::check_webhook_permission;
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::Webhook;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ListWebhooks, ListWebhooksResponse};
+use lemmy_utils::error::LemmyError;
+
+pub async fn list_webhooks(
+  Query(data): Query<ListWebhooks>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> Result<Json<ListWebhooksResponse>, LemmyError> {
+  check_webhook_permission(data.community_id, &local_user_view, &context).await?;
+
+  let webhooks = Webhook::list(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListWebhooksResponse { webhooks }))
+}
@@ -0,0 +1,27 @@
+use super::check_webhook_permission;
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookDelivery};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListWebhookDeliveries;
+use lemmy_diesel_utils::{pagination::PagedResponse, traits::Crud};
+use lemmy_utils::error::LemmyError;
+
+pub async fn list_webhook_deliveries(
+  Query(data): Query<ListWebhookDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> Result<Json<PagedResponse<WebhookDelivery>>, LemmyError> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id).await?;
+  check_webhook_permission(webhook.community_id, &local_user_view, &context).await?;
+
+  let deliveries = WebhookDelivery::list(
+    &mut context.pool(),
+    data.webhook_id,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(deliveries))
+}
@@ -0,0 +1,59 @@
+use activitypub_federation::config::Data;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, is_admin},
+};
+use lemmy_db_schema::{newtypes::CommunityId, source::community::Community};
+use lemmy_db_schema_file::enums::WebhookEventType;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_webhook_url,
+};
+use url::Url;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod list_deliveries;
+pub mod update;
+
+/// Site-wide webhooks can only be managed by admins, community webhooks also by the community
+/// mods.
+async fn check_webhook_permission(
+  community_id: Option<CommunityId>,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match community_id {
+    None => is_admin(local_user_view),
+    Some(community_id) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await
+    }
+  }
+}
+
+/// Registration applications don't belong to any community, so they are only available for
+/// site-wide webhooks.
+fn check_webhook_events(
+  events: &[WebhookEventType],
+  community_id: Option<CommunityId>,
+) -> LemmyResult<()> {
+  if events.is_empty()
+    || (community_id.is_some()
+      && events.contains(&WebhookEventType::RegistrationApplicationCreated))
+  {
+    return Err(LemmyErrorType::InvalidWebhookEvents.into());
+  }
+  Ok(())
+}
+
+/// Also rejects urls which point to the local network.
+async fn check_webhook_url(url: &str, context: &Data<LemmyContext>) -> LemmyResult<DbUrl> {
+  let url = Url::parse(url)?;
+  is_valid_webhook_url(&url)?;
+  context.is_valid_ip(&url).await?;
+  Ok(url.into())
+}
@@ -0,0 +1,41 @@
+use super::{check_webhook_events, check_webhook_permission, check_webhook_url};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookUpdateForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{EditWebhook, WebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{error::LemmyError, utils::validation::is_valid_webhook_secret};
+
+pub async fn edit_webhook(
+  Json(data): Json<EditWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> Result<Json<WebhookResponse>, LemmyError> {
+  let webhook = Webhook::read(&mut context.pool(), data.id).await?;
+  check_webhook_permission(webhook.community_id, &local_user_view, &context).await?;
+
+  if let Some(events) = &data.events {
+    check_webhook_events(events, webhook.community_id)?;
+  }
+  if let Some(secret) = &data.secret {
+    is_valid_webhook_secret(secret)?;
+  }
+  let url = match &data.url {
+    Some(url) => Some(check_webhook_url(url, &context).await?),
+    None => None,
+  };
+
+  let form = WebhookUpdateForm {
+    url,
+    secret: data.secret,
+    events: data.events,
+    enabled: data.enabled,
+    updated_at: Some(Some(Utc::now())),
+  };
+  let webhook = Webhook::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
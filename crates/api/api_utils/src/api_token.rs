@@ -0,0 +1,266 @@
+use crate::{context::LemmyContext, utils::check_local_user_deleted};
+use actix_web::http::Method;
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::api_token::{ApiToken, ApiTokenScope},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+
+/// Distinguishes API tokens from login JWTs.
+pub const API_TOKEN_PREFIX: &str = "lemmy_pat_";
+
+/// Paths below `/account` which can be used with an API token. All other account paths can only be
+/// used with a login session. This prevents API tokens from creating new tokens or taking over the
+/// account, including through account endpoints which are added later.
+const TOKEN_ACCOUNT_PATHS: [&str; 9] = [
+  "/account/unread_counts",
+  "/account/media",
+  "/account/notification",
+  "/account/validate_auth",
+  "/account/block",
+  "/account/saved",
+  "/account/read",
+  "/account/hidden",
+  "/account/liked",
+];
+
+const ADMIN_PATHS: [&str; 4] = ["/admin", "/custom_emoji", "/oauth_provider", "/site"];
+
+const MODERATE_PATHS: [&str; 31] = [
+  "/post/remove",
+  "/post/lock",
+  "/post/feature",
+  "/post/mod_edit",
+  "/post/warn",
+  "/post/report/resolve",
+  "/comment/remove",
+  "/comment/distinguish",
+  "/comment/lock",
+  "/comment/warn",
+  "/comment/report/resolve",
+  "/community/transfer",
+  "/community/ban_user",
+  "/community/mod",
+  "/community/icon",
+  "/community/banner",
+  "/community/tag",
+  "/community/flair",
+  "/community/rule",
+  "/community/mod_template",
+  "/community/mod_template/list",
+  "/community/automod",
+  "/community/automod/list",
+  "/community/pending_follows/list",
+  "/community/pending_follows/approve",
+  "/community/pending_posts/list",
+  "/community/pending_posts/approve",
+  "/report/list",
+  "/webhook",
+  "/webhook/list",
+  "/webhook/delivery/list",
+];
+
+const VOTE_PATHS: [&str; 3] = ["/post/like", "/comment/like", "/post/poll/vote"];
+
+tokio::task_local! {
+  /// Scopes of the API token which authenticated the current request. Not set for login sessions.
+  static API_TOKEN_SCOPES: Vec<ApiTokenScope>;
+}
+
+/// The kind of scope which an API call needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequiredScope {
+  Read,
+  Post,
+  Vote,
+  Moderate,
+  Admin,
+}
+
+impl Display for RequiredScope {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let name = match self {
+      RequiredScope::Read => "read",
+      RequiredScope::Post => "post",
+      RequiredScope::Vote => "vote",
+      RequiredScope::Moderate => "moderate",
+      RequiredScope::Admin => "admin",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl RequiredScope {
+  fn is_granted_by(self, scope: &ApiTokenScope) -> bool {
+    matches!(
+      (self, scope),
+      (_, ApiTokenScope::Admin)
+        | (RequiredScope::Moderate, ApiTokenScope::ModerateCommunity(_))
+        | (RequiredScope::Read, ApiTokenScope::Read)
+        | (RequiredScope::Post, ApiTokenScope::Post)
+        | (RequiredScope::Vote, ApiTokenScope::Vote)
+    )
+  }
+}
+
+/// The token is only stored as hash, so that it can't be used if the database is leaked.
+pub fn hash_api_token(token: &str) -> String {
+  hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+pub fn is_api_token(token: &str) -> bool {
+  token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// Reads the user of an API token. Unless the token has the `admin` scope, the user is treated as a
+/// normal user, so that handlers don't give it any admin powers.
+pub async fn local_user_view_from_api_token(
+  token: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, ApiToken)> {
+  let api_token = ApiToken::read_by_hash(&mut context.pool(), &hash_api_token(token)).await?;
+  let mut local_user_view =
+    LocalUserView::read(&mut context.pool(), api_token.local_user_id).await?;
+  check_local_user_deleted(&local_user_view)?;
+  ApiToken::mark_used(&mut context.pool(), api_token.id).await?;
+
+  if !api_token.scopes.contains(&ApiTokenScope::Admin) {
+    local_user_view.local_user.admin = false;
+  }
+  Ok((local_user_view, api_token))
+}
+
+/// Checks that the API call is covered by the token scopes, based on the request method and path.
+pub fn check_api_token_scope(
+  scopes: &[ApiTokenScope],
+  method: &Method,
+  path: &str,
+) -> LemmyResult<()> {
+  let path = path.strip_prefix("/api/v4").unwrap_or(path);
+  let matches = |p: &&str| path == *p || path.starts_with(&format!("{p}/"));
+  let login_only = if path == "/account" {
+    method == Method::DELETE
+  } else {
+    path.starts_with("/account/") && !TOKEN_ACCOUNT_PATHS.iter().any(matches)
+  };
+  if login_only {
+    return Err(LemmyErrorType::ApiTokenNotAllowed.into());
+  }
+
+  let required = if ADMIN_PATHS.iter().any(matches) && !(path == "/site" && method == Method::GET) {
+    RequiredScope::Admin
+  } else if MODERATE_PATHS.contains(&path) {
+    RequiredScope::Moderate
+  } else if VOTE_PATHS.contains(&path) {
+    RequiredScope::Vote
+  } else if method == Method::GET || method == Method::HEAD {
+    RequiredScope::Read
+  } else {
+    RequiredScope::Post
+  };
+
+  if scopes.iter().any(|s| required.is_granted_by(s)) {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::MissingApiTokenScope(required.to_string()).into())
+  }
+}
+
+/// Runs the request handler with the scopes of the API token, so that they can be checked with
+/// [check_api_token_community_scope].
+pub async fn with_api_token_scopes<F: Future>(scopes: Vec<ApiTokenScope>, f: F) -> F::Output {
+  API_TOKEN_SCOPES.scope(scopes, f).await
+}
+
+/// Mod actions with an API token need the `moderate:community/<id>` scope for the given community,
+/// or the `admin` scope. Requests authenticated by login session are always allowed.
+pub fn check_api_token_community_scope(community_id: CommunityId) -> LemmyResult<()> {
+  API_TOKEN_SCOPES
+    .try_with(|scopes| {
+      let allowed = scopes.iter().any(|s| {
+        s == &ApiTokenScope::Admin || s == &ApiTokenScope::ModerateCommunity(community_id)
+      });
+      if allowed {
+        Ok(())
+      } else {
+        Err(
+          LemmyErrorType::MissingApiTokenScope(
+            ApiTokenScope::ModerateCommunity(community_id).to_string(),
+          )
+          .into(),
+        )
+      }
+    })
+    .unwrap_or(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_check_api_token_scope() {
+    let read = [ApiTokenScope::Read];
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/post/list").is_ok());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/site").is_ok());
+    assert!(check_api_token_scope(&read, &Method::POST, "/api/v4/post").is_err());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/admin/users").is_err());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account").is_ok());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account/saved").is_ok());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account/media/list").is_ok());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account/token/list").is_err());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account/login/list").is_err());
+    assert!(check_api_token_scope(&read, &Method::GET, "/api/v4/account/savedx").is_err());
+
+    let post = [ApiTokenScope::Post];
+    assert!(check_api_token_scope(&post, &Method::POST, "/api/v4/post").is_ok());
+    assert!(check_api_token_scope(&post, &Method::PUT, "/api/v4/comment").is_ok());
+    assert!(check_api_token_scope(&post, &Method::POST, "/api/v4/post/like").is_err());
+    assert!(check_api_token_scope(&post, &Method::POST, "/api/v4/post/remove").is_err());
+    assert!(check_api_token_scope(&post, &Method::PUT, "/api/v4/site").is_err());
+    assert!(check_api_token_scope(&post, &Method::DELETE, "/api/v4/account").is_err());
+    assert!(check_api_token_scope(&post, &Method::PUT, "/api/v4/account/aliases").is_err());
+
+    let vote = [ApiTokenScope::Vote];
+    assert!(check_api_token_scope(&vote, &Method::POST, "/api/v4/comment/like").is_ok());
+    assert!(check_api_token_scope(&vote, &Method::POST, "/api/v4/post/poll/vote").is_ok());
+
+    let moderate = [ApiTokenScope::ModerateCommunity(CommunityId(1))];
+    assert!(check_api_token_scope(&moderate, &Method::POST, "/api/v4/post/remove").is_ok());
+    assert!(check_api_token_scope(&moderate, &Method::GET, "/api/v4/report/list").is_ok());
+    assert!(check_api_token_scope(&moderate, &Method::GET, "/api/v4/post/list").is_err());
+
+    let admin = [ApiTokenScope::Admin];
+    assert!(check_api_token_scope(&admin, &Method::POST, "/api/v4/admin/ban").is_ok());
+    assert!(check_api_token_scope(&admin, &Method::POST, "/api/v4/post/remove").is_ok());
+    assert_eq!(
+      Some(LemmyErrorType::ApiTokenNotAllowed),
+      check_api_token_scope(&admin, &Method::POST, "/api/v4/account/token")
+        .err()
+        .map(|e| e.error_type)
+    );
+    assert_eq!(
+      Some(LemmyErrorType::ApiTokenNotAllowed),
+      check_api_token_scope(&admin, &Method::POST, "/api/v4/account/move")
+        .err()
+        .map(|e| e.error_type)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_api_token_community_scope() {
+    // Login sessions are not restricted
+    assert!(check_api_token_community_scope(CommunityId(1)).is_ok());
+
+    let scopes = vec![ApiTokenScope::ModerateCommunity(CommunityId(1))];
+    with_api_token_scopes(scopes, async {
+      assert!(check_api_token_community_scope(CommunityId(1)).is_ok());
+      assert!(check_api_token_community_scope(CommunityId(2)).is_err());
+    })
+    .await;
+  }
+}
@@ -0,0 +1,406 @@
+use crate::{
+  context::LemmyContext,
+  notify::{NotifyData, notify_mod_action},
+  send_activity::{ActivityChannel, SendActivityData},
+  webhooks::{WebhookReport, send_webhooks},
+};
+use activitypub_federation::config::Data;
+use chrono::{DateTime, Duration, Utc};
+use lemmy_db_schema::{
+  newtypes::AutomodRuleId,
+  source::{
+    automod_rule::AutomodRule,
+    comment::{Comment, CommentInsertForm},
+    community::Community,
+    community_tag::PostCommunityTag,
+    modlog::{Modlog, ModlogInsertForm},
+    person::Person,
+    post::{Post, PostUpdateForm},
+    post_report::{PostReport, PostReportForm},
+  },
+  traits::Reportable,
+};
+use lemmy_db_schema_file::enums::{AutomodAction, WebhookEventType};
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+use moka::future::Cache;
+use regex::{Regex, RegexBuilder};
+use std::{collections::HashSet, sync::LazyLock};
+use url::Url;
+
+/// What [run_automod] did with a post.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AutomodOutcome {
+  /// The post was held for approval. Such posts must not be federated or notified about until a
+  /// mod approves them.
+  pub held: bool,
+  /// The post was removed.
+  pub removed: bool,
+}
+
+/// Checks a new post against the automod rules of its community, and takes the actions of all
+/// matching rules in the name of the system account. Only runs for local communities, because
+/// other instances only accept mod actions from the community instance. Posts by mods and admins
+/// are never checked.
+pub async fn run_automod(
+  post: &Post,
+  community: &Community,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<AutomodOutcome> {
+  if !community.local {
+    return Ok(AutomodOutcome::default());
+  }
+  let rules = AutomodRule::list_enabled(&mut context.pool(), community.id).await?;
+  if rules.is_empty() {
+    return Ok(AutomodOutcome::default());
+  }
+
+  let creator = Person::read(&mut context.pool(), post.creator_id).await?;
+  let is_mod = CommunityModeratorView::check_is_community_moderator(
+    &mut context.pool(),
+    community.id,
+    creator.id,
+  )
+  .await
+  .is_ok();
+  let is_admin = LocalUserView::read_person(&mut context.pool(), creator.id)
+    .await
+    .is_ok_and(|l| l.local_user.admin);
+  if is_mod || is_admin {
+    return Ok(AutomodOutcome::default());
+  }
+
+  let mut matching = vec![];
+  for rule in &rules {
+    let regexes = rule_regexes(rule).await;
+    if rule_matches(rule, &regexes, post, &creator) {
+      matching.push(rule);
+    }
+  }
+  if matching.is_empty() {
+    return Ok(AutomodOutcome::default());
+  }
+
+  let system_account = SiteView::read_system_account(&mut context.pool()).await?;
+  let names: Vec<_> = matching.iter().map(|r| r.name.as_str()).collect();
+  let reason = format!("Automod: {}", names.join(", "));
+  let actions: HashSet<_> = matching.iter().flat_map(|r| r.actions.clone()).collect();
+  let remove = actions.contains(&AutomodAction::Remove);
+  // Removal takes precedence, there is nothing to approve for a removed post.
+  let hold = !remove && actions.contains(&AutomodAction::HoldForApproval);
+
+  // Held posts go into the approval queue of the community, and are federated once approved.
+  // Everything else done here is then only sent out together with the post.
+  if hold {
+    let form = PostUpdateForm {
+      approval_pending: Some(true),
+      ..Default::default()
+    };
+    let post = Post::update(&mut context.pool(), post.id, &form).await?;
+    log_action(
+      &system_account,
+      &post,
+      &reason,
+      AutomodAction::HoldForApproval,
+      context,
+    )
+    .await?;
+  }
+
+  // Tags and replies come first, so that they are federated before a possible removal.
+  let tag_ids: Vec<_> = matching
+    .iter()
+    .filter(|r| r.actions.contains(&AutomodAction::ApplyTag))
+    .filter_map(|r| r.community_tag_id)
+    .collect();
+  for tag_id in &tag_ids {
+    PostCommunityTag::add(&mut context.pool(), post.id, *tag_id).await?;
+  }
+  if !tag_ids.is_empty() {
+    log_action(
+      &system_account,
+      post,
+      &reason,
+      AutomodAction::ApplyTag,
+      context,
+    )
+    .await?;
+    if !hold {
+      ActivityChannel::submit_activity(SendActivityData::UpdatePost(post.clone()), context)?;
+    }
+  }
+
+  let replies: Vec<_> = matching
+    .iter()
+    .filter(|r| r.actions.contains(&AutomodAction::Reply))
+    .filter_map(|r| r.reply.as_deref())
+    .collect();
+  if !replies.is_empty() {
+    reply(
+      post,
+      community,
+      &system_account,
+      replies.join("\n\n"),
+      !hold,
+      context,
+    )
+    .await?;
+    log_action(
+      &system_account,
+      post,
+      &reason,
+      AutomodAction::Reply,
+      context,
+    )
+    .await?;
+  }
+
+  if actions.contains(&AutomodAction::Lock) {
+    let form = PostUpdateForm {
+      locked: Some(true),
+      ..Default::default()
+    };
+    let post = Post::update(&mut context.pool(), post.id, &form).await?;
+    let form = ModlogInsertForm::mod_lock_post(system_account.id, &post, true, &reason);
+    let action = Modlog::create(&mut context.pool(), &[form]).await?;
+    notify_mod_action(action, context);
+    if !hold {
+      ActivityChannel::submit_activity(
+        SendActivityData::LockPost(post, system_account.clone(), true, reason.clone()),
+        context,
+      )?;
+    }
+  }
+
+  if remove {
+    let form = PostUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    let post = Post::update(&mut context.pool(), post.id, &form).await?;
+    let form = ModlogInsertForm::mod_remove_post(system_account.id, &post, true, &reason, None);
+    let action = Modlog::create(&mut context.pool(), &[form]).await?;
+    notify_mod_action(action, context);
+    ActivityChannel::submit_activity(
+      SendActivityData::RemovePost {
+        post,
+        moderator: system_account.clone(),
+        reason: reason.clone(),
+        removed: true,
+        with_replies: false,
+      },
+      context,
+    )?;
+  }
+
+  if actions.contains(&AutomodAction::Report) {
+    report(post, &system_account, reason.clone(), context).await?;
+    log_action(
+      &system_account,
+      post,
+      &reason,
+      AutomodAction::Report,
+      context,
+    )
+    .await?;
+  }
+  Ok(AutomodOutcome {
+    held: hold,
+    removed: remove,
+  })
+}
+
+/// Writes the modlog entry for an action which has no modlog kind of its own.
+async fn log_action(
+  system_account: &Person,
+  post: &Post,
+  reason: &str,
+  action: AutomodAction,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let reason = format!("{reason} ({action})");
+  let form = ModlogInsertForm::mod_automod_post(system_account.id, post, &reason);
+  let action = Modlog::create(&mut context.pool(), &[form]).await?;
+  notify_mod_action(action, context);
+  Ok(())
+}
+
+fn rule_matches(rule: &AutomodRule, regexes: &RuleRegexes, post: &Post, creator: &Person) -> bool {
+  let url = post.url.as_ref().map(|u| u.as_str());
+  let domain_matches = rule.domains.is_empty()
+    || post
+      .url
+      .as_ref()
+      .is_some_and(|u| url_has_domain(u, &rule.domains));
+  let language_matches =
+    rule.language_ids.is_empty() || rule.language_ids.contains(&post.language_id);
+  let account_age_matches = rule
+    .max_account_age_days
+    .is_none_or(|days| creator.published_at > Utc::now() - Duration::days(days.into()));
+  let post_score_matches = rule
+    .max_person_post_score
+    .is_none_or(|score| creator.post_score <= score);
+
+  regexes.title.matches(Some(&post.name))
+    && regexes.body.matches(post.body.as_deref())
+    && regexes.url.matches(url)
+    && domain_matches
+    && language_matches
+    && account_age_matches
+    && post_score_matches
+}
+
+#[derive(Clone)]
+struct RuleRegexes {
+  title: RuleRegex,
+  body: RuleRegex,
+  url: RuleRegex,
+}
+
+/// Compiling the regexes of every rule for every post is expensive, so they are cached. Rules are
+/// keyed by their update time, so that edited rules are compiled again.
+async fn rule_regexes(rule: &AutomodRule) -> RuleRegexes {
+  type RuleVersion = (AutomodRuleId, Option<DateTime<Utc>>);
+  static CACHE: LazyLock<Cache<RuleVersion, RuleRegexes>> =
+    LazyLock::new(|| Cache::builder().max_capacity(10000).build());
+  CACHE
+    .get_with((rule.id, rule.updated_at), async {
+      RuleRegexes {
+        title: RuleRegex::new(rule.title_regex.as_deref()),
+        body: RuleRegex::new(rule.body_regex.as_deref()),
+        url: RuleRegex::new(rule.url_regex.as_deref()),
+      }
+    })
+    .await
+}
+
+#[derive(Clone)]
+enum RuleRegex {
+  Missing,
+  Invalid,
+  Valid(Regex),
+}
+
+impl RuleRegex {
+  fn new(regex: Option<&str>) -> Self {
+    match regex.map(|r| RegexBuilder::new(r).case_insensitive(true).build()) {
+      None => RuleRegex::Missing,
+      Some(Ok(regex)) => RuleRegex::Valid(regex),
+      Some(Err(_)) => RuleRegex::Invalid,
+    }
+  }
+
+  /// A missing regex matches everything, but a regex never matches missing text.
+  fn matches(&self, text: Option<&str>) -> bool {
+    match self {
+      RuleRegex::Missing => true,
+      RuleRegex::Invalid => false,
+      RuleRegex::Valid(regex) => text.is_some_and(|t| regex.is_match(t)),
+    }
+  }
+}
+
+/// Also matches subdomains of the given domains.
+fn url_has_domain(url: &Url, domains: &[String]) -> bool {
+  let Some(host) = url.domain() else {
+    return false;
+  };
+  domains.iter().any(|d| {
+    host.eq_ignore_ascii_case(d)
+      || host
+        .to_lowercase()
+        .ends_with(&format!(".{}", d.to_lowercase()))
+  })
+}
+
+async fn reply(
+  post: &Post,
+  community: &Community,
+  system_account: &Person,
+  content: String,
+  federate: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let form = CommentInsertForm {
+    distinguished: Some(true),
+    ..CommentInsertForm::new(system_account.id, post.id, community.id, content)
+  };
+  let comment = Comment::create(&mut context.pool(), &form, None).await?;
+
+  NotifyData {
+    comment: Some(comment.clone()),
+    ..NotifyData::new(post.clone(), system_account.clone(), community.clone())
+  }
+  .send(context);
+  if federate {
+    ActivityChannel::submit_activity(SendActivityData::CreateComment(comment), context)?;
+  }
+  Ok(())
+}
+
+async fn report(
+  post: &Post,
+  system_account: &Person,
+  reason: String,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let form = PostReportForm {
+    creator_id: system_account.id,
+    post_id: post.id,
+    original_post_name: post.name.clone(),
+    original_post_url: post.url.clone(),
+    original_post_body: post.body.clone(),
+    reason,
+    violates_instance_rules: false,
+    rule_id: None,
+  };
+  let report = PostReport::report(&mut context.pool(), &form).await?;
+  send_webhooks(
+    WebhookEventType::ReportCreated,
+    Some(post.community_id),
+    &WebhookReport::PostReport(&report),
+    context,
+  );
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rule_regex_matches() {
+    assert!(RuleRegex::new(None).matches(None));
+    assert!(RuleRegex::new(None).matches(Some("anything")));
+    assert!(RuleRegex::new(Some("buy (now|today)")).matches(Some("BUY NOW cheap")));
+    assert!(!RuleRegex::new(Some("buy (now|today)")).matches(Some("a normal title")));
+    assert!(!RuleRegex::new(Some("buy")).matches(None));
+    // Invalid regexes never match
+    assert!(!RuleRegex::new(Some("(")).matches(Some("(")));
+  }
+
+  #[test]
+  fn test_url_has_domain() -> LemmyResult<()> {
+    let domains = vec!["spam.example".to_string()];
+    assert!(url_has_domain(
+      &Url::parse("https://spam.example/page")?,
+      &domains
+    ));
+    assert!(url_has_domain(
+      &Url::parse("https://www.SPAM.example/page")?,
+      &domains
+    ));
+    assert!(!url_has_domain(
+      &Url::parse("https://notspam.example/page")?,
+      &domains
+    ));
+    assert!(!url_has_domain(
+      &Url::parse("https://127.0.0.1/")?,
+      &domains
+    ));
+    Ok(())
+  }
+}
@@ -1,8 +1,12 @@
+pub mod api_token;
+pub mod automod;
 pub mod build_response;
 pub mod claims;
 pub mod context;
+pub mod mod_template;
 pub mod notify;
 pub mod plugins;
 pub mod request;
 pub mod send_activity;
 pub mod utils;
+pub mod webhooks;
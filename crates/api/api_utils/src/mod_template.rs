@@ -0,0 +1,89 @@
+use crate::{
+  context::LemmyContext,
+  notify::{NotifyData, notify_private_message},
+  send_activity::{ActivityChannel, SendActivityData},
+};
+use activitypub_federation::config::Data;
+use lemmy_db_schema::source::{
+  comment::{Comment, CommentInsertForm},
+  community::Community,
+  mod_template::ModTemplate,
+  person::Person,
+  post::Post,
+  private_message::{PrivateMessage, PrivateMessageInsertForm},
+};
+use lemmy_db_views_private_message::PrivateMessageView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+/// The reason of a mod action. If the moderator selected a template without typing a reason, the
+/// template body is used.
+pub fn mod_action_reason(reason: &str, mod_template: Option<&ModTemplate>) -> String {
+  match mod_template {
+    Some(t) if reason.trim().is_empty() => t.body.clone(),
+    _ => reason.to_string(),
+  }
+}
+
+/// Lets the creator know about a mod action on their post or comment, by replying with the reason
+/// as distinguished and locked comment, and/or sending it as private message.
+pub struct ModActionReply<'a> {
+  pub post: &'a Post,
+  /// Set if the action was taken on a comment, the reply is then made to this comment.
+  pub comment: Option<&'a Comment>,
+  pub community: &'a Community,
+  pub moderator: &'a Person,
+  pub reason: &'a str,
+  pub reply_with_comment: Option<bool>,
+  pub reply_with_private_message: Option<bool>,
+}
+
+impl ModActionReply<'_> {
+  pub async fn send(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    if self.reply_with_comment.unwrap_or_default() {
+      let form = CommentInsertForm {
+        distinguished: Some(true),
+        locked: Some(true),
+        ..CommentInsertForm::new(
+          self.moderator.id,
+          self.post.id,
+          self.community.id,
+          self.reason.to_string(),
+        )
+      };
+      let parent_path = self.comment.map(|c| &c.path);
+      let comment = Comment::create(&mut context.pool(), &form, parent_path).await?;
+
+      NotifyData {
+        comment: Some(comment.clone()),
+        ..NotifyData::new(
+          self.post.clone(),
+          self.moderator.clone(),
+          self.community.clone(),
+        )
+      }
+      .send(context);
+      ActivityChannel::submit_activity(SendActivityData::CreateComment(comment), context)?;
+    }
+
+    if self.reply_with_private_message.unwrap_or_default() {
+      let recipient_id = self
+        .comment
+        .map(|c| c.creator_id)
+        .unwrap_or(self.post.creator_id);
+      let form =
+        PrivateMessageInsertForm::new(self.moderator.id, recipient_id, self.reason.to_string());
+      let private_message = PrivateMessage::create(&mut context.pool(), &form).await?;
+      let view = PrivateMessageView::read(
+        &mut context.pool(),
+        private_message.id,
+        Some(self.moderator),
+      )
+      .await?;
+
+      notify_private_message(&view, true, context);
+      ActivityChannel::submit_activity(SendActivityData::CreatePrivateMessage(view), context)?;
+    }
+    Ok(())
+  }
+}
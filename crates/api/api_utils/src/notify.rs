@@ -1,4 +1,8 @@
-use crate::{context::LemmyContext, plugins::plugin_hook_notification};
+use crate::{
+  context::LemmyContext,
+  plugins::plugin_hook_notification,
+  webhooks::send_mod_action_webhooks,
+};
 use lemmy_db_schema::{
   source::{
     comment::Comment,
@@ -321,6 +325,8 @@ async fn notify_private_message_internal(
 }
 
 pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
+  send_mod_action_webhooks(&actions, context);
+
   // Mod actions should notify the target person. If there is no target person then also no
   // notification. This means each mod action can only notify a single person (eg it is not possible
   // to notify all community mods when a community gets removed).
@@ -8,9 +8,64 @@ use lemmy_db_views_registration_applications::api::CaptchaAnswer;
 use lemmy_db_views_site::api::CaptchaResponse;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::LemmyResult;
+use prometheus::{
+  HistogramOpts,
+  HistogramVec,
+  IntCounterVec,
+  Opts,
+  default_registry,
+  exponential_buckets,
+};
 use serde::{Deserialize, Serialize};
+use std::{sync::LazyLock, time::Instant};
 use tokio::task::spawn_blocking;
 
+struct PluginMetrics {
+  duration: HistogramVec,
+  failures: IntCounterVec,
+}
+
+static PLUGIN_METRICS: LazyLock<PluginMetrics> = LazyLock::new(|| {
+  #[expect(clippy::expect_used)]
+  create_plugin_metrics().expect("create plugin metrics")
+});
+
+fn create_plugin_metrics() -> prometheus::Result<PluginMetrics> {
+  let metrics = PluginMetrics {
+    duration: HistogramVec::new(
+      HistogramOpts::new(
+        "lemmy_plugin_hook_duration_seconds",
+        "Duration of plugin hook calls, by hook name",
+      )
+      .buckets(exponential_buckets(0.001, 2.0, 12)?),
+      &["hook"],
+    )?,
+    failures: IntCounterVec::new(
+      Opts::new(
+        "lemmy_plugin_hook_failures_total",
+        "Number of failed plugin hook calls, by hook name",
+      ),
+      &["hook"],
+    )?,
+  };
+  let registry = default_registry();
+  registry.register(Box::new(metrics.duration.clone()))?;
+  registry.register(Box::new(metrics.failures.clone()))?;
+  Ok(metrics)
+}
+
+/// Runs a plugin hook and records its duration and result in the Prometheus metrics.
+fn track_hook<R>(name: &str, start: Instant, res: LemmyResult<R>) -> LemmyResult<R> {
+  let m = &*PLUGIN_METRICS;
+  m.duration
+    .with_label_values(&[name])
+    .observe(start.elapsed().as_secs_f64());
+  if res.is_err() {
+    m.failures.with_label_values(&[name]).inc();
+  }
+  res
+}
+
 /// Call a plugin hook which can rewrite data
 pub async fn plugin_hook_before<T>(name: &'static str, data: T) -> LemmyResult<T>
 where
@@ -21,7 +76,9 @@ where
     return Ok(data);
   }
 
-  run_plugin_hook_before(plugins, name, data).await
+  let start = Instant::now();
+  let res = run_plugin_hook_before(plugins, name, data).await;
+  track_hook(name, start, res)
 }
 
 /// Call a plugin hook without rewriting data
@@ -35,7 +92,10 @@ where
   }
 
   let data = data.clone();
-  spawn_blocking(move || run_plugin_hook_after(name, data));
+  spawn_blocking(move || {
+    let start = Instant::now();
+    track_hook(name, start, run_plugin_hook_after(name, data))
+  });
 }
 
 /// Calls plugin hook for the given notifications Loads additional data via
@@ -53,7 +113,10 @@ pub async fn plugin_hook_notification(
   for n in notifications {
     let person = Person::read(&mut context.pool(), n.recipient_id).await?;
     let view = NotificationView::read(&mut context.pool(), n.id, &person).await?;
-    spawn_blocking(move || run_plugin_hook_after(name, view));
+    spawn_blocking(move || {
+      let start = Instant::now();
+      track_hook(name, start, run_plugin_hook_after(name, view))
+    });
   }
   Ok(())
 }
@@ -9,6 +9,7 @@ use lemmy_db_schema::{
     multi_community::MultiCommunity,
     person::Person,
     post::Post,
+    post_poll::PostPollOption,
     private_message::PrivateMessage,
     site::Site,
   },
@@ -45,6 +46,11 @@ pub enum SendActivityData {
   },
   LockPost(Post, Person, bool, String),
   FeaturePost(Post, Person, bool),
+  VotePostPoll {
+    post: Post,
+    actor: Person,
+    options: Vec<PostPollOption>,
+  },
   CreateComment(Comment),
   UpdateComment(Comment),
   DeleteComment(Comment, Person, Community),
@@ -107,11 +113,15 @@ pub enum SendActivityData {
   UpdatePrivateMessage(PrivateMessageView),
   DeletePrivateMessage(Person, PrivateMessage, bool),
   DeleteUser(Person, bool),
+  /// The old and new account of a user who moved.
+  MoveUser(Person, Person),
   CreateReport {
     object_id: Url,
     actor: Person,
     receiver: Either<Site, Community>,
     reason: String,
+    /// The community rule which is violated.
+    rule: Option<DbUrl>,
   },
   SendResolveReport {
     object_id: Url,
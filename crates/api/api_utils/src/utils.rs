@@ -1,4 +1,5 @@
 use crate::{
+  api_token::check_api_token_community_scope,
   claims::Claims,
   context::LemmyContext,
   request::{delete_image_alias, fetch_pictrs_proxied_image_details, purge_image_from_pictrs_url},
@@ -6,7 +7,7 @@ use crate::{
 use activitypub_federation::config::Data;
 use actix_web::{HttpRequest, http::header::Header};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
-use chrono::{DateTime, Days, Local, TimeZone, Utc};
+use chrono::{DateTime, Days, Duration, Local, TimeZone, Utc};
 use enum_map::{EnumMap, enum_map};
 use lemmy_db_schema::{
   newtypes::{CommunityId, CommunityTagId, ModlogId, PostId, PostOrCommentId},
@@ -52,7 +53,7 @@ use lemmy_utils::{
     LemmyResult,
     UntranslatedError,
   },
-  rate_limit::{ActionType, BucketConfig},
+  rate_limit::{ActionType, BucketConfig, RateLimitAccount, TrustTier},
   settings::SETTINGS,
   spawn_try_task,
   utils::{
@@ -81,6 +82,7 @@ pub async fn is_mod_or_admin(
   community_id: CommunityId,
 ) -> LemmyResult<()> {
   check_local_user_banned_or_deleted(local_user_view)?;
+  check_api_token_community_scope(community_id)?;
   let is_mod = CommunityModeratorView::check_is_community_moderator(
     pool,
     community_id,
@@ -384,6 +386,11 @@ pub fn local_site_rate_limit_to_rate_limit_config(
     ActionType::Comment => (l.comment_max_requests, l.comment_interval_seconds),
     ActionType::Search => (l.search_max_requests, l.search_interval_seconds),
     ActionType::ImportUserSettings => (l.import_user_settings_max_requests, l.import_user_settings_interval_seconds),
+    ActionType::Vote => (l.vote_max_requests, l.vote_interval_seconds),
+    ActionType::Follow => (l.follow_max_requests, l.follow_interval_seconds),
+    ActionType::Report => (l.report_max_requests, l.report_interval_seconds),
+    ActionType::PrivateMessage => (l.private_message_max_requests, l.private_message_interval_seconds),
+    ActionType::ResolveObject => (l.resolve_object_max_requests, l.resolve_object_interval_seconds),
   }
   .map(|_key, (max_requests, interval)| BucketConfig {
     max_requests: u32::try_from(max_requests).unwrap_or(0),
@@ -391,6 +398,69 @@ pub fn local_site_rate_limit_to_rate_limit_config(
   })
 }
 
+pub fn local_site_rate_limit_to_trust_tier_config(
+  l: &LocalSiteRateLimit,
+) -> EnumMap<TrustTier, u32> {
+  enum_map! {
+    TrustTier::NewAccount => l.new_account_limit_percent,
+    TrustTier::EstablishedAccount => l.established_account_limit_percent,
+    TrustTier::Bot => l.bot_account_limit_percent,
+    TrustTier::Moderator => l.moderator_limit_percent,
+  }
+  // Invalid values are rejected by the API, fall back to the normal limit just in case
+  .map(|_key, percent| {
+    u32::try_from(percent)
+      .ok()
+      .filter(|p| *p > 0)
+      .unwrap_or(100)
+  })
+}
+
+/// Authenticated users are rate limited by account, with limits depending on the trust tier.
+pub async fn rate_limit_account(
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<RateLimitAccount> {
+  let person = &local_user_view.person;
+  let rate_limit = SiteView::read_local(&mut context.pool())
+    .await?
+    .local_site_rate_limit;
+  let new_account_since = Utc::now() - Duration::days(rate_limit.new_account_max_age_days.into());
+
+  let trust_tier =
+    if local_user_view.local_user.admin || is_moderator_of_any_cached(person.id, context).await {
+      TrustTier::Moderator
+    } else if person.bot_account {
+      TrustTier::Bot
+    } else if person.published_at > new_account_since {
+      TrustTier::NewAccount
+    } else {
+      TrustTier::EstablishedAccount
+    };
+
+  Ok(RateLimitAccount {
+    local_user_id: local_user_view.local_user.id.0,
+    trust_tier,
+  })
+}
+
+/// Whether the person moderates any community. This is checked for every authenticated request,
+/// so the result is cached for some time.
+async fn is_moderator_of_any_cached(person_id: PersonId, context: &LemmyContext) -> bool {
+  static CACHE: LazyLock<Cache<PersonId, bool>> = LazyLock::new(|| {
+    Cache::builder()
+      .max_capacity(10000)
+      .time_to_live(CACHE_DURATION_FEDERATION)
+      .build()
+  });
+  Box::pin(CACHE.get_with(person_id, async {
+    CommunityModeratorView::is_community_moderator_of_any(&mut context.pool(), person_id)
+      .await
+      .is_ok()
+  }))
+  .await
+}
+
 pub async fn slur_regex(context: &LemmyContext) -> LemmyResult<Regex> {
   static CACHE: CacheLock<Regex> = LazyLock::new(|| {
     Cache::builder()
@@ -1131,6 +1201,7 @@ mod tests {
       child_count: 0,
       hot_rank: 0.0,
       controversy_rank: 0.0,
+      best_rank: 0.0,
       report_count: 0,
       unresolved_report_count: 0,
       federation_pending: false,
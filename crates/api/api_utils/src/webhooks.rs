@@ -0,0 +1,431 @@
+use crate::{context::LemmyContext, request::client_builder};
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, PostId},
+  source::{
+    comment_report::CommentReport,
+    community_report::CommunityReport,
+    modlog::Modlog,
+    post_report::PostReport,
+    private_message_report::PrivateMessageReport,
+    webhook::{Webhook, WebhookDelivery, WebhookDeliveryInsertForm, WebhookDeliveryUpdateForm},
+  },
+};
+use lemmy_db_schema_file::{PersonId, enums::WebhookEventType};
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::Settings,
+  spawn_try_task,
+};
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde::Serialize;
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use tokio::net::lookup_host;
+use tracing::warn;
+use url::{Host, Url};
+
+/// Delay before each retry of a failed delivery. Once all retries failed, the delivery is given
+/// up.
+const RETRY_DELAYS_MINUTES: [i64; 5] = [1, 5, 30, 120, 720];
+
+/// How many pending deliveries are retried at once by the scheduled task.
+const RETRY_BATCH_SIZE: i64 = 100;
+
+/// Hex encoded HMAC-SHA256 of the request body, prefixed with `sha256=`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Lemmy-Signature";
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Lemmy-Event";
+/// The id of the delivery. Stays the same for retries, so that receivers can skip duplicates.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Lemmy-Delivery";
+
+#[derive(Serialize)]
+struct WebhookPayload<'a, T> {
+  event: WebhookEventType,
+  created_at: DateTime<Utc>,
+  data: &'a T,
+}
+
+/// Data for [WebhookEventType::ReportCreated].
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookReport<'a> {
+  PostReport(&'a PostReport),
+  CommentReport(&'a CommentReport),
+  CommunityReport(&'a CommunityReport),
+  PrivateMessageReport(&'a PrivateMessageReport),
+}
+
+/// Data for [WebhookEventType::ModAction]. Includes the ids of the affected items, which are not
+/// part of the serialized [Modlog].
+#[derive(Serialize)]
+struct WebhookModAction<'a> {
+  #[serde(flatten)]
+  modlog: &'a Modlog,
+  mod_id: PersonId,
+  target_person_id: Option<PersonId>,
+  target_community_id: Option<CommunityId>,
+  target_post_id: Option<PostId>,
+  target_comment_id: Option<CommentId>,
+}
+
+/// Sends the event to all webhooks which are subscribed to it, in a background task. Events without
+/// community are only sent to site-wide webhooks.
+pub fn send_webhooks<T>(
+  event: WebhookEventType,
+  community_id: Option<CommunityId>,
+  data: &T,
+  context: &LemmyContext,
+) where
+  T: Serialize,
+{
+  let payload = WebhookPayload {
+    event,
+    created_at: Utc::now(),
+    data,
+  };
+  let payload = match serde_json::to_string(&payload) {
+    Ok(p) => p,
+    Err(e) => {
+      warn!("Failed to serialize webhook payload: {e}");
+      return;
+    }
+  };
+
+  let context = context.clone();
+  spawn_try_task(async move {
+    let webhooks = Webhook::list_for_event(&mut context.pool(), event, community_id).await?;
+    for webhook in webhooks {
+      // In case delivery gets interrupted, the scheduled task takes care of it.
+      let form = WebhookDeliveryInsertForm::new(
+        webhook.id,
+        event,
+        payload.clone(),
+        retry_delay(0).map(|d| Utc::now() + d),
+      );
+      // An error for one webhook must not prevent delivery to the others
+      let res = async {
+        let delivery = WebhookDelivery::create(&mut context.pool(), &form).await?;
+        deliver(&webhook, delivery, &context).await
+      }
+      .await;
+      if let Err(e) = res {
+        warn!("Failed to deliver webhook {}: {e}", webhook.id.0);
+      }
+    }
+    Ok(())
+  })
+}
+
+/// Sends a webhook for each mod action, to the webhooks of the affected community and site-wide.
+pub fn send_mod_action_webhooks(actions: &[Modlog], context: &LemmyContext) {
+  for action in actions {
+    let data = WebhookModAction {
+      modlog: action,
+      mod_id: action.mod_id,
+      target_person_id: action.target_person_id,
+      target_community_id: action.target_community_id,
+      target_post_id: action.target_post_id,
+      target_comment_id: action.target_comment_id,
+    };
+    send_webhooks(
+      WebhookEventType::ModAction,
+      action.target_community_id,
+      &data,
+      context,
+    );
+  }
+}
+
+/// Retries deliveries which failed previously. Called from a scheduled task.
+pub async fn retry_webhook_deliveries(context: &LemmyContext) -> LemmyResult<()> {
+  let pending = WebhookDelivery::list_pending(&mut context.pool(), RETRY_BATCH_SIZE).await?;
+  for (delivery, webhook) in pending {
+    deliver(&webhook, delivery, context)
+      .await
+      .inspect_err(|e| warn!("Failed to retry webhook delivery: {e}"))
+      .ok();
+  }
+  Ok(())
+}
+
+/// Makes a single delivery attempt and stores the result in the delivery log.
+async fn deliver(
+  webhook: &Webhook,
+  delivery: WebhookDelivery,
+  context: &LemmyContext,
+) -> LemmyResult<WebhookDelivery> {
+  let signature = sign(&webhook.secret, &delivery.payload)?;
+  let res = match pinned_client(webhook.url.inner(), context.settings()).await {
+    Ok(client) => client
+      .post(webhook.url.as_str())
+      .timeout(REQWEST_TIMEOUT)
+      .header(CONTENT_TYPE, "application/json")
+      .header(WEBHOOK_EVENT_HEADER, delivery.event.to_string())
+      .header(WEBHOOK_DELIVERY_HEADER, delivery.id.0.to_string())
+      .header(WEBHOOK_SIGNATURE_HEADER, format!("sha256={signature}"))
+      .body(delivery.payload.clone())
+      .send()
+      .await
+      .map_err(|e| e.to_string()),
+    Err(e) => Err(e.to_string()),
+  };
+
+  let (status_code, error) = match res {
+    Ok(res) if res.status().is_success() => (Some(res.status()), None),
+    Ok(res) => (
+      Some(res.status()),
+      Some(format!("Received HTTP {}", res.status())),
+    ),
+    Err(e) => (None, Some(e)),
+  };
+  let attempts = delivery.attempts + 1;
+  let delivered = error.is_none();
+  let form = WebhookDeliveryUpdateForm {
+    attempts: Some(attempts),
+    status_code: Some(status_code.map(|s| i32::from(s.as_u16()))),
+    error: Some(error),
+    delivered_at: Some(delivered.then(Utc::now)),
+    next_attempt_at: Some(if delivered {
+      None
+    } else {
+      retry_delay(attempts).map(|d| Utc::now() + d)
+    }),
+  };
+  WebhookDelivery::update(&mut context.pool(), delivery.id, &form).await
+}
+
+/// Builds a client which can only connect to the addresses that the webhook host currently
+/// resolves to, after checking that none of them is in the local network. Checking the url once
+/// when the webhook is created is not enough, as the host could later be changed to resolve to an
+/// internal address (DNS rebinding).
+async fn pinned_client(url: &Url, settings: &Settings) -> LemmyResult<Client> {
+  let mut builder = client_builder(settings);
+  let ips: Vec<IpAddr> = match url.host() {
+    Some(Host::Domain(domain)) => {
+      let port = url.port_or_known_default().unwrap_or(80);
+      let addrs: Vec<SocketAddr> = lookup_host((domain, port)).await?.collect();
+      builder = builder.resolve_to_addrs(domain, &addrs);
+      addrs.iter().map(SocketAddr::ip).collect()
+    }
+    Some(Host::Ipv4(ip)) => vec![ip.into()],
+    Some(Host::Ipv6(ip)) => vec![ip.into()],
+    None => vec![],
+  };
+  // Like for federation, local addresses are allowed in debug mode
+  if ips.is_empty() || (!cfg!(debug_assertions) && ips.into_iter().any(is_local_ip)) {
+    return Err(LemmyErrorType::InvalidUrl.into());
+  }
+  Ok(builder.build()?)
+}
+
+/// Addresses which are not globally reachable, following the unstable `Ipv4Addr::is_global` and
+/// `Ipv6Addr::is_global` of the standard library. Multicast addresses are also rejected.
+fn is_local_ip(ip: IpAddr) -> bool {
+  match ip.to_canonical() {
+    IpAddr::V4(ip) => {
+      let [a, b, c, _] = ip.octets();
+      ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        // "This network", 0.0.0.0/8
+        || a == 0
+        // Shared address space for carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved, 240.0.0.0/4
+        || a >= 240
+    }
+    IpAddr::V6(ip) => {
+      let [s0, s1, s2, s3, ..] = ip.segments();
+      ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        || ip.is_unspecified()
+        // Discard-only, 100::/64
+        || [s0, s1, s2, s3] == [0x100, 0, 0, 0]
+        // Documentation, 2001:db8::/32
+        || [s0, s1] == [0x2001, 0xdb8]
+        // Benchmarking, 2001:2::/48
+        || [s0, s1, s2] == [0x2001, 2, 0]
+    }
+  }
+}
+
+/// The delay before the next attempt, or `None` if the delivery should be given up.
+fn retry_delay(attempts: i32) -> Option<Duration> {
+  let index = usize::try_from(attempts).ok()?;
+  RETRY_DELAYS_MINUTES
+    .get(index)
+    .map(|minutes| Duration::minutes(*minutes))
+}
+
+fn sign(secret: &str, payload: &str) -> LemmyResult<String> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|e| anyhow!("{e}"))?;
+  mac.update(payload.as_bytes());
+  Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    webhook::WebhookInsertForm,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+  };
+
+  /// A minimal HTTP server which answers a single request with the given status, and returns the
+  /// raw request.
+  async fn receive_one_request(listener: &TcpListener, status: &str) -> LemmyResult<String> {
+    let (mut stream, _) = listener.accept().await?;
+    let mut request = vec![];
+    let mut buf = [0; 4096];
+    loop {
+      let read = stream.read(&mut buf).await?;
+      request.extend_from_slice(buf.get(..read).unwrap_or_default());
+      let text = String::from_utf8_lossy(&request);
+      // Read until the complete body is received
+      if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+        let length = headers
+          .lines()
+          .find_map(|l| {
+            l.to_lowercase()
+              .strip_prefix("content-length: ")?
+              .parse()
+              .ok()
+          })
+          .unwrap_or(0);
+        if body.len() >= length {
+          break;
+        }
+      }
+      if read == 0 {
+        break;
+      }
+    }
+    let response = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    stream.write_all(response.as_bytes()).await?;
+    Ok(String::from_utf8_lossy(&request).to_string())
+  }
+
+  #[test]
+  fn test_sign() -> LemmyResult<()> {
+    // Test vector from RFC 4231
+    assert_eq!(
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+      sign("Jefe", "what do ya want for nothing?")?
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_retry_delay() {
+    assert_eq!(Some(Duration::minutes(1)), retry_delay(0));
+    assert_eq!(Some(Duration::minutes(720)), retry_delay(4));
+    assert_eq!(None, retry_delay(5));
+  }
+
+  #[test]
+  fn test_is_local_ip() -> LemmyResult<()> {
+    assert!(is_local_ip("127.0.0.1".parse()?));
+    assert!(is_local_ip("10.1.2.3".parse()?));
+    assert!(is_local_ip("169.254.169.254".parse()?));
+    assert!(is_local_ip("::1".parse()?));
+    assert!(is_local_ip("::ffff:192.168.1.1".parse()?));
+    assert!(is_local_ip("fd00::1".parse()?));
+    assert!(is_local_ip("100.64.0.1".parse()?));
+    assert!(is_local_ip("100.127.255.254".parse()?));
+    assert!(is_local_ip("198.18.0.1".parse()?));
+    assert!(is_local_ip("2001:db8::1".parse()?));
+    assert!(!is_local_ip("100.128.0.1".parse()?));
+    assert!(!is_local_ip("1.1.1.1".parse()?));
+    assert!(!is_local_ip("2606:4700::1111".parse()?));
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_deliver() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = Url::parse(&format!("http://{}/hook", listener.local_addr()?))?;
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let admin = Person::create(pool, &PersonInsertForm::test_form(instance.id, "admin")).await?;
+    LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form_admin(admin.id),
+      vec![],
+    )
+    .await?;
+    let secret = "0123456789abcdef";
+    let webhook = Webhook::create(
+      pool,
+      &WebhookInsertForm::new(
+        admin.id,
+        url.into(),
+        secret.to_string(),
+        vec![WebhookEventType::PostCreated],
+      ),
+    )
+    .await?;
+    let payload = r#"{"event":"post_created"}"#.to_string();
+    let form = WebhookDeliveryInsertForm::new(
+      webhook.id,
+      WebhookEventType::PostCreated,
+      payload.clone(),
+      None,
+    );
+    let delivery = WebhookDelivery::create(pool, &form).await?;
+
+    // A failed attempt gets scheduled for retry
+    let (request, delivery) = tokio::try_join!(
+      receive_one_request(&listener, "500 Internal Server Error"),
+      deliver(&webhook, delivery, &context)
+    )?;
+    assert_eq!(1, delivery.attempts);
+    assert_eq!(Some(500), delivery.status_code);
+    assert!(delivery.delivered_at.is_none());
+    assert!(delivery.next_attempt_at.is_some());
+
+    let signature = format!("sha256={}", sign(secret, &payload)?);
+    let request = request.to_lowercase();
+    assert!(request.contains(&format!("x-lemmy-signature: {signature}")));
+    assert!(request.contains("x-lemmy-event: post_created"));
+    assert!(request.ends_with(&payload));
+
+    // The retry succeeds
+    let (_, delivery) = tokio::try_join!(
+      receive_one_request(&listener, "204 No Content"),
+      deliver(&webhook, delivery, &context)
+    )?;
+    assert_eq!(2, delivery.attempts);
+    assert_eq!(Some(204), delivery.status_code);
+    assert!(delivery.error.is_none());
+    assert!(delivery.delivered_at.is_some());
+    assert!(delivery.next_attempt_at.is_none());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -2,6 +2,7 @@ use actix_web::{guard, web::*};
 use lemmy_api::{
   comment::{
     distinguish::distinguish_comment,
+    history::get_comment_history,
     like::like_comment,
     list_comment_likes::list_comment_likes,
     lock::lock_comment,
@@ -12,19 +13,37 @@ use lemmy_api::{
     add_mod::add_mod_to_community,
     ban::ban_from_community,
     block::user_block_community,
+    flair::{
+      assign_community_flair,
+      create_community_flair,
+      delete_community_flair,
+      edit_community_flair,
+      list_community_flairs,
+    },
     follow::follow_community,
+    mod_template::{
+      create_mod_template,
+      delete_mod_template,
+      edit_mod_template,
+      list_mod_templates,
+    },
     multi_community_follow::follow_multi_community,
     pending_follows::{approve::post_pending_follows_approve, list::get_pending_follows_list},
+    pending_posts::{approve::post_pending_posts_approve, list::get_pending_posts_list},
     random::get_random_community,
+    recommended::list_recommended_communities,
+    rule::{create_rule, delete_rule, edit_rule, list_rules},
     tag::{create_community_tag, delete_community_tag, edit_community_tag},
     transfer::transfer_community,
     update_notifications::edit_community_notifications,
   },
   federation::{
+    import_content::{MAX_ARCHIVE_SIZE, get_content_import, import_content_history},
     list_comments::{list_comments, list_comments_slim},
     list_person_content::list_person_content,
     list_persons::list_persons,
     list_posts::list_posts,
+    move_account::{move_account, set_account_aliases},
     read_community::get_community,
     read_multi_community::read_multi_community,
     read_person::read_person,
@@ -38,6 +57,7 @@ use lemmy_api::{
     block::user_block_person,
     change_password::change_password,
     change_password_after_reset::change_password_after_reset,
+    export_archive::{create_data_archive, download_data_archive, get_data_archive},
     export_data::export_user_data,
     generate_totp_secret::generate_totp_secret,
     get_captcha::get_captcha,
@@ -69,12 +89,14 @@ use lemmy_api::{
     feature::feature_post,
     get_link_metadata::get_link_metadata,
     hide::hide_post,
+    history::get_post_history,
     like::like_post,
     list_post_likes::list_post_likes,
     lock::lock_post,
     mark_many_read::mark_posts_as_read,
     mark_read::mark_post_as_read,
     mod_update::mod_edit_post,
+    poll::{get_post_poll, vote_post_poll},
     save::save_post,
     update_notifications::edit_post_notifications,
     warning::create_post_warning,
@@ -89,6 +111,12 @@ use lemmy_api::{
   site::{
     admin_allow_instance::admin_allow_instance,
     admin_block_instance::admin_block_instance,
+    admin_federation_queue::{
+      admin_list_federation_queues,
+      admin_mark_instance_dead,
+      admin_reset_federation_queue,
+      admin_skip_federation_activity,
+    },
     admin_list_users::admin_list_users,
     federated_instances::get_federated_instances,
     list_all_media::list_all_media,
@@ -107,9 +135,28 @@ use lemmy_api::{
   },
 };
 use lemmy_api_crud::{
+  api_token::{
+    create::create_api_token,
+    delete::delete_api_token,
+    list::list_api_tokens,
+    update::edit_api_token,
+  },
+  automod::{
+    create::create_automod_rule,
+    delete::delete_automod_rule,
+    list::list_automod_rules,
+    update::edit_automod_rule,
+  },
   comment::{
     create::create_comment,
     delete::delete_comment,
+    draft::{
+      create_comment_draft,
+      delete_comment_draft,
+      edit_comment_draft,
+      list_comment_drafts,
+      publish_comment_draft,
+    },
     read::get_comment,
     remove::remove_comment,
     update::edit_comment,
@@ -143,6 +190,13 @@ use lemmy_api_crud::{
   post::{
     create::create_post,
     delete::delete_post,
+    draft::{
+      create_post_draft,
+      delete_post_draft,
+      edit_post_draft,
+      list_post_drafts,
+      publish_post_draft,
+    },
     read::get_post,
     remove::remove_post,
     update::edit_post,
@@ -164,6 +218,13 @@ use lemmy_api_crud::{
     delete::delete_account,
     my_user::get_my_user,
   },
+  webhook::{
+    create::create_webhook,
+    delete::delete_webhook,
+    list::list_webhooks,
+    list_deliveries::list_webhook_deliveries,
+    update::edit_webhook,
+  },
 };
 use lemmy_routes::images::{
   delete::{
@@ -213,7 +274,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       )
       .service(
         resource("/resolve_object")
-          .wrap(rate_limit.search())
+          .wrap(rate_limit.resolve_object())
           .route(get().to(resolve_object)),
       )
       // Community
@@ -223,15 +284,24 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.register())
           .route(post().to(create_community)),
       )
+      .service(
+        resource("/community/follow")
+          .wrap(rate_limit.follow())
+          .route(post().to(follow_community)),
+      )
+      .service(
+        resource("/community/report")
+          .wrap(rate_limit.report())
+          .route(post().to(create_community_report)),
+      )
       .service(
         scope("/community")
           .route("", get().to(get_community))
           .route("", put().to(edit_community))
           .route("", delete().to(delete_community))
           .route("/random", get().to(get_random_community))
+          .route("/recommended", get().to(list_recommended_communities))
           .route("/list", get().to(list_communities))
-          .route("/follow", post().to(follow_community))
-          .route("/report", post().to(create_community_report))
           .route("/report/resolve", put().to(resolve_community_report))
           // Mod Actions
           .route("/remove", post().to(remove_community))
@@ -245,13 +315,40 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/tag", post().to(create_community_tag))
           .route("/tag", put().to(edit_community_tag))
           .route("/tag", delete().to(delete_community_tag))
+          .route("/flair", post().to(create_community_flair))
+          .route("/flair", put().to(edit_community_flair))
+          .route("/flair", delete().to(delete_community_flair))
+          .route("/flair/list", get().to(list_community_flairs))
+          .route("/flair/assign", post().to(assign_community_flair))
+          .route("/rule", post().to(create_rule))
+          .route("/rule", put().to(edit_rule))
+          .route("/rule", delete().to(delete_rule))
+          .route("/rule/list", get().to(list_rules))
+          .route("/mod_template", post().to(create_mod_template))
+          .route("/mod_template", put().to(edit_mod_template))
+          .route("/mod_template", delete().to(delete_mod_template))
+          .route("/mod_template/list", get().to(list_mod_templates))
+          .route("/automod", post().to(create_automod_rule))
+          .route("/automod", put().to(edit_automod_rule))
+          .route("/automod", delete().to(delete_automod_rule))
+          .route("/automod/list", get().to(list_automod_rules))
           .route("/notifications", put().to(edit_community_notifications))
           .service(
             scope("/pending_follows")
               .route("/list", get().to(get_pending_follows_list))
               .route("/approve", post().to(post_pending_follows_approve)),
+          )
+          .service(
+            scope("/pending_posts")
+              .route("/list", get().to(get_pending_posts_list))
+              .route("/approve", post().to(post_pending_posts_approve)),
           ),
       )
+      .service(
+        resource("/multi_community/follow")
+          .wrap(rate_limit.follow())
+          .route(post().to(follow_multi_community)),
+      )
       .service(
         scope("/multi_community")
           .route("", post().to(create_multi_community))
@@ -259,8 +356,15 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", get().to(read_multi_community))
           .route("/entry", post().to(create_multi_community_entry))
           .route("/entry", delete().to(delete_multi_community_entry))
-          .route("/list", get().to(list_multi_communities))
-          .route("/follow", post().to(follow_multi_community)),
+          .route("/list", get().to(list_multi_communities)),
+      )
+      .service(
+        scope("/webhook")
+          .route("", post().to(create_webhook))
+          .route("", put().to(edit_webhook))
+          .route("", delete().to(delete_webhook))
+          .route("/list", get().to(list_webhooks))
+          .route("/delivery/list", get().to(list_webhook_deliveries)),
       )
       .route("/federated_instances", get().to(get_federated_instances))
       // Post
@@ -271,11 +375,31 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.post())
           .route(post().to(create_post)),
       )
+      .service(
+        resource("/post/draft/publish")
+          .wrap(rate_limit.post())
+          .route(post().to(publish_post_draft)),
+      )
       .service(
         resource("/post/site_metadata")
           .wrap(rate_limit.search())
           .route(get().to(get_link_metadata)),
       )
+      .service(
+        resource("/post/like")
+          .wrap(rate_limit.vote())
+          .route(post().to(like_post)),
+      )
+      .service(
+        resource("/post/poll/vote")
+          .wrap(rate_limit.vote())
+          .route(post().to(vote_post_poll)),
+      )
+      .service(
+        resource("/post/report")
+          .wrap(rate_limit.report())
+          .route(post().to(create_post_report)),
+      )
       .service(
         scope("/post")
           .route("", get().to(get_post))
@@ -288,14 +412,18 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/lock", post().to(lock_post))
           .route("/feature", post().to(feature_post))
           .route("/list", get().to(list_posts))
-          .route("/like", post().to(like_post))
           .route("/like/list", get().to(list_post_likes))
+          .route("/history", get().to(get_post_history))
+          .route("/poll", get().to(get_post_poll))
           .route("/save", put().to(save_post))
-          .route("/report", post().to(create_post_report))
           .route("/report/resolve", put().to(resolve_post_report))
           .route("/notifications", put().to(edit_post_notifications))
           .route("/mod_edit", put().to(mod_edit_post))
-          .route("/warn", post().to(create_post_warning)),
+          .route("/warn", post().to(create_post_warning))
+          .route("/draft", post().to(create_post_draft))
+          .route("/draft", put().to(edit_post_draft))
+          .route("/draft", delete().to(delete_post_draft))
+          .route("/draft/list", get().to(list_post_drafts)),
       )
       // Comment
       .service(
@@ -305,6 +433,21 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.comment())
           .route(post().to(create_comment)),
       )
+      .service(
+        resource("/comment/draft/publish")
+          .wrap(rate_limit.comment())
+          .route(post().to(publish_comment_draft)),
+      )
+      .service(
+        resource("/comment/like")
+          .wrap(rate_limit.vote())
+          .route(post().to(like_comment)),
+      )
+      .service(
+        resource("/comment/report")
+          .wrap(rate_limit.report())
+          .route(post().to(create_comment_report)),
+      )
       .service(
         scope("/comment")
           .route("", get().to(get_comment))
@@ -312,23 +455,36 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", delete().to(delete_comment))
           .route("/remove", post().to(remove_comment))
           .route("/distinguish", post().to(distinguish_comment))
-          .route("/like", post().to(like_comment))
           .route("/like/list", get().to(list_comment_likes))
           .route("/save", put().to(save_comment))
           .route("/lock", post().to(lock_comment))
+          .route("/history", get().to(get_comment_history))
           .route("/list", get().to(list_comments))
           .route("/list/slim", get().to(list_comments_slim))
           .route("/warn", post().to(create_comment_warning))
-          .route("/report", post().to(create_comment_report))
-          .route("/report/resolve", put().to(resolve_comment_report)),
+          .route("/report/resolve", put().to(resolve_comment_report))
+          .route("/draft", post().to(create_comment_draft))
+          .route("/draft", put().to(edit_comment_draft))
+          .route("/draft", delete().to(delete_comment_draft))
+          .route("/draft/list", get().to(list_comment_drafts)),
       )
       // Private Message
+      .service(
+        // Handle POST to /private_message separately to add the private_message() rate limiter
+        resource("/private_message")
+          .guard(guard::Post())
+          .wrap(rate_limit.private_message())
+          .route(post().to(create_private_message)),
+      )
+      .service(
+        resource("/private_message/report")
+          .wrap(rate_limit.report())
+          .route(post().to(create_pm_report)),
+      )
       .service(
         scope("/private_message")
-          .route("", post().to(create_private_message))
           .route("", put().to(edit_private_message))
           .route("", delete().to(delete_private_message))
-          .route("/report", post().to(create_pm_report))
           .route("/report/resolve", put().to(resolve_pm_report)),
       )
       // Reports
@@ -373,7 +529,16 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
               .route("/mark_as_read", post().to(mark_notification_as_read)),
           )
           .route("", delete().to(delete_account))
+          .route("/aliases", put().to(set_account_aliases))
+          .route("/move", post().to(move_account))
           .route("/login/list", get().to(list_logins))
+          .service(
+            scope("/token")
+              .route("", post().to(create_api_token))
+              .route("", put().to(edit_api_token))
+              .route("", delete().to(delete_api_token))
+              .route("/list", get().to(list_api_tokens)),
+          )
           .route("/validate_auth", get().to(validate_auth))
           .route(
             "/donation_dialog_shown",
@@ -410,6 +575,20 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
               .wrap(rate_limit.import_user_settings())
               .route(get().to(export_user_data)),
           )
+          .service(
+            resource("/data/archive")
+              .wrap(rate_limit.import_user_settings())
+              .route(post().to(create_data_archive)),
+          )
+          .route("/data/archive/status", get().to(get_data_archive))
+          .route("/data/archive/download", get().to(download_data_archive))
+          .service(
+            resource("/data/import")
+              .wrap(rate_limit.import_user_settings())
+              .app_data(PayloadConfig::new(MAX_ARCHIVE_SIZE))
+              .route(post().to(import_content_history)),
+          )
+          .route("/data/import/status", get().to(get_content_import))
           .service(
             scope("/invite")
               .route("", post().to(create_invitation))
@@ -455,6 +634,13 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
             scope("/instance")
               .route("/block", post().to(admin_block_instance))
               .route("/allow", post().to(admin_allow_instance)),
+          )
+          .service(
+            scope("/federation_queue")
+              .route("/list", get().to(admin_list_federation_queues))
+              .route("/reset", post().to(admin_reset_federation_queue))
+              .route("/skip", post().to(admin_skip_federation_activity))
+              .route("/mark_dead", post().to(admin_mark_instance_dead)),
           ),
       )
       .service(
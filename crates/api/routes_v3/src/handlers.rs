@@ -372,6 +372,7 @@ pub(crate) async fn create_post_v3(
     custom_thumbnail,
     tags: None,
     scheduled_publish_time_at: None,
+    poll: None,
   };
   let res = Box::pin(create_post(Json(data), context, local_user_view)).await?;
   convert_post_response(res)
@@ -15,6 +15,7 @@ use crate::protocol::{
   following::{
     accept::AcceptFollow,
     follow::Follow,
+    move_person::MovePerson,
     reject::RejectFollow,
     undo_follow::UndoFollow,
   },
@@ -47,10 +48,40 @@ pub enum SharedInboxActivities {
   Report(Report),
   ResolveReport(ResolveReport),
   AnnounceActivity(AnnounceActivity),
+  MovePerson(MovePerson),
   /// This is a catch-all and needs to be last
   RawAnnouncableActivities(RawAnnouncableActivities),
 }
 
+impl SharedInboxActivities {
+  /// Name of the activity type for metrics. Activities which are sent to a community inbox are
+  /// only named if they have a known type, so that remote instances can't create arbitrary metric
+  /// labels.
+  pub fn kind_name(&self) -> &'static str {
+    use SharedInboxActivities::*;
+    const ANNOUNCABLE_KINDS: [&str; 12] = [
+      "Create", "Update", "Like", "Dislike", "Delete", "Undo", "Block", "Add", "Remove", "Lock",
+      "Flag", "Resolve",
+    ];
+    match self {
+      Follow(_) => "Follow",
+      AcceptFollow(_) => "AcceptFollow",
+      RejectFollow(_) => "RejectFollow",
+      UndoFollow(_) => "UndoFollow",
+      Report(_) => "Report",
+      ResolveReport(_) => "ResolveReport",
+      AnnounceActivity(_) => "Announce",
+      MovePerson(_) => "Move",
+      RawAnnouncableActivities(a) => a
+        .other
+        .get("type")
+        .and_then(|t| t.as_str())
+        .and_then(|t| ANNOUNCABLE_KINDS.into_iter().find(|k| *k == t))
+        .unwrap_or("Other"),
+    }
+  }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 #[enum_delegate::implement(Activity)]
@@ -127,7 +158,12 @@ mod tests {
     test_parse_lemmy_item::<SharedInboxActivities>(
       "../apub/assets/lemmy/activities/create_or_update/create_comment.json",
     )?;
+    test_parse_lemmy_item::<SharedInboxActivities>(
+      "../apub/assets/lemmy/activities/following/move.json",
+    )?;
     test_json::<SharedInboxActivities>("../apub/assets/mastodon/activities/follow.json")?;
+    let move_ = test_json::<SharedInboxActivities>("../apub/assets/mastodon/activities/move.json")?;
+    assert_eq!("Move", move_.inner().kind_name());
     Ok(())
   }
 }
@@ -22,7 +22,11 @@ use lemmy_apub_objects::{
     protocol::{Id, InCommunity},
   },
 };
-use lemmy_db_schema::source::{activity::ActivitySendTargets, community::CommunityActions};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  community::CommunityActions,
+  post::Post,
+};
 use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError};
 use serde_json::Value;
 use url::Url;
@@ -59,11 +63,24 @@ impl Activity for RawAnnouncableActivities {
     // verify and receive activity
     activity.verify(context).await?;
     let ap_id = activity.actor().clone().into();
+    let post_id = match &activity {
+      AnnouncableActivities::CreateOrUpdatePost(c) => Some(c.object.id.clone()),
+      _ => None,
+    };
     activity.receive(context).await?;
 
+    // Posts which are waiting for mod approval are announced once they get approved
+    let approval_pending = match post_id {
+      Some(post_id) => Post::read_from_apub_id(&mut context.pool(), post_id.into())
+        .await?
+        .is_some_and(|p| p.approval_pending),
+      None => false,
+    };
+
     // if community is local, send activity to followers
     if let Some(community) = community
       && community.local
+      && !approval_pending
     {
       verify_person_in_community(&ap_id, &community, context).await?;
       AnnounceActivity::send(self, &community, context).await?;
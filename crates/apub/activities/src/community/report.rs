@@ -22,6 +22,7 @@ use lemmy_api_utils::{
     check_community_deleted_removed,
     check_post_deleted_or_removed,
   },
+  webhooks::{WebhookReport, send_webhooks},
 };
 use lemmy_apub_objects::{
   objects::{
@@ -34,6 +35,7 @@ use lemmy_apub_objects::{
   utils::functions::{verify_person_in_community, verify_person_in_site_or_community},
 };
 use lemmy_db_schema::{
+  newtypes::{CommunityId, RuleId},
   source::{
     comment_report::{CommentReport, CommentReportForm},
     community::Community,
@@ -41,10 +43,12 @@ use lemmy_db_schema::{
     post::Post,
     post_report::{PostReport, PostReportForm},
     private_message_report::{PrivateMessageReport, PrivateMessageReportForm},
+    rule::Rule,
   },
   traits::Reportable,
 };
-use lemmy_diesel_utils::traits::Crud;
+use lemmy_db_schema_file::enums::WebhookEventType;
+use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
 use lemmy_utils::error::{LemmyError, LemmyResult};
 use url::Url;
 
@@ -54,6 +58,7 @@ impl Report {
     actor: &ApubPerson,
     receiver: &Either<ApubSite, ApubCommunity>,
     reason: Option<String>,
+    rule: Option<DbUrl>,
     context: &Data<LemmyContext>,
   ) -> LemmyResult<Self> {
     let kind = FlagType::Flag;
@@ -67,6 +72,7 @@ impl Report {
       kind,
       id: id.clone(),
       audience: receiver.as_ref().right().map(|c| c.ap_id.clone().into()),
+      rule: rule.map(Into::into),
     })
   }
 
@@ -75,13 +81,26 @@ impl Report {
     actor: &ApubPerson,
     receiver: &Either<ApubSite, ApubCommunity>,
     reason: String,
+    rule: Option<DbUrl>,
     context: Data<LemmyContext>,
   ) -> LemmyResult<()> {
-    let report = Self::new(&object_id, actor, receiver, Some(reason), &context)?;
+    let report = Self::new(&object_id, actor, receiver, Some(reason), rule, &context)?;
     let inboxes = report_inboxes(object_id, receiver, actor, &context).await?;
 
     send_lemmy_activity(&context, report, actor, inboxes, false).await
   }
+
+  /// Reads the referenced rule, ignoring rules which are unknown or belong to another community.
+  async fn rule_id(
+    &self,
+    community_id: CommunityId,
+    context: &Data<LemmyContext>,
+  ) -> Option<RuleId> {
+    let rule = Rule::read_apub(&mut context.pool(), &self.rule.clone()?.into())
+      .await
+      .ok()?;
+    (rule.community_id == Some(community_id)).then_some(rule.id)
+  }
 }
 
 #[async_trait::async_trait]
@@ -131,6 +150,7 @@ impl Activity for Report {
     let reason = self.reason()?;
     match self.object.dereference(context).await? {
       ReportableObjects::Left(PostOrComment::Left(post)) => {
+        let rule_id = self.rule_id(post.community_id, context).await;
         let report_form = PostReportForm {
           creator_id: actor.id,
           post_id: post.id,
@@ -139,18 +159,34 @@ impl Activity for Report {
           reason,
           original_post_body: post.body.clone(),
           violates_instance_rules: false,
+          rule_id,
         };
-        PostReport::report(&mut context.pool(), &report_form).await?;
+        let report = PostReport::report(&mut context.pool(), &report_form).await?;
+        send_webhooks(
+          WebhookEventType::ReportCreated,
+          Some(post.community_id),
+          &WebhookReport::PostReport(&report),
+          context,
+        );
       }
       ReportableObjects::Left(PostOrComment::Right(comment)) => {
+        let post = Post::read(&mut context.pool(), comment.post_id).await?;
+        let rule_id = self.rule_id(post.community_id, context).await;
         let report_form = CommentReportForm {
           creator_id: actor.id,
           comment_id: comment.id,
           original_comment_text: comment.content.clone(),
           reason,
           violates_instance_rules: false,
+          rule_id,
         };
-        CommentReport::report(&mut context.pool(), &report_form).await?;
+        let report = CommentReport::report(&mut context.pool(), &report_form).await?;
+        send_webhooks(
+          WebhookEventType::ReportCreated,
+          Some(post.community_id),
+          &WebhookReport::CommentReport(&report),
+          context,
+        );
       }
       ReportableObjects::Right(Either::Left(community)) => {
         let report_form = CommunityReportForm {
@@ -164,7 +200,13 @@ impl Activity for Report {
           original_community_summary: community.summary.clone(),
           original_community_sidebar: community.sidebar.clone(),
         };
-        CommunityReport::report(&mut context.pool(), &report_form).await?;
+        let report = CommunityReport::report(&mut context.pool(), &report_form).await?;
+        send_webhooks(
+          WebhookEventType::ReportCreated,
+          None,
+          &WebhookReport::CommunityReport(&report),
+          context,
+        );
       }
       ReportableObjects::Right(Either::Right(private_message)) => {
         let form = PrivateMessageReportForm {
@@ -173,7 +215,13 @@ impl Activity for Report {
           original_pm_text: private_message.content.clone(),
           reason,
         };
-        PrivateMessageReport::report(&mut context.pool(), &form).await?;
+        let report = PrivateMessageReport::report(&mut context.pool(), &form).await?;
+        send_webhooks(
+          WebhookEventType::ReportCreated,
+          None,
+          &WebhookReport::PrivateMessageReport(&report),
+          context,
+        );
       }
     };
 
@@ -52,7 +52,7 @@ impl ResolveReport {
   ) -> LemmyResult<()> {
     let kind = ResolveType::Resolve;
     let id = generate_activity_id(kind.clone(), &context)?;
-    let report = Report::new(&object_id, report_creator, receiver, None, &context)?;
+    let report = Report::new(&object_id, report_creator, receiver, None, None, &context)?;
     let resolve = ResolveReport {
       actor: actor.id().clone().into(),
       to: [receiver.id().clone().into()],
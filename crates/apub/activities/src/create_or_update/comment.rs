@@ -30,6 +30,7 @@ use lemmy_apub_objects::{
 use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentActions, CommentLikeForm},
+    comment_revision::CommentRevision,
     community::Community,
     person::Person,
     post::Post,
@@ -123,7 +124,7 @@ impl Activity for CreateOrUpdateNote {
     let existing_comment = self.object.id.dereference_local(context).await.ok();
     let (post, _) = self.object.get_parents(context).await?;
     if let (Some(distinguished), Some(existing_comment)) =
-      (self.object.distinguished, existing_comment)
+      (self.object.distinguished, &existing_comment)
       && distinguished != existing_comment.distinguished
     {
       let creator = self.actor.dereference(context).await?;
@@ -131,6 +132,9 @@ impl Activity for CreateOrUpdateNote {
     }
 
     let comment = ApubComment::from_json(self.object, context).await?;
+    if let Some(existing_comment) = &existing_comment {
+      CommentRevision::create_if_changed(&mut context.pool(), existing_comment, &comment).await?;
+    }
 
     // author likes their own comment by default
     let like_form = CommentLikeForm::new(comment.id, comment.creator_id, Some(true));
@@ -6,6 +6,7 @@ use lemmy_utils::error::LemmyResult;
 
 pub mod comment;
 pub(crate) mod note_wrapper;
+pub mod poll_vote;
 pub mod post;
 pub mod private_message;
 
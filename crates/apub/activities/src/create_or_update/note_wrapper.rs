@@ -2,13 +2,15 @@ use crate::protocol::create_or_update::{
   note::CreateOrUpdateNote,
   note_wrapper::CreateOrUpdateNoteWrapper,
   page::CreateOrUpdatePage,
+  poll_vote::CreatePollVote,
   private_message::CreateOrUpdatePrivateMessage,
 };
 use activitypub_federation::{config::Data, traits::Activity};
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_apub_objects::{objects::community::ApubCommunity, utils::protocol::InCommunity};
+use lemmy_db_schema::source::{post::Post, post_poll::PostPoll};
 use lemmy_utils::error::{LemmyError, LemmyResult};
-use serde_json::{from_value, to_value};
+use serde_json::{Value, from_value, to_value};
 use url::Url;
 
 /// In Activitypub, both private messages and comments are represented by `type: Note` which
@@ -38,6 +40,19 @@ impl Activity for CreateOrUpdateNoteWrapper {
     // additional data in field `other: Map<String, Value>`.
     let val = to_value(self)?;
 
+    // Votes in polls are sent as notes with a name and without content, which reply to a local
+    // poll. Check this first to avoid verifying every comment as a vote.
+    let object = val.get("object");
+    let is_poll_vote = object.and_then(|o| o.get("name")).is_some()
+      && object.and_then(|o| o.get("content")).is_none()
+      && replies_to_local_poll(object, context).await;
+    if is_poll_vote {
+      let poll_vote = from_value::<CreatePollVote>(val)?;
+      CreatePollVote::verify(&poll_vote, context).await?;
+      CreatePollVote::receive(poll_vote, context).await?;
+      return Ok(());
+    }
+
     // Convert self to a comment and get the community. If the conversion is
     // successful and a community is returned, this is a comment.
     let comment = from_value::<CreateOrUpdateNote>(val.clone());
@@ -75,3 +90,22 @@ impl InCommunity for CreateOrUpdateNoteWrapper {
     comment.community(context).await
   }
 }
+
+/// Checks if the note replies to a poll on this instance, without fetching anything.
+async fn replies_to_local_poll(object: Option<&Value>, context: &Data<LemmyContext>) -> bool {
+  let Some(in_reply_to) = object
+    .and_then(|o| o.get("inReplyTo"))
+    .and_then(Value::as_str)
+    .and_then(|u| Url::parse(u).ok())
+  else {
+    return false;
+  };
+  let Ok(Some(post)) = Post::read_from_apub_id(&mut context.pool(), in_reply_to.into()).await
+  else {
+    return false;
+  };
+  post.local
+    && PostPoll::read_for_post(&mut context.pool(), post.id)
+      .await
+      .is_ok_and(|p| p.is_some())
+}
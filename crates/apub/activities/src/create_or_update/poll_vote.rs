@@ -0,0 +1,148 @@
+use crate::{
+  generate_activity_id,
+  protocol::{
+    CreateOrUpdateType,
+    create_or_update::{
+      page::CreateOrUpdatePage,
+      poll_vote::{CreatePollVote, PollVote},
+    },
+  },
+  send_lemmy_activity,
+  verify_person,
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::CreateType, object::NoteType},
+  protocol::verification::{verify_domains_match, verify_urls_match},
+  traits::{Activity, Actor, Object},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::{
+  objects::{community::ApubCommunity, person::ApubPerson},
+  utils::functions::verify_person_in_community,
+};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  community::Community,
+  person::Person,
+  post::Post,
+  post_poll::{PostPoll, PostPollOption, PostPollVote, PostPollVoteForm},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use url::Url;
+
+/// Sends the votes of a local user in a remote poll to the poll creator. There is one activity
+/// for each selected option.
+pub(crate) async fn send_poll_vote(
+  post: Post,
+  actor: Person,
+  options: Vec<PostPollOption>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let actor: ApubPerson = actor.into();
+  let creator: ApubPerson = Person::read(&mut context.pool(), post.creator_id)
+    .await?
+    .into();
+  let to = vec![creator.id().clone()];
+
+  for option in options {
+    let id = generate_activity_id(CreateType::Create, &context)?;
+    let object_id = Url::parse(&format!(
+      "{}#votes/{}/{}",
+      actor.ap_id, post.id, option.id.0
+    ))?;
+    let vote = CreatePollVote {
+      id,
+      actor: actor.id().clone().into(),
+      to: to.clone(),
+      object: PollVote {
+        id: object_id,
+        kind: NoteType::Note,
+        name: option.name,
+        attributed_to: actor.id().clone().into(),
+        to: to.clone(),
+        in_reply_to: post.ap_id.clone().into(),
+      },
+      kind: CreateType::Create,
+    };
+    let inbox = ActivitySendTargets::to_inbox(creator.shared_inbox_or_inbox());
+    send_lemmy_activity(&context, vote, &actor, inbox, true).await?;
+  }
+  Ok(())
+}
+
+#[async_trait::async_trait]
+impl Activity for CreatePollVote {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    verify_person(&self.actor, context).await?;
+    verify_urls_match(self.actor.inner(), self.object.attributed_to.inner())?;
+    verify_domains_match(self.actor.inner(), &self.object.id)?;
+    // Votes are only counted by the instance of the poll creator
+    let post = self.object.in_reply_to.dereference_local(context).await?;
+    if !post.local {
+      return Err(LemmyErrorType::InvalidPollVote.into());
+    }
+    PostPoll::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .ok_or(LemmyErrorType::InvalidPollVote)?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    let post = self.object.in_reply_to.dereference_local(context).await?;
+    let poll = PostPoll::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .ok_or(LemmyErrorType::InvalidPollVote)?;
+    if poll.is_closed() {
+      return Err(LemmyErrorType::PollClosed.into());
+    }
+    let community: ApubCommunity = Community::read(&mut context.pool(), post.community_id)
+      .await?
+      .into();
+    verify_person_in_community(&self.actor, &community, context).await?;
+
+    let option = PostPollOption::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .find(|o| o.name == self.object.name)
+      .ok_or(LemmyErrorType::InvalidPollVote)?;
+    let actor = self.actor.dereference(context).await?;
+    if poll.multiple_choice {
+      let form = PostPollVoteForm {
+        post_poll_option_id: option.id,
+        person_id: actor.id,
+        post_id: post.id,
+      };
+      PostPollVote::add(&mut context.pool(), &form).await?;
+    } else {
+      // A single choice vote replaces the previous vote of the person
+      PostPollVote::vote(&mut context.pool(), post.id, actor.id, &[option.id]).await?;
+    }
+
+    // Federate the new vote counts to followers of the community
+    let poll = PostPoll::update_counts(&mut context.pool(), post.id).await?;
+    if !poll.results_hidden() {
+      let creator_id = post.creator_id;
+      CreateOrUpdatePage::send(
+        post.0,
+        creator_id,
+        CreateOrUpdateType::Update,
+        context.clone(),
+      )
+      .await?;
+    }
+    Ok(())
+  }
+}
@@ -3,7 +3,11 @@ use crate::{
   community::send_activity_in_community,
   create_or_update::{parse_apub_mentions, tagged_user_inboxes},
   generate_activity_id,
-  protocol::{CreateOrUpdateType, create_or_update::page::CreateOrUpdatePage},
+  protocol::{
+    CreateOrUpdateType,
+    community::announce::AnnounceActivity,
+    create_or_update::page::CreateOrUpdatePage,
+  },
 };
 use activitypub_federation::{
   config::Data,
@@ -12,9 +16,11 @@ use activitypub_federation::{
 };
 use chrono::Utc;
 use lemmy_api_utils::{
+  automod::{AutomodOutcome, run_automod},
   context::LemmyContext,
   notify::NotifyData,
   utils::check_community_deleted_removed,
+  webhooks::send_webhooks,
 };
 use lemmy_apub_objects::{
   objects::{
@@ -32,13 +38,15 @@ use lemmy_db_schema::{
     community::Community,
     person::Person,
     post::{Post, PostActions, PostLikeForm, PostUpdateForm},
+    post_revision::PostRevision,
   },
   traits::Likeable,
 };
-use lemmy_db_schema_file::PersonId;
+use lemmy_db_schema_file::{PersonId, enums::WebhookEventType};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use tracing::warn;
 use url::Url;
 
 impl CreateOrUpdatePage {
@@ -77,6 +85,14 @@ impl CreateOrUpdatePage {
       CreateOrUpdatePage::new(post.into(), &person, &community, kind, &context).await?;
     let inboxes = tagged_user_inboxes(&create_or_update.object.tag, &context).await?;
     let activity = AnnouncableActivities::CreateOrUpdatePost(create_or_update);
+    if !person.local {
+      // Activities can't be signed for remote users. This happens when a mod approves a remote
+      // post, which was held back from the community followers until now.
+      if community.local && community.visibility.can_federate() {
+        AnnounceActivity::send(activity.try_into()?, &community, &context).await?;
+      }
+      return Ok(());
+    }
     send_activity_in_community(activity, &person, &community, inboxes, false, &context).await?;
     Ok(())
   }
@@ -108,13 +124,15 @@ impl Activity for CreateOrUpdatePage {
     let community = self.community(context).await?;
     let is_same_actor =
       verify_urls_match(self.actor.inner(), self.object.creator()?.inner()).is_ok();
-    let original_post =
-      Post::read_from_apub_id(&mut context.pool(), self.object.id.clone().into()).await;
+    let original_post = Post::read_from_apub_id(&mut context.pool(), self.object.id.clone().into())
+      .await
+      .ok()
+      .flatten();
     let is_mod_action = verify_mod_action(&self.actor, self.object.id.inner(), &community, context)
       .await
       .is_ok();
     // allow mods to edit the post
-    if !is_same_actor && let Ok(Some(post)) = original_post {
+    if !is_same_actor && let Some(post) = &original_post {
       if is_mod_action {
         let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
         let form = PostUpdateForm {
@@ -123,7 +141,7 @@ impl Activity for CreateOrUpdatePage {
           ..Default::default()
         };
         Post::update(&mut context.pool(), post.id, &form).await?;
-        update_apub_post_tags(&self.object, &post, context).await?;
+        update_apub_post_tags(&self.object, post, context).await?;
         return Ok(());
       } else {
         return Err(LemmyErrorType::NotAModerator.into());
@@ -137,7 +155,25 @@ impl Activity for CreateOrUpdatePage {
     verify_urls_match(self.actor.inner(), self.object.creator()?.inner())?;
     let site_view = SiteView::read_local(&mut context.pool()).await?;
 
-    let post = ApubPost::from_json(self.object.clone(), context).await?;
+    let actor = self.actor.dereference(context).await?;
+    let mut post = ApubPost::from_json(self.object.clone(), context).await?;
+    if let Some(original_post) = &original_post {
+      PostRevision::create_if_changed(&mut context.pool(), original_post, &post).await?;
+    }
+
+    // New posts in local communities may need mod approval, in which case they are announced
+    // once they get approved.
+    let approval_pending =
+      original_post.is_none() && !is_mod_action && community.post_needs_approval(&actor);
+    if approval_pending {
+      let form = PostUpdateForm {
+        approval_pending: Some(true),
+        ..Default::default()
+      };
+      post = Post::update(&mut context.pool(), post.id, &form)
+        .await?
+        .into();
+    }
 
     // author likes their own post by default
     let like_form = PostLikeForm::new(post.id, post.creator_id, Some(true));
@@ -148,14 +184,38 @@ impl Activity for CreateOrUpdatePage {
 
     let do_send_email =
       self.kind == CreateOrUpdateType::Create && !site_view.local_site.email_notifications_disabled;
-    let actor = self.actor.dereference(context).await?;
 
-    NotifyData {
-      apub_mentions: Some(parse_apub_mentions(&self.object.tag, context).await?),
-      do_send_email,
-      ..NotifyData::new(post.0, actor.0, community.0)
+    // Held posts are not announced, see RawAnnouncableActivities::receive
+    let is_new = self.kind == CreateOrUpdateType::Create && original_post.is_none();
+    let automod = if is_new {
+      run_automod(&post, &community, context)
+        .await
+        .inspect_err(|e| warn!("Failed to run automod: {e}"))
+        .unwrap_or_default()
+    } else {
+      AutomodOutcome::default()
+    };
+    let published = !post.approval_pending && !automod.held;
+
+    // Pending posts are sent to webhooks once they get approved
+    if is_new && published && !automod.removed {
+      send_webhooks(
+        WebhookEventType::PostCreated,
+        Some(post.community_id),
+        &post.0,
+        context,
+      );
+    }
+
+    // Pending posts notify once they get approved
+    if published {
+      NotifyData {
+        apub_mentions: Some(parse_apub_mentions(&self.object.tag, context).await?),
+        do_send_email,
+        ..NotifyData::new(post.0, actor.0, community.0)
+      }
+      .send(context);
     }
-    .send(context);
 
     Ok(())
   }
@@ -20,6 +20,7 @@ use serde::Serialize;
 
 pub(crate) mod accept;
 pub(crate) mod follow;
+pub(crate) mod move_person;
 pub(crate) mod reject;
 pub(crate) mod undo_follow;
 
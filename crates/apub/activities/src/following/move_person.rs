@@ -0,0 +1,76 @@
+use crate::{
+  generate_activity_id,
+  protocol::following::move_person::MovePerson,
+  send_lemmy_activity,
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::MoveType, public},
+  protocol::verification::verify_urls_match,
+  traits::{Activity, Object},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::objects::person::ApubPerson;
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  person::{Person, PersonUpdateForm},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use url::Url;
+
+impl MovePerson {
+  pub async fn send(
+    actor: &ApubPerson,
+    target: &ApubPerson,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<()> {
+    let move_ = MovePerson {
+      actor: actor.id().clone().into(),
+      to: vec![public()],
+      object: actor.id().clone().into(),
+      target: target.id().clone().into(),
+      kind: MoveType::Move,
+      id: generate_activity_id(MoveType::Move, context)?,
+    };
+    let inboxes = ActivitySendTargets::to_all_instances();
+    send_lemmy_activity(context, move_, actor, inboxes, true).await
+  }
+}
+
+#[async_trait::async_trait]
+impl Activity for MovePerson {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, _context: &Data<LemmyContext>) -> LemmyResult<()> {
+    // A user can only move their own account
+    verify_urls_match(self.actor.inner(), self.object.inner())?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let old = self.object.dereference(context).await?;
+    // Always refetch the new account, as the alias was likely added just before the move
+    let new = self.target.dereference_forced(context).await?;
+    if old.id == new.id || !new.also_known_as.contains(&old.ap_id) {
+      return Err(LemmyErrorType::AccountMoveAliasMissing.into());
+    }
+
+    let form = PersonUpdateForm {
+      moved_to: Some(Some(new.ap_id.clone())),
+      ..Default::default()
+    };
+    Person::update(&mut context.pool(), old.id, &form).await?;
+    Person::migrate_actions(&mut context.pool(), old.id, new.id).await?;
+    Ok(())
+  }
+}
@@ -5,7 +5,7 @@ use crate::{
     lock::send_lock,
     update::{send_update_community, send_update_multi_community},
   },
-  create_or_update::private_message::send_create_or_update_pm,
+  create_or_update::{poll_vote::send_poll_vote, private_message::send_create_or_update_pm},
   deletion::{
     DeletableObjects,
     send_apub_delete_in_community,
@@ -17,6 +17,7 @@ use crate::{
     CreateOrUpdateType,
     community::{report::Report, resolve_report::ResolveReport, warn::Warn},
     create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
+    following::move_person::MovePerson,
   },
   voting::send_like_activity,
 };
@@ -201,6 +202,11 @@ pub async fn match_outgoing_activities(
         .await
       }
       FeaturePost(post, actor, featured) => send_feature_post(post, actor, featured, context).await,
+      VotePostPoll {
+        post,
+        actor,
+        options,
+      } => send_poll_vote(post, actor, options, context).await,
       CreateComment(comment) => {
         let creator_id = comment.creator_id;
         CreateOrUpdateNote::send(comment, creator_id, CreateOrUpdateType::Create, context).await
@@ -335,17 +341,20 @@ pub async fn match_outgoing_activities(
         send_apub_delete_private_message(&person.into(), pm, deleted, context).await
       }
       DeleteUser(person, remove_data) => send_apub_delete_user(person, remove_data, context).await,
+      MoveUser(old, new) => MovePerson::send(&old.into(), &new.into(), &context).await,
       CreateReport {
         object_id,
         actor,
         receiver,
         reason,
+        rule,
       } => {
         Report::send(
           ObjectId::from(object_id),
           &actor.into(),
           &receiver.map_either(Into::into, Into::into),
           reason,
+          rule,
           context,
         )
         .await
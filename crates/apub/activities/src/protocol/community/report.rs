@@ -29,6 +29,8 @@ pub struct Report {
   pub(crate) kind: FlagType,
   pub(crate) id: Url,
   pub(crate) audience: Option<ObjectId<ApubCommunity>>,
+  /// The community rule which is violated, as sent by Lemmy
+  pub(crate) rule: Option<Url>,
 }
 
 impl Report {
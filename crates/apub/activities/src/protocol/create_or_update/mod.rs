@@ -1,6 +1,7 @@
 pub mod note;
 pub(crate) mod note_wrapper;
 pub mod page;
+pub mod poll_vote;
 pub mod private_message;
 
 #[cfg(test)]
@@ -9,6 +10,7 @@ mod tests {
   use crate::protocol::create_or_update::{
     note::CreateOrUpdateNote,
     page::CreateOrUpdatePage,
+    poll_vote::CreatePollVote,
     private_message::CreateOrUpdatePrivateMessage,
   };
   use lemmy_apub_objects::utils::test::{test_json, test_parse_lemmy_item};
@@ -37,6 +39,10 @@ mod tests {
     test_json::<CreateOrUpdateNoteWrapper>(
       "../apub/assets/lemmy/activities/create_or_update/create_private_message_0.19.json",
     )?;
+    test_json::<CreatePollVote>("../apub/assets/mastodon/activities/create_poll_vote.json")?;
+    test_json::<CreateOrUpdateNoteWrapper>(
+      "../apub/assets/mastodon/activities/create_poll_vote.json",
+    )?;
     test_parse_lemmy_item::<NoteWrapper>("../apub/assets/lemmy/objects/comment.json")?;
     test_parse_lemmy_item::<NoteWrapper>("../apub/assets/lemmy/objects/private_message.json")?;
     Ok(())
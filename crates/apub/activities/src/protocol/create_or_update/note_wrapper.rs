@@ -1,4 +1,4 @@
-use activitypub_federation::kinds::object::NoteType;
+use activitypub_federation::{kinds::object::NoteType, protocol::helpers::deserialize_one_or_many};
 use lemmy_apub_objects::protocol::private_message::PrivateMessageType;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -9,9 +9,9 @@ use url::Url;
 pub struct CreateOrUpdateNoteWrapper {
   pub(crate) object: NoteWrapper,
   pub(crate) id: Url,
-  #[serde(default)]
+  #[serde(default, deserialize_with = "deserialize_one_or_many")]
   pub(crate) to: Vec<Url>,
-  #[serde(default)]
+  #[serde(default, deserialize_with = "deserialize_one_or_many")]
   pub(crate) cc: Vec<Url>,
   pub(crate) actor: Url,
   #[serde(flatten)]
@@ -0,0 +1,37 @@
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::{activity::CreateType, object::NoteType},
+  protocol::helpers::deserialize_one_or_many,
+};
+use lemmy_apub_objects::objects::{person::ApubPerson, post::ApubPost};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A vote in a poll, in the format used by Mastodon. It is sent directly to the poll creator, who
+/// counts the votes and federates the new counts with an update of the poll.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePollVote {
+  pub(crate) id: Url,
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: PollVote,
+  #[serde(rename = "type")]
+  pub(crate) kind: CreateType,
+}
+
+/// A note without content, whose `name` is the option which was voted for. For multiple choice
+/// polls there is one vote for each selected option.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollVote {
+  pub(crate) id: Url,
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) name: String,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) in_reply_to: ObjectId<ApubPost>,
+}
@@ -1,11 +1,17 @@
 pub(crate) mod accept;
 pub mod follow;
+pub mod move_person;
 pub(crate) mod reject;
 pub mod undo_follow;
 
 #[cfg(test)]
 mod tests {
-  use crate::protocol::following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow};
+  use crate::protocol::following::{
+    accept::AcceptFollow,
+    follow::Follow,
+    move_person::MovePerson,
+    undo_follow::UndoFollow,
+  };
   use lemmy_apub_objects::utils::test::test_parse_lemmy_item;
   use lemmy_utils::error::LemmyResult;
 
@@ -16,6 +22,7 @@ mod tests {
     test_parse_lemmy_item::<UndoFollow>(
       "../apub/assets/lemmy/activities/following/undo_follow.json",
     )?;
+    test_parse_lemmy_item::<MovePerson>("../apub/assets/lemmy/activities/following/move.json")?;
     Ok(())
   }
 }
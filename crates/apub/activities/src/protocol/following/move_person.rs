@@ -0,0 +1,24 @@
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::activity::MoveType,
+  protocol::helpers::deserialize_one_or_many,
+};
+use lemmy_apub_objects::objects::person::ApubPerson;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by a user who moved to a new account. Actor and object are the old account, target is the
+/// new account. Compatible with Mastodon's account migration.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePerson {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: ObjectId<ApubPerson>,
+  pub(crate) target: ObjectId<ApubPerson>,
+  #[serde(rename = "type")]
+  pub(crate) kind: MoveType,
+  pub(crate) id: Url,
+}
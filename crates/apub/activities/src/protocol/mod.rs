@@ -64,7 +64,12 @@ mod tests {
       page::CreateOrUpdatePage,
     },
     deletion::delete::Delete,
-    following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow},
+    following::{
+      accept::AcceptFollow,
+      follow::Follow,
+      move_person::MovePerson,
+      undo_follow::UndoFollow,
+    },
     voting::{undo_vote::UndoVote, vote::Vote},
   };
   use lemmy_apub_objects::utils::test::test_json;
@@ -93,6 +98,7 @@ mod tests {
     test_json::<Vote>("../apub/assets/mastodon/activities/like_page.json")?;
     test_json::<UndoVote>("../apub/assets/mastodon/activities/undo_like_page.json")?;
     test_json::<Report>("../apub/assets/mastodon/activities/flag.json")?;
+    test_json::<MovePerson>("../apub/assets/mastodon/activities/move.json")?;
     Ok(())
   }
 
@@ -26,8 +26,16 @@ use lemmy_utils::{
   FEDERATION_CONTEXT,
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
 };
+use prometheus::{IntCounter, IntCounterVec, Opts, default_registry};
 use serde::Deserialize;
-use std::time::Duration;
+use std::{
+  sync::{
+    Arc,
+    LazyLock,
+    atomic::{AtomicBool, Ordering},
+  },
+  time::Duration,
+};
 use tokio::time::timeout;
 use tracing::debug;
 use url::Url;
@@ -41,25 +49,68 @@ pub mod site;
 
 const INCOMING_ACTIVITY_TIMEOUT: Duration = Duration::from_secs(9);
 
+struct IncomingMetrics {
+  activities: IntCounterVec,
+  verification_failures: IntCounter,
+}
+
+/// Registered in the default registry, so they are included in the output of the Prometheus
+/// endpoint if it is enabled.
+static INCOMING_METRICS: LazyLock<IncomingMetrics> = LazyLock::new(|| {
+  #[expect(clippy::expect_used)]
+  create_incoming_metrics().expect("create incoming federation metrics")
+});
+
+fn create_incoming_metrics() -> prometheus::Result<IncomingMetrics> {
+  let metrics = IncomingMetrics {
+    activities: IntCounterVec::new(
+      Opts::new(
+        "lemmy_federation_incoming_activities_total",
+        "Number of verified activities received in the shared inbox, by type",
+      ),
+      &["type"],
+    )?,
+    verification_failures: IntCounter::with_opts(Opts::new(
+      "lemmy_federation_incoming_verification_failures_total",
+      "Number of activities received in the shared inbox which failed parsing or verification",
+    ))?,
+  };
+  default_registry().register(Box::new(metrics.activities.clone()))?;
+  default_registry().register(Box::new(metrics.verification_failures.clone()))?;
+  Ok(metrics)
+}
+
 pub async fn shared_inbox(
   request: HttpRequest,
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> LemmyResult<HttpResponse> {
+  let verified = Arc::new(AtomicBool::new(false));
+  let hook = Dummy {
+    verified: verified.clone(),
+  };
   let receive_fut =
     receive_activity_with_hook::<SharedInboxActivities, UserOrCommunity, LemmyContext>(
-      request, body, Dummy, &data,
+      request, body, hook, &data,
     );
   // Set a timeout shorter than `REQWEST_TIMEOUT` for processing incoming activities. This is to
   // avoid taking a long time to process an incoming activity when a required data fetch times out.
   // In this case our own instance would timeout and be marked as dead by the sender. Better to
   // consider the activity broken and move on.
-  timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
+  let res = timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
     .await
-    .with_lemmy_type(UntranslatedError::InboxTimeout.into())?
+    .with_lemmy_type(UntranslatedError::InboxTimeout.into())
+    .and_then(|r| r);
+  // The hook is only called after the activity was verified
+  if res.is_err() && !verified.load(Ordering::Relaxed) {
+    INCOMING_METRICS.verification_failures.inc();
+  }
+  res
 }
 
-struct Dummy;
+struct Dummy {
+  verified: Arc<AtomicBool>,
+}
 
 impl ReceiveActivityHook<SharedInboxActivities, UserOrCommunity, LemmyContext> for Dummy {
   async fn hook(
@@ -68,10 +119,16 @@ impl ReceiveActivityHook<SharedInboxActivities, UserOrCommunity, LemmyContext> f
     _actor: &UserOrCommunity,
     context: &Data<LemmyContext>,
   ) -> LemmyResult<()> {
+    self.verified.store(true, Ordering::Relaxed);
+
     // Store received activities in the database. This ensures that the same activity doesn't get
     // received and processed more than once, which would be a waste of resources.
     debug!("Received activity {}", activity.id().to_string());
     ReceivedActivity::create(&mut context.pool(), &activity.id().clone().into()).await?;
+    INCOMING_METRICS
+      .activities
+      .with_label_values(&[activity.kind_name()])
+      .inc();
 
     // This could also take the actor as param, but lifetimes and serde derives are tricky.
     // It is really a before hook, but doesnt allow modifying the data. It could use a
@@ -6,8 +6,10 @@ use crate::{
       append_attachments_to_comment,
       check_apub_id_valid_with_strictness,
       context_url,
+      creator_flair_id,
       generate_to,
       read_from_string_or_source,
+      update_creator_flair,
       verify_person_in_community,
       verify_visibility,
     },
@@ -139,6 +141,7 @@ impl Object for ApubComment {
       audience: Some(community.ap_id.into()),
       attachment: vec![],
       context: Some(context_url(&self.ap_id)),
+      flair: creator_flair_id(creator_id, community_id, context).await?,
     };
 
     Ok(note)
@@ -239,6 +242,13 @@ impl Object for ApubComment {
       parent_comment_path.as_ref(),
     )
     .await?;
+    update_creator_flair(
+      note.flair.as_ref(),
+      creator.id,
+      comment.community_id,
+      context,
+    )
+    .await?;
     plugin_hook_after("federated_comment_after_receive", &comment);
     Ok(comment.into())
   }
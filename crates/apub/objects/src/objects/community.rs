@@ -1,6 +1,11 @@
 use crate::{
   objects::instance::fetch_instance_actor_for_object,
-  protocol::{group::Group, tags::ApubCommunityTag},
+  protocol::{
+    flair::ApubCommunityFlair,
+    group::Group,
+    rule::ApubCommunityRule,
+    tags::ApubCommunityTag,
+  },
   utils::{
     functions::{
       GetActorType,
@@ -35,7 +40,10 @@ use lemmy_db_schema::{
   source::{
     actor_language::CommunityLanguage,
     community::{Community, CommunityInsertForm, CommunityUpdateForm},
+    community_flair::CommunityFlair,
     community_tag::CommunityTag,
+    custom_emoji::CustomEmoji,
+    rule::Rule,
   },
   traits::ApubActor,
 };
@@ -118,6 +126,15 @@ impl Object for ApubCommunity {
     let langs = CommunityLanguage::read(&mut data.pool(), community_id).await?;
     let language = LanguageTag::new_multiple(langs, &mut data.pool()).await?;
     let community_tags = CommunityTag::read_for_community(&mut data.pool(), community_id).await?;
+    let mut flair = vec![];
+    for f in CommunityFlair::read_for_community(&mut data.pool(), community_id).await? {
+      let emoji = match f.custom_emoji_id {
+        Some(id) => CustomEmoji::read(&mut data.pool(), id).await.ok(),
+        None => None,
+      };
+      flair.push(ApubCommunityFlair::to_json(f, emoji.as_ref()));
+    }
+    let rules = Rule::read_for_community(&mut data.pool(), Some(community_id)).await?;
     let group = Group {
       kind: GroupType::Group,
       id: self.id().clone().into(),
@@ -149,6 +166,8 @@ impl Object for ApubCommunity {
         .into_iter()
         .map(ApubCommunityTag::to_json)
         .collect(),
+      flair,
+      rules: rules.into_iter().map(ApubCommunityRule::to_json).collect(),
     };
     Ok(group)
   }
@@ -250,6 +269,34 @@ impl Object for ApubCommunity {
     let existing_tags = CommunityTag::read_for_community(&mut context.pool(), community.id).await?;
     CommunityTag::update_many(&mut context.pool(), new_tags, existing_tags).await?;
 
+    let new_flairs = group
+      .flair
+      .iter()
+      .map(|f| {
+        let mut form = f.to_insert_form(community.id);
+        form.text = remove_slurs(&form.text, &slur_regex);
+        form
+      })
+      .collect();
+    let existing_flairs =
+      CommunityFlair::read_for_community(&mut context.pool(), community.id).await?;
+    CommunityFlair::update_many(&mut context.pool(), new_flairs, existing_flairs).await?;
+
+    // The position of rules is given by their order in the group
+    let new_rules = group
+      .rules
+      .iter()
+      .zip(0..)
+      .map(|(r, position)| {
+        let mut form = r.to_insert_form(community.id, position);
+        form.title = remove_slurs(&form.title, &slur_regex);
+        form.description = form.description.map(|d| remove_slurs(&d, &slur_regex));
+        form
+      })
+      .collect();
+    let existing_rules = Rule::read_for_community(&mut context.pool(), Some(community.id)).await?;
+    Rule::update_many(&mut context.pool(), new_rules, existing_rules).await?;
+
     let community: ApubCommunity = community.into();
 
     // These collections are not necessary for Lemmy to work, so ignore errors. Reset request count
@@ -122,6 +122,8 @@ impl Object for ApubPerson {
       public_key: self.public_key(),
       updated: self.updated_at,
       inbox: self.inbox_url.clone().into(),
+      also_known_as: self.also_known_as.iter().cloned().map(Into::into).collect(),
+      moved_to: self.moved_to.clone().map(Into::into),
     };
     Ok(person)
   }
@@ -181,6 +183,8 @@ impl Object for ApubPerson {
       ),
       matrix_user_id: person.matrix_user_id,
       instance_id,
+      also_known_as: Some(person.also_known_as.into_iter().map(Into::into).collect()),
+      moved_to: person.moved_to.map(Into::into),
     };
     let person = DbPerson::upsert(&mut context.pool(), &person_form).await?;
 
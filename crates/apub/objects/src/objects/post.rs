@@ -1,14 +1,16 @@
 use crate::{
   protocol::{
-    page::{Attachment, Page, PageType},
+    page::{Attachment, Page, PageType, PollOption, PollOptionReplies},
     tags::{ApubCommunityTag, ApubTag, Hashtag, HashtagType},
   },
   utils::{
     functions::{
       check_apub_id_valid_with_strictness,
       context_url,
+      creator_flair_id,
       generate_to,
       read_from_string_or_source_opt,
+      update_creator_flair,
       verify_person_in_community,
       verify_visibility,
     },
@@ -19,6 +21,7 @@ use crate::{
 };
 use activitypub_federation::{
   config::Data,
+  kinds::{collection::CollectionType, object::NoteType},
   protocol::{
     values::MediaTypeMarkdownOrHtml,
     verification::{verify_domains_match, verify_is_remote_object},
@@ -26,7 +29,7 @@ use activitypub_federation::{
   traits::Object,
 };
 use anyhow::anyhow;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use html2text::{from_read_with_decorator, render::TrivialDecorator};
 use lemmy_api_utils::{
   context::LemmyContext,
@@ -40,12 +43,16 @@ use lemmy_api_utils::{
     update_post_tags,
   },
 };
-use lemmy_db_schema::source::{
-  community::Community,
-  community_tag::CommunityTag,
-  local_site::LocalSite,
-  person::Person,
-  post::{Post, PostInsertForm, PostUpdateForm},
+use lemmy_db_schema::{
+  newtypes::PostId,
+  source::{
+    community::Community,
+    community_tag::CommunityTag,
+    local_site::LocalSite,
+    person::Person,
+    post::{Post, PostInsertForm, PostUpdateForm},
+    post_poll::{PostPoll, PostPollInsertForm, PostPollOption, PostPollOptionInsertForm},
+  },
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_site::SiteView;
@@ -64,6 +71,7 @@ use stringreader::StringReader;
 use url::Url;
 
 const MAX_TITLE_LENGTH: usize = 200;
+const MAX_POLL_OPTION_LENGTH: usize = 200;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ApubPost(pub Post);
@@ -157,8 +165,13 @@ impl Object for ApubPost {
     let maa = collect_non_local_mentions(self.body.as_deref(), None, &community, context).await?;
     tags.extend(maa.mentions);
 
+    let poll = ApubPoll::read(self.id, context).await?;
     let page = Page {
-      kind: PageType::Page,
+      kind: if poll.is_some() {
+        PageType::Question
+      } else {
+        PageType::Page
+      },
       id: self.ap_id.clone().into(),
       attributed_to: AttributedTo::Lemmy(creator.ap_id.into()),
       to: generate_to(&community)?,
@@ -177,6 +190,12 @@ impl Object for ApubPost {
       in_reply_to: None,
       tag: tags,
       context: Some(context_url(&self.ap_id)),
+      one_of: poll.as_ref().and_then(|p| p.one_of.clone()),
+      any_of: poll.as_ref().and_then(|p| p.any_of.clone()),
+      end_time: poll.as_ref().and_then(|p| p.end_time),
+      closed: poll.as_ref().and_then(|p| p.closed),
+      voters_count: poll.and_then(|p| p.voters_count),
+      flair: creator_flair_id(creator_id, community_id, context).await?,
     };
     Ok(page)
   }
@@ -310,6 +329,8 @@ impl Object for ApubPost {
     plugin_hook_after("federated_post_after_receive", &post);
 
     update_apub_post_tags(&page, &post, context).await?;
+    update_creator_flair(page.flair.as_ref(), creator.id, community.id, context).await?;
+    ApubPoll::from_page(&page, &post, context).await?;
 
     let post_ = post.clone();
     let context_ = context.clone();
@@ -349,6 +370,90 @@ pub async fn update_apub_post_tags(
   Ok(())
 }
 
+/// The poll fields of a page.
+struct ApubPoll {
+  one_of: Option<Vec<PollOption>>,
+  any_of: Option<Vec<PollOption>>,
+  end_time: Option<DateTime<Utc>>,
+  closed: Option<DateTime<Utc>>,
+  voters_count: Option<i32>,
+}
+
+impl ApubPoll {
+  /// Returns `None` if the post is not a poll.
+  async fn read(post_id: PostId, context: &LemmyContext) -> LemmyResult<Option<Self>> {
+    let Some(poll) = PostPoll::read_for_post(&mut context.pool(), post_id).await? else {
+      return Ok(None);
+    };
+    // Vote counts are left out while they are hidden, as Mastodon has no way to mark them as
+    // hidden.
+    let results_hidden = poll.results_hidden();
+    let options = PostPollOption::read_for_post(&mut context.pool(), post_id)
+      .await?
+      .into_iter()
+      .map(|o| PollOption {
+        kind: NoteType::Note,
+        name: o.name,
+        replies: (!results_hidden).then_some(PollOptionReplies {
+          kind: CollectionType::Collection,
+          total_items: o.vote_count,
+        }),
+      })
+      .collect::<Vec<_>>();
+    let (one_of, any_of) = if poll.multiple_choice {
+      (None, Some(options))
+    } else {
+      (Some(options), None)
+    };
+    Ok(Some(ApubPoll {
+      one_of,
+      any_of,
+      end_time: poll.end_time_at,
+      closed: poll.closed_at,
+      voters_count: (!results_hidden).then_some(poll.voter_count),
+    }))
+  }
+
+  /// Stores the poll of a received page, including the current vote counts. Polls can't be
+  /// removed from a post once created.
+  async fn from_page(page: &Page, post: &Post, context: &LemmyContext) -> LemmyResult<()> {
+    let (multiple_choice, options) = match (&page.one_of, &page.any_of) {
+      (Some(options), _) => (false, options),
+      (None, Some(options)) => (true, options),
+      (None, None) => return Ok(()),
+    };
+    // Local polls count their votes themselves.
+    if post.local {
+      return Ok(());
+    }
+
+    let slur_regex = slur_regex(context).await?;
+    let options = options
+      .iter()
+      .map(|o| PostPollOptionInsertForm {
+        post_id: post.id,
+        name: truncate_for_db(&remove_slurs(&o.name, &slur_regex), MAX_POLL_OPTION_LENGTH),
+        vote_count: Some(
+          o.replies
+            .as_ref()
+            .map(|r| r.total_items)
+            .unwrap_or_default(),
+        ),
+      })
+      .collect::<Vec<_>>();
+    let form = PostPollInsertForm {
+      multiple_choice: Some(multiple_choice),
+      end_time_at: page.end_time,
+      closed_at: page.closed,
+      voter_count: Some(page.voters_count.unwrap_or_default()),
+      updated_at: page.updated,
+      ..PostPollInsertForm::new(post.id)
+    };
+    PostPoll::upsert_apub(&mut context.pool(), &form, &options).await?;
+    Ok(())
+  }
+}
+
 pub async fn append_attachments_to_body(
   content: &Option<String>,
   attachments: &[Attachment],
@@ -402,6 +507,7 @@ mod tests {
     utils::test::{file_to_json_object, parse_lemmy_community, parse_lemmy_person},
   };
   use lemmy_db_schema::{source::instance::Instance, test_data::TestData};
+  use lemmy_utils::error::LemmyErrorType;
   use pretty_assertions::assert_eq;
   use serial_test::serial;
 
@@ -483,4 +589,39 @@ mod tests {
     Instance::delete_all(&mut context.pool()).await?;
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_mastodon_poll() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let test_data = TestData::create(&mut context.pool()).await?;
+    parse_lemmy_community(&context).await?;
+
+    let json = file_to_json_object("../apub/assets/mastodon/objects/person.json")?;
+    ApubPerson::from_json(json, &context).await?;
+
+    let json = file_to_json_object("../apub/assets/mastodon/objects/question.json")?;
+    let post = ApubPost::from_json(json, &context).await?;
+    assert_eq!(post.name, "Which starship is the best?");
+
+    let poll = PostPoll::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(!poll.multiple_choice);
+    assert!(poll.end_time_at.is_some());
+    assert_eq!(poll.voter_count, 3);
+    let options = PostPollOption::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .map(|o| (o.name, o.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      options,
+      vec![("Enterprise".to_string(), 2), ("Voyager".to_string(), 1)]
+    );
+
+    test_data.delete(&mut context.pool()).await?;
+    Instance::delete_all(&mut context.pool()).await?;
+    Ok(())
+  }
 }
@@ -0,0 +1,59 @@
+use crate::utils::protocol::ImageObject;
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    community_flair::{CommunityFlair, CommunityFlairInsertForm},
+    custom_emoji::CustomEmoji,
+  },
+};
+use lemmy_db_schema_file::enums::TagColor;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+enum CommunityFlairType {
+  #[default]
+  CommunityUserFlair,
+}
+
+/// A user flair that a community owns. This is a lemmy extension which is included in the `Group`,
+/// while `Page` and `Note` contain the id of the flair which their author has in the community.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubCommunityFlair {
+  #[serde(rename = "type")]
+  kind: CommunityFlairType,
+  pub id: Url,
+  pub name: String,
+  pub color: Option<TagColor>,
+  /// The custom emoji of the flair. Only used for display, receiving instances don't store it.
+  pub icon: Option<ImageObject>,
+  pub self_assignable: Option<bool>,
+}
+
+impl ApubCommunityFlair {
+  pub fn to_json(flair: CommunityFlair, emoji: Option<&CustomEmoji>) -> Self {
+    ApubCommunityFlair {
+      kind: Default::default(),
+      id: flair.ap_id.into(),
+      name: flair.text,
+      color: Some(flair.color),
+      icon: emoji.map(|e| ImageObject::new(e.image_url.clone())),
+      self_assignable: Some(flair.self_assignable),
+    }
+  }
+
+  pub fn to_insert_form(&self, community_id: CommunityId) -> CommunityFlairInsertForm {
+    CommunityFlairInsertForm {
+      ap_id: self.id.clone().into(),
+      community_id,
+      text: self.name.clone(),
+      color: self.color,
+      custom_emoji_id: None,
+      self_assignable: self.self_assignable,
+      deleted: Some(false),
+    }
+  }
+}
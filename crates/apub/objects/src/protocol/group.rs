@@ -1,6 +1,6 @@
 use crate::{
   objects::community::ApubCommunity,
-  protocol::tags::ApubCommunityTag,
+  protocol::{flair::ApubCommunityFlair, rule::ApubCommunityRule, tags::ApubCommunityTag},
   utils::protocol::{AttributedTo, Endpoints, ImageObject, LanguageTag, Source},
 };
 use activitypub_federation::{
@@ -64,4 +64,10 @@ pub struct Group {
   pub(crate) discoverable: Option<bool>,
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub(crate) tag: Vec<ApubCommunityTag>,
+  // lemmy extension
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) flair: Vec<ApubCommunityFlair>,
+  // lemmy extension
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) rules: Vec<ApubCommunityRule>,
 }
@@ -1,3 +1,4 @@
+pub mod flair;
 pub mod group;
 pub mod instance;
 pub mod multi_community;
@@ -5,6 +6,7 @@ pub mod note;
 pub mod page;
 pub mod person;
 pub mod private_message;
+pub mod rule;
 pub mod tags;
 
 #[cfg(test)]
@@ -56,6 +56,8 @@ pub struct Note {
   #[serde(default)]
   pub(crate) attachment: Vec<Attachment>,
   pub(crate) context: Option<String>,
+  // lemmy extension, the id of the author's flair in the community
+  pub(crate) flair: Option<Url>,
 }
 
 impl Note {
@@ -14,8 +14,9 @@ use activitypub_federation::{
   config::Data,
   fetch::object_id::ObjectId,
   kinds::{
+    collection::CollectionType,
     link::LinkType,
-    object::{DocumentType, ImageType},
+    object::{DocumentType, ImageType, NoteType},
   },
   protocol::{
     helpers::{deserialize_one_or_many, deserialize_skip_error},
@@ -40,6 +41,8 @@ pub enum PageType {
   Note,
   Video,
   Event,
+  /// A poll, with options in `oneOf` or `anyOf`.
+  Question,
 }
 
 #[skip_serializing_none]
@@ -78,6 +81,37 @@ pub struct Page {
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub tag: Vec<ApubTag>,
   pub(crate) context: Option<String>,
+  /// Options of a single choice poll.
+  pub(crate) one_of: Option<Vec<PollOption>>,
+  /// Options of a multiple choice poll.
+  pub(crate) any_of: Option<Vec<PollOption>>,
+  /// Time when the poll closes, or closed.
+  pub(crate) end_time: Option<DateTime<Utc>>,
+  /// Set once the poll is closed.
+  pub(crate) closed: Option<DateTime<Utc>>,
+  pub(crate) voters_count: Option<i32>,
+  // lemmy extension, the id of the author's flair in the community
+  pub(crate) flair: Option<Url>,
+}
+
+/// A poll option, in the format used by Mastodon.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOption {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) name: String,
+  /// Contains the number of votes. Missing if the results are hidden until the poll is closed.
+  pub(crate) replies: Option<PollOptionReplies>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOptionReplies {
+  #[serde(rename = "type")]
+  pub(crate) kind: CollectionType,
+  pub(crate) total_items: i32,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -5,7 +5,7 @@ use crate::{
 use activitypub_federation::{
   fetch::object_id::ObjectId,
   protocol::{
-    helpers::{deserialize_last, deserialize_skip_error},
+    helpers::{deserialize_last, deserialize_one_or_many, deserialize_skip_error},
     public_key::PublicKey,
   },
 };
@@ -50,4 +50,10 @@ pub struct Person {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// other accounts of the same user, required for account migration with `Move`
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) also_known_as: Vec<Url>,
+  /// set if the account was moved to a new account
+  pub(crate) moved_to: Option<Url>,
 }
@@ -0,0 +1,53 @@
+use crate::utils::{functions::read_from_string_or_source_opt, protocol::Source};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::rule::{Rule, RuleInsertForm},
+};
+use lemmy_utils::utils::markdown::markdown_to_html;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+enum CommunityRuleType {
+  #[default]
+  CommunityRule,
+}
+
+/// A rule of a community. This is a lemmy extension which is included in the `Group` in order of
+/// position, and referenced by id in `Flag` activities.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubCommunityRule {
+  #[serde(rename = "type")]
+  kind: CommunityRuleType,
+  pub id: Url,
+  pub name: String,
+  /// The rule description as html
+  pub content: Option<String>,
+  pub source: Option<Source>,
+}
+
+impl ApubCommunityRule {
+  pub fn to_json(rule: Rule) -> Self {
+    ApubCommunityRule {
+      kind: Default::default(),
+      id: rule.ap_id.into(),
+      name: rule.title,
+      content: rule.description.as_ref().map(|d| markdown_to_html(d)),
+      source: rule.description.map(Source::new),
+    }
+  }
+
+  pub fn to_insert_form(&self, community_id: CommunityId, position: i32) -> RuleInsertForm {
+    RuleInsertForm {
+      ap_id: self.id.clone().into(),
+      community_id: Some(community_id),
+      title: self.name.clone(),
+      description: read_from_string_or_source_opt(&self.content, &None, &self.source),
+      position: Some(position),
+      deleted: Some(false),
+    }
+  }
+}
@@ -12,12 +12,19 @@ use activitypub_federation::{
 };
 use either::Either;
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema::source::{
-  community::Community,
-  instance::{Instance, InstanceActions},
-  local_site::LocalSite,
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    community::{Community, CommunityActions},
+    community_flair::CommunityFlair,
+    instance::{Instance, InstanceActions},
+    local_site::LocalSite,
+  },
+};
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{ActorType, CommunityVisibility},
 };
-use lemmy_db_schema_file::enums::{ActorType, CommunityVisibility};
 use lemmy_db_views_community_moderator::CommunityPersonBanView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::connection::DbPool;
@@ -234,6 +241,39 @@ pub async fn verify_person_in_site_or_community(
   Ok(())
 }
 
+/// The flair id which is federated with posts and comments of the person.
+pub async fn creator_flair_id(
+  person_id: PersonId,
+  community_id: CommunityId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Option<Url>> {
+  let flair = CommunityFlair::read_for_person(&mut context.pool(), person_id, community_id).await?;
+  Ok(flair.map(|f| f.ap_id.into()))
+}
+
+/// Sets the flair of the author of a received post or comment. Only self assignable flairs are
+/// accepted, so that users can't take flairs reserved for mods.
+/// Unknown flairs are ignored, and the flair isn't removed if it's missing, as other platforms
+/// don't send it.
+pub async fn update_creator_flair(
+  flair_id: Option<&Url>,
+  person_id: PersonId,
+  community_id: CommunityId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let Some(flair_id) = flair_id else {
+    return Ok(());
+  };
+  let Ok(flair) = CommunityFlair::read_apub(&mut context.pool(), &flair_id.clone().into()).await
+  else {
+    return Ok(());
+  };
+  if flair.community_id != community_id || !flair.self_assignable {
+    return Ok(());
+  }
+  CommunityActions::set_flair(&mut context.pool(), person_id, community_id, Some(flair.id)).await
+}
+
 pub fn verify_is_public(to: &[Url], cc: &[Url]) -> LemmyResult<()> {
   if ![to, cc].iter().any(|set| set.contains(&public())) {
     Err(UntranslatedError::ObjectIsNotPublic.into())
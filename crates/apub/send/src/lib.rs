@@ -1,7 +1,7 @@
 use crate::{util::CancellableTask, worker::InstanceWorker};
 use activitypub_federation::config::FederationConfig;
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema::source::instance::Instance;
+use lemmy_db_schema::source::{federation_queue_state::FederationQueueState, instance::Instance};
 use lemmy_db_schema_file::InstanceId;
 use lemmy_utils::{error::LemmyResult, settings::structs::FederationWorkerConfig};
 use stats::receive_print_stats;
@@ -16,6 +16,7 @@ use tracing::info;
 use util::FederationQueueStateWithDomain;
 
 mod inboxes;
+mod metrics;
 mod send;
 mod stats;
 mod util;
@@ -106,6 +107,19 @@ impl SendManager {
     let local_domain = self.context.settings().get_hostname_without_port()?;
     let mut pool = self.context.pool();
     loop {
+      for instance_id in FederationQueueState::list_pending_requests(&mut pool).await? {
+        if instance_id.inner() % self.opts.process_count != process_index {
+          continue;
+        }
+        // stop the worker first so that it doesn't overwrite the changes with its own state, it
+        // gets restarted below
+        if let Some(worker) = self.workers.remove(&instance_id)
+          && let Err(e) = worker.cancel().await
+        {
+          tracing::error!("error stopping worker: {e}");
+        }
+        FederationQueueState::apply_pending_requests(&mut pool, instance_id).await?;
+      }
       let mut total_count = 0;
       let mut dead_count = 0;
       let mut disallowed_count = 0;
@@ -151,11 +165,11 @@ impl SendManager {
               )
             }),
           );
-        } else if !should_federate
-          && let Some(worker) = self.workers.remove(&instance.id)
-          && let Err(e) = worker.cancel().await
-        {
-          tracing::error!("error stopping worker: {e}");
+        } else if !should_federate && let Some(worker) = self.workers.remove(&instance.id) {
+          if let Err(e) = worker.cancel().await {
+            tracing::error!("error stopping worker: {e}");
+          }
+          metrics::remove_instance(&instance.domain);
         }
       }
       let worker_count = self.workers.len();
@@ -0,0 +1,88 @@
+//! Prometheus metrics for outgoing federation. They are registered in the default registry, so
+//! they are included in the output of the Prometheus endpoint if it is enabled.
+
+use prometheus::{
+  GaugeVec,
+  HistogramOpts,
+  HistogramVec,
+  IntGaugeVec,
+  Opts,
+  default_registry,
+  exponential_buckets,
+};
+use std::sync::LazyLock;
+
+pub(crate) struct FederationMetrics {
+  /// Number of activities which were not sent yet
+  pub lag_activities: IntGaugeVec,
+  /// Time since the last successfully sent activity was published, if there are activities left
+  pub lag_seconds: GaugeVec,
+  pub fail_count: IntGaugeVec,
+  pub in_flight: IntGaugeVec,
+  /// Duration of each HTTP request which sends an activity
+  pub send_duration: HistogramVec,
+}
+
+pub(crate) static METRICS: LazyLock<FederationMetrics> = LazyLock::new(|| {
+  #[expect(clippy::expect_used)]
+  create_metrics().expect("create federation metrics")
+});
+
+fn create_metrics() -> prometheus::Result<FederationMetrics> {
+  let metrics = FederationMetrics {
+    lag_activities: IntGaugeVec::new(
+      Opts::new(
+        "lemmy_federation_queue_lag_activities",
+        "Number of activities which were not sent to the instance yet",
+      ),
+      &["domain"],
+    )?,
+    lag_seconds: GaugeVec::new(
+      Opts::new(
+        "lemmy_federation_queue_lag_seconds",
+        "Seconds since the last activity sent to the instance was published, 0 if up to date",
+      ),
+      &["domain"],
+    )?,
+    fail_count: IntGaugeVec::new(
+      Opts::new(
+        "lemmy_federation_fail_count",
+        "Consecutive failed attempts to send an activity to the instance",
+      ),
+      &["domain"],
+    )?,
+    in_flight: IntGaugeVec::new(
+      Opts::new(
+        "lemmy_federation_in_flight_sends",
+        "Number of activities which are currently being sent to the instance",
+      ),
+      &["domain"],
+    )?,
+    send_duration: HistogramVec::new(
+      HistogramOpts::new(
+        "lemmy_federation_send_duration_seconds",
+        "Duration of HTTP requests to send activities to other instances",
+      )
+      .buckets(exponential_buckets(0.05, 2.0, 10)?),
+      &["result"],
+    )?,
+  };
+
+  let registry = default_registry();
+  registry.register(Box::new(metrics.lag_activities.clone()))?;
+  registry.register(Box::new(metrics.lag_seconds.clone()))?;
+  registry.register(Box::new(metrics.fail_count.clone()))?;
+  registry.register(Box::new(metrics.in_flight.clone()))?;
+  registry.register(Box::new(metrics.send_duration.clone()))?;
+  Ok(metrics)
+}
+
+/// Removes the metrics of an instance which no longer has a worker, so that alerts don't fire for
+/// instances which are dead or blocked.
+pub(crate) fn remove_instance(domain: &str) {
+  let m = &*METRICS;
+  m.lag_activities.remove_label_values(&[domain]).ok();
+  m.lag_seconds.remove_label_values(&[domain]).ok();
+  m.fail_count.remove_label_values(&[domain]).ok();
+  m.in_flight.remove_label_values(&[domain]).ok();
+}
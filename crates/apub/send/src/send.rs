@@ -1,4 +1,4 @@
-use crate::util::get_actor_cached;
+use crate::{metrics::METRICS, util::get_actor_cached};
 use activitypub_federation::{
   activity_sending::SendActivityTask,
   config::Data,
@@ -17,7 +17,7 @@ use lemmy_utils::{
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::ops::Deref;
+use std::{ops::Deref, time::Instant};
 use tokio::{sync::mpsc::UnboundedSender, time::sleep};
 use tokio_util::sync::CancellationToken;
 
@@ -124,7 +124,17 @@ impl SendRetryTask<'_> {
       // usually only one due to shared inbox
       tracing::debug!("sending out {}", task);
       let mut fail_count = initial_fail_count;
-      while let Err(e) = task.sign_and_send(&context).await {
+      loop {
+        let start = Instant::now();
+        let res = task.sign_and_send(&context).await;
+        let result = if res.is_ok() { "success" } else { "failure" };
+        METRICS
+          .send_duration
+          .with_label_values(&[result])
+          .observe(start.elapsed().as_secs_f64());
+        let Err(e) = res else {
+          break;
+        };
         fail_count += 1;
         report.send(SendActivityResult::Failure {
           fail_count,
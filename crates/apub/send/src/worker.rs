@@ -1,5 +1,6 @@
 use crate::{
   inboxes::RealCommunityInboxCollector,
+  metrics::METRICS,
   send::{SendActivityResult, SendRetryTask, SendSuccessInfo},
   util::{
     FederationQueueStateWithDomain,
@@ -188,6 +189,7 @@ impl InstanceWorker {
         continue;
       }
       self.in_flight += 1;
+      self.update_in_flight_metric();
       last_sent_id = next_id_to_send;
       self.spawn_send_if_needed(next_id_to_send).await?;
     }
@@ -267,6 +269,7 @@ impl InstanceWorker {
       match event {
         SendActivityResult::Success(s) => {
           self.in_flight -= 1;
+          self.update_in_flight_metric();
           if !s.was_skipped {
             self.state.fail_count = max(0, self.state.fail_count - 1);
             self.mark_instance_alive().await?;
@@ -434,6 +437,7 @@ impl InstanceWorker {
     FederationQueueState::upsert(&mut self.pool(), &self.state)
       .await
       .map_err(|e| anyhow::anyhow!(e))?;
+    self.update_queue_metrics().await?;
     self.stats_sender.send(FederationQueueStateWithDomain {
       state: self.state.clone(),
       domain: self.instance.domain.clone(),
@@ -441,6 +445,42 @@ impl InstanceWorker {
     Ok(())
   }
 
+  async fn update_queue_metrics(&mut self) -> Result<()> {
+    let latest_id = get_latest_activity_id(&mut self.pool())
+      .await?
+      .unwrap_or(ActivityId(0));
+    let last_successful_id = self.state.last_successful_id.unwrap_or(latest_id);
+    let behind = max(0, latest_id.0 - last_successful_id.0);
+    let lag_seconds = match self.state.last_successful_published_time_at {
+      Some(published_at) if behind > 0 => (Utc::now() - published_at)
+        .to_std()
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default(),
+      _ => 0.0,
+    };
+    let domain = &self.instance.domain;
+    METRICS
+      .lag_activities
+      .with_label_values(&[domain])
+      .set(behind);
+    METRICS
+      .lag_seconds
+      .with_label_values(&[domain])
+      .set(lag_seconds);
+    METRICS
+      .fail_count
+      .with_label_values(&[domain])
+      .set(self.state.fail_count.into());
+    Ok(())
+  }
+
+  fn update_in_flight_metric(&self) {
+    METRICS
+      .in_flight
+      .with_label_values(&[&self.instance.domain])
+      .set(self.in_flight.into());
+  }
+
   fn pool(&self) -> DbPool<'_> {
     DbPool::Pool(&self.pool)
   }
@@ -3,8 +3,16 @@ use crate::{
   newtypes::ActivityId,
   source::activity::{ReceivedActivity, SentActivity, SentActivityForm},
 };
-use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  QueryableByName,
+  dsl::insert_into,
+  sql_query,
+  sql_types::{Array, BigInt, Int4},
+};
 use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::InstanceId;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
@@ -40,6 +48,82 @@ impl SentActivity {
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
+
+  /// The next activities in the outgoing queue of each of the given instances, oldest first. Only
+  /// activities which the instance is interested in are included, the same way as in the send
+  /// worker: activities for all instances if the instance has a site, activities for followers of
+  /// a community which is followed from the instance, and activities for inboxes on the instance.
+  pub async fn list_pending(
+    pool: &mut DbPool<'_>,
+    instance_ids: Vec<InstanceId>,
+    limit: i64,
+  ) -> LemmyResult<Vec<(InstanceId, Self)>> {
+    #[derive(QueryableByName)]
+    struct InstancePendingActivity {
+      #[diesel(sql_type = Int4)]
+      instance_id: InstanceId,
+      #[diesel(embed)]
+      activity: SentActivity,
+    }
+
+    let conn = &mut get_conn(pool).await?;
+    let pending = sql_query(
+      "SELECT
+          q.instance_id,
+          a.*
+      FROM
+          federation_queue_state q
+          INNER JOIN instance i ON i.id = q.instance_id
+          CROSS JOIN LATERAL (
+              SELECT
+                  *
+              FROM
+                  sent_activity s
+              WHERE
+                  s.id > q.last_successful_id
+                  AND ((s.send_all_instances
+                          AND EXISTS (
+                              SELECT
+                              FROM
+                                  site
+                              WHERE
+                                  site.instance_id = i.id))
+                      OR s.send_community_followers_of IN (
+                          SELECT
+                              ca.community_id
+                          FROM
+                              community_actions ca
+                              INNER JOIN person p ON p.id = ca.person_id
+                          WHERE
+                              p.instance_id = i.id
+                              AND NOT p.local
+                              AND ca.followed_at IS NOT NULL)
+                      OR EXISTS (
+                          SELECT
+                          FROM
+                              unnest(s.send_inboxes) AS inbox
+                          WHERE
+                              r.url_host (inbox) = i.domain))
+              ORDER BY
+                  s.id
+              LIMIT $2) a
+      WHERE
+          q.instance_id = ANY ($1)
+      ORDER BY
+          a.id",
+    )
+    .bind::<Array<Int4>, _>(instance_ids)
+    .bind::<BigInt, _>(limit)
+    .load::<InstancePendingActivity>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)?;
+    Ok(
+      pending
+        .into_iter()
+        .map(|p| (p.instance_id, p.activity))
+        .collect(),
+    )
+  }
 }
 
 impl ReceivedActivity {
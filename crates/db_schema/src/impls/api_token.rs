@@ -0,0 +1,205 @@
+use crate::{
+  newtypes::{ApiTokenId, LocalUserId},
+  source::api_token::{ApiToken, ApiTokenInsertForm, ApiTokenScope, ApiTokenUpdateForm},
+};
+use chrono::{Duration, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  deserialize::FromSql,
+  insert_into,
+  pg::{Pg, PgValue},
+  serialize::ToSql,
+  sql_types::Text,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::api_token;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for ApiToken {
+  type InsertForm = ApiTokenInsertForm;
+  type UpdateForm = ApiTokenUpdateForm;
+  type IdType = ApiTokenId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(api_token::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    api_token_id: ApiTokenId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(api_token::table.find(api_token_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl ApiToken {
+  /// Returns the token with the given hash, if it is not expired.
+  pub async fn read_by_hash(pool: &mut DbPool<'_>, token_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    api_token::table
+      .filter(api_token::token_hash.eq(token_hash))
+      .filter(
+        api_token::expires_at
+          .is_null()
+          .or(api_token::expires_at.gt(now().nullable())),
+      )
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotLoggedIn)
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    api_token::table
+      .filter(api_token::local_user_id.eq(local_user_id))
+      .order_by(api_token::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Updates the last usage time. To avoid a write on every request, this is only done once per
+  /// minute.
+  pub async fn mark_used(pool: &mut DbPool<'_>, api_token_id: ApiTokenId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let minute_ago = Utc::now() - Duration::minutes(1);
+    diesel::update(
+      api_token::table.find(api_token_id).filter(
+        api_token::last_used_at
+          .is_null()
+          .or(api_token::last_used_at.lt(minute_ago)),
+      ),
+    )
+    .set(api_token::last_used_at.eq(now().nullable()))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Revokes all tokens of the user on password change, or account deletion.
+  pub async fn delete_all(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(api_token::table.filter(api_token::local_user_id.eq(local_user_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+impl FromSql<Text, Pg> for ApiTokenScope {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+    Ok(value.parse()?)
+  }
+}
+
+impl ToSql<Text, Pg> for ApiTokenScope {
+  fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+    <String as ToSql<Text, Pg>>::to_sql(&self.to_string(), &mut out.reborrow())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::CommunityId,
+    source::{
+      api_token::{ApiToken, ApiTokenInsertForm, ApiTokenScope},
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[test]
+  fn test_parse_scope() {
+    let scopes = [
+      ApiTokenScope::Read,
+      ApiTokenScope::Post,
+      ApiTokenScope::Vote,
+      ApiTokenScope::ModerateCommunity(CommunityId(5)),
+      ApiTokenScope::Admin,
+    ];
+    for scope in scopes {
+      assert_eq!(Ok(scope), scope.to_string().parse());
+    }
+    assert_eq!(
+      "moderate:community/5",
+      ApiTokenScope::ModerateCommunity(CommunityId(5)).to_string()
+    );
+    assert!("moderate:community/abc".parse::<ApiTokenScope>().is_err());
+    assert!("write".parse::<ApiTokenScope>().is_err());
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_token() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "bot")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+
+    let scopes = vec![
+      ApiTokenScope::Read,
+      ApiTokenScope::ModerateCommunity(CommunityId(5)),
+    ];
+    let form = ApiTokenInsertForm {
+      local_user_id: local_user.id,
+      name: "my bot".to_string(),
+      token_hash: "hash".to_string(),
+      scopes: scopes.clone(),
+      expires_at: None,
+    };
+    let token = ApiToken::create(pool, &form).await?;
+    assert_eq!(scopes, token.scopes);
+    assert_eq!(token, ApiToken::read_by_hash(pool, "hash").await?);
+
+    ApiToken::mark_used(pool, token.id).await?;
+    let used = ApiToken::read(pool, token.id).await?;
+    assert!(used.last_used_at.is_some());
+
+    // Expired tokens can't be used anymore
+    let expired_form = ApiTokenInsertForm {
+      token_hash: "expired_hash".to_string(),
+      expires_at: Some(Utc::now() - Duration::days(1)),
+      ..form
+    };
+    ApiToken::create(pool, &expired_form).await?;
+    assert!(ApiToken::read_by_hash(pool, "expired_hash").await.is_err());
+    assert_eq!(2, ApiToken::list(pool, local_user.id).await?.len());
+
+    assert_eq!(2, ApiToken::delete_all(pool, local_user.id).await?);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
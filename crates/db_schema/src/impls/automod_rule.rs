@@ -0,0 +1,142 @@
+use crate::{
+  newtypes::{AutomodRuleId, CommunityId},
+  source::automod_rule::{AutomodRule, AutomodRuleInsertForm, AutomodRuleUpdateForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::automod_rule;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for AutomodRule {
+  type InsertForm = AutomodRuleInsertForm;
+  type UpdateForm = AutomodRuleUpdateForm;
+  type IdType = AutomodRuleId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(automod_rule::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    automod_rule_id: AutomodRuleId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(automod_rule::table.find(automod_rule_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl AutomodRule {
+  /// Lists all rules of a community, including disabled ones.
+  pub async fn list(pool: &mut DbPool<'_>, community_id: CommunityId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    automod_rule::table
+      .filter(automod_rule::community_id.eq(community_id))
+      .order_by(automod_rule::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// The rules which are checked for new posts in the community.
+  pub async fn list_enabled(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    automod_rule::table
+      .filter(automod_rule::community_id.eq(community_id))
+      .filter(automod_rule::enabled)
+      .order_by(automod_rule::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::LanguageId,
+    source::{
+      automod_rule::{AutomodRule, AutomodRuleInsertForm, AutomodRuleUpdateForm},
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+    },
+  };
+  use lemmy_db_schema_file::enums::AutomodAction;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_automod_rule() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        instance.id,
+        "test_automod".to_string(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+
+    let form = AutomodRuleInsertForm {
+      title_regex: Some("buy now".to_string()),
+      domains: Some(vec!["spam.example".to_string()]),
+      language_ids: Some(vec![LanguageId(37)]),
+      ..AutomodRuleInsertForm::new(
+        community.id,
+        "No spam".to_string(),
+        vec![AutomodAction::Remove, AutomodAction::Report],
+      )
+    };
+    let rule = AutomodRule::create(pool, &form).await?;
+    assert!(rule.enabled);
+    assert_eq!(vec![LanguageId(37)], rule.language_ids);
+    assert_eq!(
+      vec![AutomodAction::Remove, AutomodAction::Report],
+      rule.actions
+    );
+
+    let form = AutomodRuleInsertForm::new(
+      community.id,
+      "New accounts".to_string(),
+      vec![AutomodAction::HoldForApproval],
+    );
+    let rule2 = AutomodRule::create(pool, &form).await?;
+    let form = AutomodRuleUpdateForm {
+      enabled: Some(false),
+      ..Default::default()
+    };
+    AutomodRule::update(pool, rule2.id, &form).await?;
+
+    assert_eq!(2, AutomodRule::list(pool, community.id).await?.len());
+    assert_eq!(
+      vec![rule.clone()],
+      AutomodRule::list_enabled(pool, community.id).await?
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -27,7 +27,7 @@ use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  schema::{comment, comment_actions, community, post},
+  schema::{comment, comment_actions, comment_revision, community, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -48,6 +48,20 @@ impl Comment {
   ) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
 
+    // Previous versions would otherwise still contain the original content
+    diesel::delete(
+      comment_revision::table.filter(
+        comment_revision::comment_id.eq_any(
+          comment::table
+            .filter(comment::creator_id.eq(creator_id))
+            .select(comment::id),
+        ),
+      ),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
     diesel::update(comment::table.filter(comment::creator_id.eq(creator_id)))
       .set((
         comment::content.eq(DELETED_REPLACEMENT_TEXT),
@@ -514,6 +528,7 @@ mod tests {
       language_id: LanguageId::default(),
       child_count: 1,
       controversy_rank: 0.0,
+      best_rank: 0.378_447_5,
       downvotes: 0,
       upvotes: 1,
       score: 1,
@@ -643,6 +658,7 @@ mod tests {
     assert_eq!(0, comment_aggs_after_dislike.score);
     assert_eq!(1, comment_aggs_after_dislike.upvotes);
     assert_eq!(1, comment_aggs_after_dislike.downvotes);
+    assert!(comment_aggs_after_dislike.best_rank < comment_aggs_before_delete.best_rank);
 
     // Remove the first comment like
     let form = CommentLikeForm::new(inserted_comment.id, inserted_person.id, None);
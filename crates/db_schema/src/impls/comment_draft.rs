@@ -0,0 +1,76 @@
+use crate::{
+  newtypes::CommentDraftId,
+  source::comment_draft::{CommentDraft, CommentDraftInsertForm, CommentDraftUpdateForm},
+};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{delete, insert_into},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{PersonId, schema::comment_draft};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+  utils::functions::coalesce,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for CommentDraft {
+  type InsertForm = CommentDraftInsertForm;
+  type UpdateForm = CommentDraftUpdateForm;
+  type IdType = CommentDraftId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_draft::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    comment_draft_id: CommentDraftId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(comment_draft::table.find(comment_draft_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl CommentDraft {
+  /// Lists the drafts of a user, most recently changed first.
+  pub async fn list(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    comment_draft::table
+      .filter(comment_draft::creator_id.eq(creator_id))
+      .order_by(coalesce(comment_draft::updated_at, comment_draft::published_at).desc())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn count(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    comment_draft::table
+      .filter(comment_draft::creator_id.eq(creator_id))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete_all(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(comment_draft::table.filter(comment_draft::creator_id.eq(creator_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
@@ -0,0 +1,49 @@
+use crate::{
+  newtypes::CommentId,
+  source::{
+    comment::Comment,
+    comment_revision::{CommentRevision, CommentRevisionInsertForm},
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::comment_revision;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommentRevision {
+  /// Stores the previous version of a comment, if the edit changed its content.
+  pub async fn create_if_changed(
+    pool: &mut DbPool<'_>,
+    orig: &Comment,
+    updated: &Comment,
+  ) -> LemmyResult<Option<Self>> {
+    if orig.content == updated.content {
+      return Ok(None);
+    }
+    let form = CommentRevisionInsertForm {
+      comment_id: orig.id,
+      content: orig.content.clone(),
+      published_at: orig.updated_at.unwrap_or(orig.published_at),
+    };
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_revision::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .map(Some)
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Lists the previous versions of a comment, newest first.
+  pub async fn list(pool: &mut DbPool<'_>, comment_id: CommentId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    comment_revision::table
+      .filter(comment_revision::comment_id.eq(comment_id))
+      .order_by(comment_revision::published_at.desc())
+      .then_order_by(comment_revision::id.desc())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
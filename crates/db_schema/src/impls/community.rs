@@ -13,18 +13,19 @@ use crate::{
       CommunityPersonBanForm,
       CommunityUpdateForm,
     },
+    person::Person,
     post::Post,
   },
   traits::{ApubActor, Bannable, Blockable, Followable},
   utils::{format_actor_url, queries::filters::filter_is_subscribed},
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
   QueryDsl,
-  dsl::{exists, insert_into, not},
+  dsl::{count_star, exists, insert_into, not},
   expression::SelectableHelper,
   select,
   update,
@@ -48,6 +49,9 @@ use lemmy_utils::{
 };
 use url::Url;
 
+/// How many subscribers are considered for [CommunityActions::list_co_subscribed].
+const CO_SUBSCRIBER_SAMPLE: i64 = 1000;
+
 impl Crud for Community {
   type InsertForm = CommunityInsertForm;
   type UpdateForm = CommunityUpdateForm;
@@ -119,6 +123,24 @@ pub enum CollectionType {
 }
 
 impl Community {
+  /// Whether a new post by the given person needs mod approval. Mods and admins are not checked
+  /// here, so the caller needs to exempt them.
+  pub fn post_needs_approval(&self, creator: &Person) -> bool {
+    if !self.local || !self.posts_require_approval {
+      return false;
+    }
+    let is_new_account = self
+      .post_approval_max_account_age_days
+      .map(|days| creator.published_at > Utc::now() - Duration::days(days.into()));
+    let is_low_score = self
+      .post_approval_max_person_post_score
+      .map(|score| creator.post_score <= score);
+    match (is_new_account, is_low_score) {
+      (None, None) => true,
+      (a, b) => a.unwrap_or(false) || b.unwrap_or(false),
+    }
+  }
+
   pub async fn insert_apub(
     pool: &mut DbPool<'_>,
     timestamp: DateTime<Utc>,
@@ -399,6 +421,59 @@ impl CommunityActions {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Communities which have the most subscribers in common with the given communities, together
+  /// with the number of shared subscribers. Only the most recent subscribers of the given
+  /// communities are considered, so that this stays fast for huge communities.
+  pub async fn list_co_subscribed(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_ids: &[CommunityId],
+    limit: i64,
+  ) -> LemmyResult<Vec<(CommunityId, i64)>> {
+    if community_ids.is_empty() {
+      return Ok(vec![]);
+    }
+    let conn = &mut get_conn(pool).await?;
+
+    let co_subscriber_actions = diesel::alias!(community_actions as co_subscriber_actions);
+    let co_subscriber_ids = co_subscriber_actions
+      .filter(
+        co_subscriber_actions
+          .field(community_actions::follow_state)
+          .eq(Some(CommunityFollowerState::Accepted)),
+      )
+      .filter(
+        co_subscriber_actions
+          .field(community_actions::community_id)
+          .eq_any(community_ids.to_vec()),
+      )
+      .filter(
+        co_subscriber_actions
+          .field(community_actions::person_id)
+          .ne(person_id),
+      )
+      .select(co_subscriber_actions.field(community_actions::person_id))
+      .order_by(
+        co_subscriber_actions
+          .field(community_actions::followed_at)
+          .desc(),
+      )
+      .limit(CO_SUBSCRIBER_SAMPLE);
+    community_actions::table
+      .filter(filter_is_subscribed())
+      .filter(community_actions::person_id.eq_any(co_subscriber_ids))
+      .filter(not(
+        community_actions::community_id.eq_any(community_ids.to_vec()),
+      ))
+      .group_by(community_actions::community_id)
+      .select((community_actions::community_id, count_star()))
+      .order_by((count_star().desc(), community_actions::community_id.asc()))
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn update_notification_state(
     community_id: CommunityId,
     person_id: PersonId,
@@ -748,6 +823,9 @@ mod tests {
       unresolved_report_count: 0,
       interactions_month: 0,
       local_removed: false,
+      posts_require_approval: false,
+      post_approval_max_account_age_days: None,
+      post_approval_max_person_post_score: None,
     };
 
     let community_follower_form = CommunityFollowerForm::new(
@@ -839,6 +917,50 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_post_needs_approval() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "approval_person");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "approval_community".into(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    assert!(!community.post_needs_approval(&person));
+
+    // Without limits all posts need approval
+    let form = CommunityUpdateForm {
+      posts_require_approval: Some(true),
+      ..Default::default()
+    };
+    let community = Community::update(pool, community.id, &form).await?;
+    assert!(community.post_needs_approval(&person));
+
+    // The account is new, but has a higher post score
+    let form = CommunityUpdateForm {
+      post_approval_max_person_post_score: Some(Some(-1)),
+      ..Default::default()
+    };
+    let community = Community::update(pool, community.id, &form).await?;
+    assert!(!community.post_needs_approval(&person));
+
+    let form = CommunityUpdateForm {
+      post_approval_max_account_age_days: Some(Some(7)),
+      ..Default::default()
+    };
+    let community = Community::update(pool, community.id, &form).await?;
+    assert!(community.post_needs_approval(&person));
+
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_aggregates() -> LemmyResult<()> {
@@ -0,0 +1,219 @@
+use crate::{
+  newtypes::{CommunityFlairId, CommunityId},
+  source::{
+    community::CommunityActions,
+    community_flair::{
+      CommunityFlair,
+      CommunityFlairInsertForm,
+      CommunityFlairUpdateForm,
+      CommunityFlairView,
+    },
+  },
+};
+use diesel::{
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  deserialize::FromSql,
+  insert_into,
+  pg::{Pg, PgValue},
+  serialize::ToSql,
+  sql_types::{Json, Nullable},
+  upsert::excluded,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use diesel_uplete::{UpleteCount, uplete};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{community_actions, community_flair},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::collections::HashSet;
+
+impl Crud for CommunityFlair {
+  type InsertForm = CommunityFlairInsertForm;
+  type UpdateForm = CommunityFlairUpdateForm;
+  type IdType = CommunityFlairId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_flair::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    id: CommunityFlairId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_flair::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl CommunityFlair {
+  pub async fn read_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_flair::table
+      .filter(community_flair::community_id.eq(community_id))
+      .filter(community_flair::deleted.eq(false))
+      .order_by(community_flair::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Syncs the flairs of a remote community. Flairs which are missing from `forms` are marked as
+  /// deleted.
+  pub async fn update_many(
+    pool: &mut DbPool<'_>,
+    mut forms: Vec<CommunityFlairInsertForm>,
+    existing_flairs: Vec<CommunityFlair>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let new_flair_ids = forms
+      .iter()
+      .map(|flair| flair.ap_id.clone())
+      .collect::<HashSet<_>>();
+    let delete_forms = existing_flairs
+      .into_iter()
+      .filter(|flair| !new_flair_ids.contains(&flair.ap_id))
+      .map(|f| CommunityFlairInsertForm {
+        ap_id: f.ap_id,
+        community_id: f.community_id,
+        text: f.text,
+        color: Some(f.color),
+        custom_emoji_id: None,
+        self_assignable: Some(f.self_assignable),
+        deleted: Some(true),
+      });
+    forms.extend(delete_forms);
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          insert_into(community_flair::table)
+            .values(&forms)
+            .on_conflict(community_flair::ap_id)
+            .do_update()
+            .set((
+              community_flair::text.eq(excluded(community_flair::text)),
+              community_flair::color.eq(excluded(community_flair::color)),
+              community_flair::self_assignable.eq(excluded(community_flair::self_assignable)),
+              community_flair::deleted.eq(excluded(community_flair::deleted)),
+            ))
+            .execute(conn)
+            .await?;
+
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await?;
+
+    Ok(())
+  }
+
+  pub async fn read_apub(pool: &mut DbPool<'_>, ap_id: &DbUrl) -> LemmyResult<CommunityFlair> {
+    let conn = &mut get_conn(pool).await?;
+    community_flair::table
+      .filter(community_flair::ap_id.eq(ap_id))
+      .filter(community_flair::deleted.eq(false))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Reads the flair which the person has in the given community, if any.
+  pub async fn read_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+  ) -> LemmyResult<Option<CommunityFlair>> {
+    let conn = &mut get_conn(pool).await?;
+    community_actions::table
+      .inner_join(community_flair::table)
+      .filter(community_actions::person_id.eq(person_id))
+      .filter(community_actions::community_id.eq(community_id))
+      .filter(community_flair::deleted.eq(false))
+      .select(community_flair::all_columns)
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl CommunityActions {
+  /// Assigns a flair to the person, or removes their flair if `flair_id` is `None`.
+  pub async fn set_flair(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+    flair_id: Option<CommunityFlairId>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    if let Some(flair_id) = flair_id {
+      let form = (
+        community_actions::person_id.eq(person_id),
+        community_actions::community_id.eq(community_id),
+        community_actions::flair_id.eq(flair_id),
+      );
+      insert_into(community_actions::table)
+        .values(form)
+        .on_conflict((
+          community_actions::person_id,
+          community_actions::community_id,
+        ))
+        .do_update()
+        .set(form)
+        .execute(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    } else {
+      uplete(community_actions::table.find((person_id, community_id)))
+        .set_null(community_actions::flair_id)
+        .get_result::<UpleteCount>(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    }
+    Ok(())
+  }
+}
+
+impl FromSql<Nullable<Json>, Pg> for CommunityFlairView {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    let value = <serde_json::Value as FromSql<Json, Pg>>::from_sql(bytes)?;
+    Ok(serde_json::from_value::<CommunityFlairView>(value)?)
+  }
+  fn from_nullable_sql(
+    bytes: Option<<Pg as diesel::backend::Backend>::RawValue<'_>>,
+  ) -> diesel::deserialize::Result<Self> {
+    match bytes {
+      Some(bytes) => Self::from_sql(bytes),
+      None => Ok(Self(None)),
+    }
+  }
+}
+
+impl ToSql<Nullable<Json>, Pg> for CommunityFlairView {
+  fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+    let value = serde_json::to_value(self)?;
+    <serde_json::Value as ToSql<Json, Pg>>::to_sql(&value, &mut out.reborrow())
+  }
+}
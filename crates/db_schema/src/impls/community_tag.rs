@@ -207,4 +207,24 @@ impl PostCommunityTag {
       })
       .await
   }
+
+  /// Adds a single tag to the post, keeping its existing tags.
+  pub async fn add(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    community_tag_id: CommunityTagId,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let form = PostCommunityTagForm {
+      post_id,
+      community_tag_id,
+    };
+    insert_into(post_community_tag::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    Ok(())
+  }
 }
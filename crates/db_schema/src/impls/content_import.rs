@@ -0,0 +1,158 @@
+use crate::{
+  newtypes::{ContentImportId, LocalUserId},
+  source::content_import::{ContentImport, ContentImportFailure},
+};
+use diesel::{
+  ExpressionMethods,
+  NullableExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  insert_into,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::{content_import, content_import_failure};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl ContentImport {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    total_items: i32,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(content_import::table)
+      .values((
+        content_import::local_user_id.eq(local_user_id),
+        content_import::total_items.eq(total_items),
+      ))
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// The most recently started import of the user, if any.
+  pub async fn read_latest(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    content_import::table
+      .filter(content_import::local_user_id.eq(local_user_id))
+      .order_by(content_import::published_at.desc())
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks a single item as processed. If it couldn't be imported, the reason is stored as well.
+  pub async fn item_processed(
+    pool: &mut DbPool<'_>,
+    id: ContentImportId,
+    failure: Option<(&str, &str)>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    if let Some((ap_id, reason)) = failure {
+      insert_into(content_import_failure::table)
+        .values((
+          content_import_failure::content_import_id.eq(id),
+          content_import_failure::ap_id.eq(ap_id),
+          content_import_failure::reason.eq(reason),
+        ))
+        .execute(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    }
+    diesel::update(content_import::table.find(id))
+      .set(content_import::processed_items.eq(content_import::processed_items + 1))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn finish(pool: &mut DbPool<'_>, id: ContentImportId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(content_import::table.find(id))
+      .set(content_import::finished_at.eq(now().nullable()))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+}
+
+impl ContentImportFailure {
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    content_import_id: ContentImportId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    content_import_failure::table
+      .filter(content_import_failure::content_import_id.eq(content_import_id))
+      .order_by(content_import_failure::id)
+      .select(Self::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    content_import::{ContentImport, ContentImportFailure},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_content_import() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "importer")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+
+    assert_eq!(None, ContentImport::read_latest(pool, local_user.id).await?);
+
+    let import = ContentImport::create(pool, local_user.id, 2).await?;
+    ContentImport::item_processed(pool, import.id, None).await?;
+    ContentImport::item_processed(
+      pool,
+      import.id,
+      Some(("https://example.com/post/1", "not found")),
+    )
+    .await?;
+    ContentImport::finish(pool, import.id).await?;
+
+    let latest = ContentImport::read_latest(pool, local_user.id).await?;
+    assert!(latest.is_some_and(|i| i.processed_items == 2 && i.finished_at.is_some()));
+
+    let failures = ContentImportFailure::list(pool, import.id).await?;
+    assert_eq!(1, failures.len());
+    assert_eq!(
+      Some("https://example.com/post/1"),
+      failures.first().map(|f| f.ap_id.as_str())
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
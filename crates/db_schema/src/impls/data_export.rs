@@ -0,0 +1,152 @@
+use crate::{
+  newtypes::{DataExportId, LocalUserId},
+  source::data_export::DataExport,
+};
+use diesel::{
+  ExpressionMethods,
+  NullableExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  dsl::{IntervalDsl, insert_into},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::data_export;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl DataExport {
+  pub async fn create(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(data_export::table)
+      .values(data_export::local_user_id.eq(local_user_id))
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// The most recently requested export of the user, if any.
+  pub async fn read_latest(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    data_export::table
+      .filter(data_export::local_user_id.eq(local_user_id))
+      .order_by(data_export::published_at.desc())
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Stores the path of the finished archive, which makes it available for download.
+  pub async fn finish(
+    pool: &mut DbPool<'_>,
+    id: DataExportId,
+    archive_path: &str,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(data_export::table.find(id))
+      .set((
+        data_export::archive_path.eq(archive_path),
+        data_export::finished_at.eq(now().nullable()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn mark_failed(pool: &mut DbPool<'_>, id: DataExportId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(data_export::table.find(id))
+      .set(data_export::failed.eq(true))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// The path of the most recent finished archive of the user.
+  pub async fn read_archive_path(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<String> {
+    let conn = &mut get_conn(pool).await?;
+    data_export::table
+      .filter(data_export::local_user_id.eq(local_user_id))
+      .filter(data_export::finished_at.is_not_null())
+      .order_by(data_export::published_at.desc())
+      .select(data_export::archive_path.assume_not_null())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Archives can be downloaded for one week after they were requested. Returns the paths of the
+  /// deleted archives, so that the files can be removed.
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<Vec<String>> {
+    let conn = &mut get_conn(pool).await?;
+    let paths =
+      diesel::delete(data_export::table.filter(data_export::published_at.lt(now() - 7.days())))
+        .returning(data_export::archive_path)
+        .get_results::<Option<String>>(conn)
+        .await?;
+    Ok(paths.into_iter().flatten().collect())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    data_export::DataExport,
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_data_export() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "exporter")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+
+    assert_eq!(None, DataExport::read_latest(pool, local_user.id).await?);
+
+    // The archive can only be downloaded once it is finished
+    let export = DataExport::create(pool, local_user.id).await?;
+    assert_eq!(None, export.finished_at);
+    assert!(
+      DataExport::read_archive_path(pool, local_user.id)
+        .await
+        .is_err()
+    );
+
+    DataExport::finish(pool, export.id, "/tmp/archive.zip").await?;
+    let latest = DataExport::read_latest(pool, local_user.id).await?;
+    assert!(latest.is_some_and(|e| e.finished_at.is_some() && !e.failed));
+    assert_eq!(
+      "/tmp/archive.zip",
+      DataExport::read_archive_path(pool, local_user.id).await?
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,8 +1,21 @@
-use crate::source::federation_queue_state::FederationQueueState;
-use diesel::{ExpressionMethods, Insertable, OptionalExtension, QueryDsl, SelectableHelper};
+use crate::{newtypes::ActivityId, source::federation_queue_state::FederationQueueState};
+use chrono::{DateTime, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  Insertable,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  dsl::insert_into,
+  upsert::excluded,
+};
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema_file::{InstanceId, schema::federation_queue_state};
-use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::functions::greatest_2_nullable,
+};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl FederationQueueState {
@@ -22,9 +35,15 @@ impl FederationQueueState {
           last_retry_at: None,
           last_successful_id: None, // this value is set to the most current id for new instances
           last_successful_published_time_at: None,
+          reset_requested: false,
+          skip_to_id: None,
+          marked_dead: false,
         }),
     )
   }
+
+  /// Writes the state of the federation worker. The columns which are set by admins are left
+  /// unchanged.
   pub async fn upsert(pool: &mut DbPool<'_>, state: &FederationQueueState) -> LemmyResult<usize> {
     let conn = &mut get_conn(pool).await?;
 
@@ -32,9 +51,177 @@ impl FederationQueueState {
       .insert_into(federation_queue_state::table)
       .on_conflict(federation_queue_state::instance_id)
       .do_update()
-      .set(state)
+      .set((
+        federation_queue_state::last_successful_id
+          .eq(excluded(federation_queue_state::last_successful_id)),
+        federation_queue_state::last_successful_published_time_at.eq(excluded(
+          federation_queue_state::last_successful_published_time_at,
+        )),
+        federation_queue_state::fail_count.eq(excluded(federation_queue_state::fail_count)),
+        federation_queue_state::last_retry_at.eq(excluded(federation_queue_state::last_retry_at)),
+      ))
       .execute(conn)
       .await
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
+
+  /// Requests to reset the retry backoff, so that the next activity is sent immediately.
+  pub async fn request_reset(pool: &mut DbPool<'_>, instance_id: InstanceId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let form = (
+      federation_queue_state::instance_id.eq(instance_id),
+      federation_queue_state::reset_requested.eq(true),
+    );
+    insert_into(federation_queue_state::table)
+      .values(form)
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(form)
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Requests to skip all activities up to and including the given one.
+  pub async fn request_skip(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    activity_id: ActivityId,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let form = (
+      federation_queue_state::instance_id.eq(instance_id),
+      federation_queue_state::skip_to_id.eq(activity_id),
+    );
+    insert_into(federation_queue_state::table)
+      .values(form)
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(form)
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn set_marked_dead(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    marked_dead: bool,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let form = (
+      federation_queue_state::instance_id.eq(instance_id),
+      federation_queue_state::marked_dead.eq(marked_dead),
+    );
+    insert_into(federation_queue_state::table)
+      .values(form)
+      .on_conflict(federation_queue_state::instance_id)
+      .do_update()
+      .set(form)
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Instances for which an admin requested a reset or skip, which wasn't applied yet.
+  pub async fn list_pending_requests(pool: &mut DbPool<'_>) -> LemmyResult<Vec<InstanceId>> {
+    let conn = &mut get_conn(pool).await?;
+    federation_queue_state::table
+      .filter(
+        federation_queue_state::reset_requested
+          .eq(true)
+          .or(federation_queue_state::skip_to_id.is_not_null()),
+      )
+      .select(federation_queue_state::instance_id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Applies the requests from admins. This must only be called while there is no worker running
+  /// for the instance, otherwise the worker would overwrite the changes. A skip never moves the
+  /// queue backwards, in case the worker sent more activities since it was requested.
+  pub async fn apply_pending_requests(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(federation_queue_state::table.find(instance_id))
+      .set((
+        federation_queue_state::last_successful_id.eq(greatest_2_nullable(
+          federation_queue_state::skip_to_id,
+          federation_queue_state::last_successful_id,
+        )),
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::last_retry_at.eq(None::<DateTime<Utc>>),
+        federation_queue_state::reset_requested.eq(false),
+        federation_queue_state::skip_to_id.eq(None::<ActivityId>),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::source::instance::Instance;
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pending_requests() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "queue_state.tld").await?;
+
+    let mut state = FederationQueueState::load(pool, instance.id).await?;
+    state.last_successful_id = Some(ActivityId(10));
+    state.fail_count = 5;
+    FederationQueueState::upsert(pool, &state).await?;
+
+    FederationQueueState::request_skip(pool, instance.id, ActivityId(11)).await?;
+    FederationQueueState::set_marked_dead(pool, instance.id, true).await?;
+    assert_eq!(
+      vec![instance.id],
+      FederationQueueState::list_pending_requests(pool).await?
+    );
+
+    // The worker doesn't overwrite the requests with its own state
+    FederationQueueState::upsert(pool, &state).await?;
+    let read = FederationQueueState::load(pool, instance.id).await?;
+    assert_eq!(Some(ActivityId(11)), read.skip_to_id);
+    assert!(read.marked_dead);
+
+    FederationQueueState::apply_pending_requests(pool, instance.id).await?;
+    let read = FederationQueueState::load(pool, instance.id).await?;
+    assert_eq!(Some(ActivityId(11)), read.last_successful_id);
+    assert_eq!(0, read.fail_count);
+    assert_eq!(None, read.skip_to_id);
+    assert!(read.marked_dead);
+
+    // Skipping to an activity which was sent in the meantime keeps the queue position
+    FederationQueueState::request_skip(pool, instance.id, ActivityId(5)).await?;
+    FederationQueueState::apply_pending_requests(pool, instance.id).await?;
+    let read = FederationQueueState::load(pool, instance.id).await?;
+    assert_eq!(Some(ActivityId(11)), read.last_successful_id);
+    assert!(
+      FederationQueueState::list_pending_requests(pool)
+        .await?
+        .is_empty()
+    );
+
+    diesel::delete(federation_queue_state::table.find(instance.id))
+      .execute(&mut get_conn(pool).await?)
+      .await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
 }
@@ -12,6 +12,7 @@ use crate::{
 };
 use chrono::Utc;
 use diesel::{
+  BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
   OptionalExtension,
@@ -149,12 +150,17 @@ impl Instance {
   }
 
   /// returns a list of all instances, each with a flag of whether the instance is allowed or not
-  /// and dead or not ordered by id
+  /// and dead or not ordered by id. Instances can also be marked dead manually by an admin.
   pub async fn read_federated_with_blocked_and_dead(
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<Vec<(Self, bool, bool)>> {
     let conn = &mut get_conn(pool).await?;
-    let is_dead_expr = coalesce(instance::updated_at, instance::published_at).lt(now() - 3.days());
+    let is_dead_expr = coalesce(instance::updated_at, instance::published_at)
+      .lt(now() - 3.days())
+      .or(coalesce(
+        federation_queue_state::marked_dead.nullable(),
+        false,
+      ));
     // this needs to be done in two steps because the meaning of the "blocked" column depends on the
     // existence of any value at all in the allowlist. (so a normal join wouldn't work)
     let use_allowlist = federation_allowlist::table
@@ -164,6 +170,7 @@ impl Instance {
     if use_allowlist {
       instance::table
         .left_join(federation_allowlist::table)
+        .left_join(federation_queue_state::table)
         .select((
           Self::as_select(),
           federation_allowlist::instance_id.nullable().is_not_null(),
@@ -176,6 +183,7 @@ impl Instance {
     } else {
       instance::table
         .left_join(federation_blocklist::table)
+        .left_join(federation_queue_state::table)
         .select((
           Self::as_select(),
           federation_blocklist::instance_id.nullable().is_null(),
@@ -64,6 +64,23 @@ impl LocalSiteRateLimitUpdateForm {
       && self.comment_interval_seconds.is_none()
       && self.search_max_requests.is_none()
       && self.search_interval_seconds.is_none()
+      && self.import_user_settings_max_requests.is_none()
+      && self.import_user_settings_interval_seconds.is_none()
+      && self.new_account_max_age_days.is_none()
+      && self.new_account_limit_percent.is_none()
+      && self.established_account_limit_percent.is_none()
+      && self.bot_account_limit_percent.is_none()
+      && self.moderator_limit_percent.is_none()
+      && self.vote_max_requests.is_none()
+      && self.vote_interval_seconds.is_none()
+      && self.follow_max_requests.is_none()
+      && self.follow_interval_seconds.is_none()
+      && self.report_max_requests.is_none()
+      && self.report_interval_seconds.is_none()
+      && self.private_message_max_requests.is_none()
+      && self.private_message_interval_seconds.is_none()
+      && self.resolve_object_max_requests.is_none()
+      && self.resolve_object_interval_seconds.is_none()
       && self.updated_at.is_none()
   }
 }
@@ -1,12 +1,19 @@
 pub mod activity;
 pub mod actor_language;
+pub mod api_token;
+pub mod automod_rule;
 pub mod comment;
+pub mod comment_draft;
 pub mod comment_report;
+pub mod comment_revision;
 pub mod community;
 pub mod community_community_follow;
+pub mod community_flair;
 pub mod community_report;
 pub mod community_tag;
+pub mod content_import;
 pub mod custom_emoji;
+pub mod data_export;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
@@ -21,6 +28,7 @@ pub mod local_site_url_blocklist;
 pub mod local_user;
 pub mod local_user_invite;
 pub mod login_token;
+pub mod mod_template;
 pub mod modlog;
 pub mod multi_community;
 pub mod notification;
@@ -29,10 +37,16 @@ pub mod oauth_provider;
 pub mod password_reset_request;
 pub mod person;
 pub mod post;
+pub mod post_draft;
+pub mod post_poll;
 pub mod post_report;
+pub mod post_revision;
 pub mod private_message;
 pub mod private_message_report;
 pub mod registration_application;
+pub mod rule;
 pub mod secret;
+pub mod shared_state;
 pub mod site;
 pub mod tagline;
+pub mod webhook;
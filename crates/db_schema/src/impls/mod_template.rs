@@ -0,0 +1,167 @@
+use crate::{
+  newtypes::{CommunityId, ModTemplateId},
+  source::mod_template::{ModTemplate, ModTemplateInsertForm, ModTemplateUpdateForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::mod_template;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for ModTemplate {
+  type InsertForm = ModTemplateInsertForm;
+  type UpdateForm = ModTemplateUpdateForm;
+  type IdType = ModTemplateId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_template::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    id: ModTemplateId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_template::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl ModTemplate {
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    mod_template::table
+      .filter(mod_template::community_id.eq(community_id))
+      .order_by((mod_template::title, mod_template::id))
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Reads the template which was selected for a mod action, and ensures that it belongs to the
+  /// community where the action happens.
+  pub async fn read_for_action(
+    pool: &mut DbPool<'_>,
+    id: Option<ModTemplateId>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Option<Self>> {
+    let Some(id) = id else {
+      return Ok(None);
+    };
+    let template = Self::read(pool, id).await?;
+    if template.community_id != community_id {
+      return Err(LemmyErrorType::ModTemplateNotInCommunity.into());
+    }
+    Ok(Some(template))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    mod_template::{ModTemplate, ModTemplateInsertForm, ModTemplateUpdateForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_mod_templates() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        instance.id,
+        "test_mod_templates".to_string(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let other_community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        instance.id,
+        "test_mod_templates_2".to_string(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+
+    let spam = ModTemplate::create(
+      pool,
+      &ModTemplateInsertForm {
+        community_id: community.id,
+        title: "Spam".to_string(),
+        body: "Removed as spam".to_string(),
+        rule_id: None,
+      },
+    )
+    .await?;
+    let off_topic = ModTemplate::create(
+      pool,
+      &ModTemplateInsertForm {
+        community_id: community.id,
+        title: "Off topic".to_string(),
+        body: "Please post this elsewhere".to_string(),
+        rule_id: None,
+      },
+    )
+    .await?;
+
+    let form = ModTemplateUpdateForm {
+      body: Some("Removed as spam, see the sidebar".to_string()),
+      ..Default::default()
+    };
+    let spam = ModTemplate::update(pool, spam.id, &form).await?;
+    assert_eq!("Removed as spam, see the sidebar", spam.body);
+
+    // Sorted by title
+    let templates = ModTemplate::list_for_community(pool, community.id).await?;
+    assert_eq!(vec![off_topic, spam.clone()], templates);
+    assert!(
+      ModTemplate::list_for_community(pool, other_community.id)
+        .await?
+        .is_empty()
+    );
+
+    // Templates can only be used in their own community
+    assert_eq!(
+      Some(spam.clone()),
+      ModTemplate::read_for_action(pool, Some(spam.id), community.id).await?
+    );
+    assert_eq!(
+      None,
+      ModTemplate::read_for_action(pool, None, community.id).await?
+    );
+    assert!(
+      ModTemplate::read_for_action(pool, Some(spam.id), other_community.id)
+        .await
+        .is_err()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -150,6 +150,34 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::ModWarnPost, false, mod_person_id)
     }
   }
+  pub fn mod_approve_post(
+    mod_person_id: PersonId,
+    post: &Post,
+    approved: bool,
+    reason: Option<&'a str>,
+  ) -> Self {
+    let kind = if approved {
+      ModlogKind::ModApprovePost
+    } else {
+      ModlogKind::ModRejectPost
+    };
+    Self {
+      reason,
+      target_post_id: Some(post.id),
+      target_community_id: Some(post.community_id),
+      target_person_id: Some(post.creator_id),
+      ..ModlogInsertForm::new(kind, false, mod_person_id)
+    }
+  }
+  pub fn mod_automod_post(mod_person_id: PersonId, post: &Post, reason: &'a str) -> Self {
+    Self {
+      reason: Some(reason),
+      target_post_id: Some(post.id),
+      target_community_id: Some(post.community_id),
+      target_person_id: Some(post.creator_id),
+      ..ModlogInsertForm::new(ModlogKind::ModAutomodPost, false, mod_person_id)
+    }
+  }
   pub fn admin_remove_community(
     mod_person: &Person,
     community_id: CommunityId,
@@ -242,6 +270,44 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::AdminBlockInstance, !block, mod_person_id)
     }
   }
+  pub fn admin_reset_federation_queue(
+    mod_person_id: PersonId,
+    instance_id: InstanceId,
+    reason: Option<&'a str>,
+  ) -> Self {
+    Self {
+      reason,
+      target_instance_id: Some(instance_id),
+      ..ModlogInsertForm::new(ModlogKind::AdminResetFederationQueue, false, mod_person_id)
+    }
+  }
+  pub fn admin_skip_federation_activity(
+    mod_person_id: PersonId,
+    instance_id: InstanceId,
+    reason: Option<&'a str>,
+  ) -> Self {
+    Self {
+      reason,
+      target_instance_id: Some(instance_id),
+      ..ModlogInsertForm::new(
+        ModlogKind::AdminSkipFederationActivity,
+        false,
+        mod_person_id,
+      )
+    }
+  }
+  pub fn admin_mark_instance_dead(
+    mod_person_id: PersonId,
+    instance_id: InstanceId,
+    dead: bool,
+    reason: Option<&'a str>,
+  ) -> Self {
+    Self {
+      reason,
+      target_instance_id: Some(instance_id),
+      ..ModlogInsertForm::new(ModlogKind::AdminMarkInstanceDead, !dead, mod_person_id)
+    }
+  }
   pub fn admin_purge_comment(
     mod_person_id: PersonId,
     comment: &Comment,
@@ -1,14 +1,17 @@
 use crate::{
   diesel::{BoolExpressionMethods, NullableExpressionMethods, OptionalExtension},
   newtypes::{CommunityId, LocalUserId},
-  source::person::{
-    Person,
-    PersonActions,
-    PersonBlockForm,
-    PersonFollowerForm,
-    PersonInsertForm,
-    PersonNoteForm,
-    PersonUpdateForm,
+  source::{
+    community::{Community, CommunityFollowerForm},
+    person::{
+      Person,
+      PersonActions,
+      PersonBlockForm,
+      PersonFollowerForm,
+      PersonInsertForm,
+      PersonNoteForm,
+      PersonUpdateForm,
+    },
   },
   traits::{ApubActor, Blockable, Followable},
   utils::format_actor_url,
@@ -20,13 +23,23 @@ use diesel::{
   QueryDsl,
   dsl::{exists, insert_into, not, select},
   expression::SelectableHelper,
+  upsert::excluded,
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  schema::{instance, instance_actions, local_user, person, person_actions},
+  enums::CommunityFollowerState,
+  schema::{
+    community,
+    community_actions,
+    instance,
+    instance_actions,
+    local_user,
+    person,
+    person_actions,
+  },
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -95,6 +108,144 @@ impl Person {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 
+  /// Transfer state to the new account after a person moved. Local users who blocked the old
+  /// account also block the new account, followers of the old account are moved to the new
+  /// account, and follows of local communities are moved over.
+  ///
+  /// Follows of remote communities can't be moved in the database, as the remote instance needs
+  /// to know about them. These communities are returned, so that new follows can be sent.
+  pub async fn migrate_actions(
+    pool: &mut DbPool<'_>,
+    old_person_id: PersonId,
+    new_person_id: PersonId,
+  ) -> LemmyResult<Vec<Community>> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let blockers: Vec<PersonId> = person_actions::table
+            .inner_join(person::table.on(person_actions::person_id.eq(person::id)))
+            .filter(person_actions::target_id.eq(old_person_id))
+            .filter(person_actions::blocked_at.is_not_null())
+            .filter(person::local)
+            .filter(person::id.ne(new_person_id))
+            .select(person_actions::person_id)
+            .load(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::NotFound)?;
+          let block_forms = blockers
+            .into_iter()
+            .map(|p| PersonBlockForm::new(p, new_person_id))
+            .collect::<Vec<_>>();
+          insert_into(person_actions::table)
+            .values(&block_forms)
+            .on_conflict((person_actions::person_id, person_actions::target_id))
+            .do_update()
+            .set(person_actions::blocked_at.eq(excluded(person_actions::blocked_at)))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          let followers: Vec<(PersonId, Option<bool>)> = person_actions::table
+            .filter(person_actions::target_id.eq(old_person_id))
+            .filter(person_actions::followed_at.is_not_null())
+            .filter(person_actions::person_id.ne(new_person_id))
+            .select((person_actions::person_id, person_actions::follow_pending))
+            .load(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::NotFound)?;
+          let follower_ids = followers.iter().map(|(p, _)| *p).collect::<Vec<_>>();
+          let follower_forms = followers
+            .into_iter()
+            .map(|(p, pending)| PersonFollowerForm::new(new_person_id, p, pending.unwrap_or(false)))
+            .collect::<Vec<_>>();
+          insert_into(person_actions::table)
+            .values(&follower_forms)
+            .on_conflict((person_actions::person_id, person_actions::target_id))
+            .do_update()
+            .set((
+              person_actions::followed_at.eq(excluded(person_actions::followed_at)),
+              person_actions::follow_pending.eq(excluded(person_actions::follow_pending)),
+            ))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          uplete(
+            person_actions::table
+              .filter(person_actions::target_id.eq(old_person_id))
+              .filter(person_actions::person_id.eq_any(follower_ids)),
+          )
+          .set_null(person_actions::followed_at)
+          .set_null(person_actions::follow_pending)
+          .get_result::<UpleteCount>(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          let follows: Vec<(CommunityId, Option<CommunityFollowerState>)> =
+            community_actions::table
+              .inner_join(community::table)
+              .filter(community_actions::person_id.eq(old_person_id))
+              .filter(community_actions::followed_at.is_not_null())
+              .filter(community::local)
+              .select((
+                community_actions::community_id,
+                community_actions::follow_state,
+              ))
+              .load(conn)
+              .await
+              .with_lemmy_type(LemmyErrorType::NotFound)?;
+          let community_ids = follows.iter().map(|(c, _)| *c).collect::<Vec<_>>();
+          let follow_forms = follows
+            .into_iter()
+            .map(|(c, state)| {
+              CommunityFollowerForm::new(
+                c,
+                new_person_id,
+                state.unwrap_or(CommunityFollowerState::Accepted),
+              )
+            })
+            .collect::<Vec<_>>();
+          insert_into(community_actions::table)
+            .values(&follow_forms)
+            .on_conflict((
+              community_actions::person_id,
+              community_actions::community_id,
+            ))
+            .do_update()
+            .set((
+              community_actions::followed_at.eq(excluded(community_actions::followed_at)),
+              community_actions::follow_state.eq(excluded(community_actions::follow_state)),
+            ))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          uplete(
+            community_actions::table
+              .filter(community_actions::person_id.eq(old_person_id))
+              .filter(community_actions::community_id.eq_any(community_ids)),
+          )
+          .set_null(community_actions::followed_at)
+          .set_null(community_actions::follow_state)
+          .set_null(community_actions::follow_approver_id)
+          .get_result::<UpleteCount>(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          community_actions::table
+            .inner_join(community::table)
+            .filter(community_actions::person_id.eq(old_person_id))
+            .filter(community_actions::followed_at.is_not_null())
+            .filter(community::local.eq(false))
+            .select(Community::as_select())
+            .load(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::NotFound)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
   pub async fn delete_account(
     pool: &mut DbPool<'_>,
     person_id: PersonId,
@@ -416,14 +567,22 @@ mod tests {
   use crate::{
     source::{
       comment::{Comment, CommentActions, CommentInsertForm, CommentLikeForm, CommentUpdateForm},
-      community::{Community, CommunityInsertForm},
-      person::{Person, PersonActions, PersonFollowerForm, PersonInsertForm, PersonUpdateForm},
+      community::{Community, CommunityActions, CommunityFollowerForm, CommunityInsertForm},
+      person::{
+        Person,
+        PersonActions,
+        PersonBlockForm,
+        PersonFollowerForm,
+        PersonInsertForm,
+        PersonUpdateForm,
+      },
       post::{Post, PostActions, PostInsertForm, PostLikeForm},
     },
     test_data::TestData,
-    traits::{Followable, Likeable},
+    traits::{Blockable, Followable, Likeable},
   };
   use diesel_uplete::UpleteCount;
+  use lemmy_db_schema_file::enums::CommunityFollowerState;
   use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -459,6 +618,8 @@ mod tests {
       post_score: 0,
       comment_count: 0,
       comment_score: 0,
+      also_known_as: vec![],
+      moved_to: None,
     };
 
     let read_person = Person::read(pool, data.person.id).await?;
@@ -508,6 +669,75 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn migrate_actions() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+
+    let old_form = PersonInsertForm::test_form(data.instance.id, "old_account");
+    let old = Person::create(pool, &old_form).await?;
+    let new_form = PersonInsertForm::test_form(data.instance.id, "new_account");
+    let new = Person::create(pool, &new_form).await?;
+
+    let community_form =
+      CommunityInsertForm::new(data.instance.id, "moving".into(), "pubkey".to_string());
+    let community = Community::create(pool, &community_form).await?;
+    let follow_form =
+      CommunityFollowerForm::new(community.id, old.id, CommunityFollowerState::Accepted);
+    CommunityActions::follow(pool, &follow_form).await?;
+    let block_form = PersonBlockForm::new(data.person.id, old.id);
+    PersonActions::block(pool, &block_form).await?;
+    let follower_form = PersonInsertForm::test_form(data.instance.id, "follower");
+    let follower = Person::create(pool, &follower_form).await?;
+    let person_follow_form = PersonFollowerForm::new(old.id, follower.id, false);
+    PersonActions::follow(pool, &person_follow_form).await?;
+
+    Person::migrate_actions(pool, old.id, new.id).await?;
+
+    // followers of the old account now follow the new account
+    assert_eq!(
+      vec![follower.inbox_url.clone()],
+      PersonActions::follower_inboxes(pool, new.id).await?
+    );
+    assert!(
+      PersonActions::follower_inboxes(pool, old.id)
+        .await?
+        .is_empty()
+    );
+
+    // the block is copied to the new account
+    assert!(
+      PersonActions::read_block(pool, data.person.id, new.id)
+        .await
+        .is_err()
+    );
+    assert!(
+      PersonActions::read_block(pool, data.person.id, old.id)
+        .await
+        .is_err()
+    );
+
+    // the follow is moved to the new account
+    let community = Community::read(pool, community.id).await?;
+    assert_eq!(1, community.subscribers);
+    let new_follow = CommunityActions::read(pool, community.id, new.id).await?;
+    assert_eq!(
+      Some(CommunityFollowerState::Accepted),
+      new_follow.follow_state
+    );
+    assert!(
+      CommunityActions::read(pool, community.id, old.id)
+        .await
+        .is_err()
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_aggregates() -> LemmyResult<()> {
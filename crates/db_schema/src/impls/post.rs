@@ -32,7 +32,7 @@ use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
   enums::PostNotificationsMode,
-  schema::{community, local_user, person, post, post_actions},
+  schema::{community, local_user, person, post, post_actions, post_revision},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -143,6 +143,20 @@ impl Post {
   ) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
 
+    // Previous versions would otherwise still contain the original content
+    diesel::delete(
+      post_revision::table.filter(
+        post_revision::post_id.eq_any(
+          post::table
+            .filter(post::creator_id.eq(for_creator_id))
+            .select(post::id),
+        ),
+      ),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
     diesel::update(post::table.filter(post::creator_id.eq(for_creator_id)))
       .set((
         post::name.eq(DELETED_REPLACEMENT_TEXT),
@@ -665,6 +679,7 @@ mod tests {
       scaled_rank: RANK_DEFAULT,
       unresolved_report_count: 0,
       federation_pending: false,
+      approval_pending: false,
     };
 
     // Post Like
@@ -0,0 +1,138 @@
+use crate::{
+  newtypes::PostDraftId,
+  source::post_draft::{PostDraft, PostDraftInsertForm, PostDraftUpdateForm},
+};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{delete, insert_into},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{PersonId, schema::post_draft};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+  utils::functions::coalesce,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for PostDraft {
+  type InsertForm = PostDraftInsertForm;
+  type UpdateForm = PostDraftUpdateForm;
+  type IdType = PostDraftId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_draft::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    post_draft_id: PostDraftId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(post_draft::table.find(post_draft_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl PostDraft {
+  /// Lists the drafts of a user, most recently changed first.
+  pub async fn list(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_draft::table
+      .filter(post_draft::creator_id.eq(creator_id))
+      .order_by(coalesce(post_draft::updated_at, post_draft::published_at).desc())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn count(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    post_draft::table
+      .filter(post_draft::creator_id.eq(creator_id))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete_all(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(post_draft::table.filter(post_draft::creator_id.eq(creator_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post_draft::{PostDraft, PostDraftInsertForm, PostDraftUpdateForm},
+  };
+  use chrono::Utc;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_draft() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "drafter")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "test_drafts".to_string(), "pubkey".to_string()),
+    )
+    .await?;
+
+    let form = PostDraftInsertForm::new(person.id, community.id, "Unfinished".to_string());
+    let first = PostDraft::create(pool, &form).await?;
+    let form = PostDraftInsertForm {
+      body: Some("Some thoughts".to_string()),
+      ..PostDraftInsertForm::new(person.id, community.id, String::new())
+    };
+    let second = PostDraft::create(pool, &form).await?;
+    assert_eq!(2, PostDraft::count(pool, person.id).await?);
+
+    // Editing a draft moves it to the top
+    let form = PostDraftUpdateForm {
+      url: Some(Some("https://example.com".to_string())),
+      updated_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    let first = PostDraft::update(pool, first.id, &form).await?;
+    assert_eq!(Some("https://example.com".to_string()), first.url);
+    assert_eq!(
+      vec![first.id, second.id],
+      PostDraft::list(pool, person.id)
+        .await?
+        .into_iter()
+        .map(|d| d.id)
+        .collect::<Vec<_>>()
+    );
+
+    PostDraft::delete(pool, second.id).await?;
+    assert_eq!(1, PostDraft::count(pool, person.id).await?);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
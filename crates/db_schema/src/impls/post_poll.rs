@@ -0,0 +1,370 @@
+use crate::{
+  newtypes::{PostId, PostPollOptionId},
+  source::post_poll::{
+    PostPoll,
+    PostPollInsertForm,
+    PostPollOption,
+    PostPollOptionInsertForm,
+    PostPollUpdateForm,
+    PostPollVote,
+    PostPollVoteForm,
+  },
+};
+use chrono::Utc;
+use diesel::{
+  AggregateExpressionMethods,
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  delete,
+  dsl::{count, count_star, insert_into},
+  update,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{post_poll, post_poll_option, post_poll_vote},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for PostPoll {
+  type InsertForm = PostPollInsertForm;
+  type UpdateForm = PostPollUpdateForm;
+  type IdType = PostId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_poll::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    update(post_poll::table.find(post_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl PostPoll {
+  /// Creates a new poll together with its options.
+  pub async fn create_with_options(
+    pool: &mut DbPool<'_>,
+    form: &PostPollInsertForm,
+    options: &[String],
+  ) -> LemmyResult<(Self, Vec<PostPollOption>)> {
+    let conn = &mut get_conn(pool).await?;
+    let option_forms = options
+      .iter()
+      .map(|name| PostPollOptionInsertForm {
+        post_id: form.post_id,
+        name: name.clone(),
+        vote_count: None,
+      })
+      .collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let poll = insert_into(post_poll::table)
+            .values(form)
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          let options = insert_into(post_poll_option::table)
+            .values(&option_forms)
+            .get_results::<PostPollOption>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          Ok((poll, options))
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Returns the poll of the given post, or `None` if the post is not a poll.
+  pub async fn read_for_post(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll::table
+      .find(post_id)
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Inserts or updates a poll received over federation. Options are matched by name, and options
+  /// which are not included anymore get deleted together with their votes.
+  pub async fn upsert_apub(
+    pool: &mut DbPool<'_>,
+    form: &PostPollInsertForm,
+    options: &[PostPollOptionInsertForm],
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let post_id = form.post_id;
+    let names = options.iter().map(|o| o.name.clone()).collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let poll = insert_into(post_poll::table)
+            .values(form)
+            .on_conflict(post_poll::post_id)
+            .do_update()
+            .set(form)
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          delete(
+            post_poll_option::table
+              .filter(post_poll_option::post_id.eq(post_id))
+              .filter(post_poll_option::name.ne_all(names)),
+          )
+          .execute(conn)
+          .await?;
+
+          for option in options {
+            insert_into(post_poll_option::table)
+              .values(option)
+              .on_conflict((post_poll_option::post_id, post_poll_option::name))
+              .do_update()
+              .set(option)
+              .execute(conn)
+              .await
+              .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          }
+          Ok(poll)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Recounts the votes of all options and the number of voters. Only used for local polls,
+  /// the counts of remote polls are received over federation.
+  pub async fn update_counts(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let votes = post_poll_vote::table
+            .filter(post_poll_vote::post_id.eq(post_id))
+            .group_by(post_poll_vote::post_poll_option_id)
+            .select((post_poll_vote::post_poll_option_id, count_star()))
+            .load::<(PostPollOptionId, i64)>(conn)
+            .await?;
+
+          update(post_poll_option::table.filter(post_poll_option::post_id.eq(post_id)))
+            .set(post_poll_option::vote_count.eq(0))
+            .execute(conn)
+            .await?;
+          for (option_id, count) in votes {
+            update(post_poll_option::table.find(option_id))
+              .set(post_poll_option::vote_count.eq(i32::try_from(count).unwrap_or(i32::MAX)))
+              .execute(conn)
+              .await?;
+          }
+
+          let voter_count = post_poll_vote::table
+            .filter(post_poll_vote::post_id.eq(post_id))
+            .select(count(post_poll_vote::person_id).aggregate_distinct())
+            .first::<i64>(conn)
+            .await?;
+          update(post_poll::table.find(post_id))
+            .set((
+              post_poll::voter_count.eq(i32::try_from(voter_count).unwrap_or(i32::MAX)),
+              post_poll::updated_at.eq(Utc::now()),
+            ))
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// A poll is closed if it was closed explicitly or if its end time has passed.
+  pub fn is_closed(&self) -> bool {
+    self.closed_at.is_some() || self.end_time_at.is_some_and(|end| end <= Utc::now())
+  }
+
+  /// Vote counts are hidden from everyone except the poll creator until the poll is closed, if
+  /// the creator chose so.
+  pub fn results_hidden(&self) -> bool {
+    self.hide_results_until_closed && !self.is_closed()
+  }
+}
+
+impl PostPollOption {
+  pub async fn read_for_post(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll_option::table
+      .filter(post_poll_option::post_id.eq(post_id))
+      .order_by(post_poll_option::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl PostPollVote {
+  /// Replaces all previous votes of the person in this poll with the given options.
+  pub async fn vote(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    person_id: PersonId,
+    option_ids: &[PostPollOptionId],
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = option_ids
+      .iter()
+      .map(|option_id| PostPollVoteForm {
+        post_poll_option_id: *option_id,
+        person_id,
+        post_id,
+      })
+      .collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(
+            post_poll_vote::table
+              .filter(post_poll_vote::post_id.eq(post_id))
+              .filter(post_poll_vote::person_id.eq(person_id)),
+          )
+          .execute(conn)
+          .await?;
+
+          insert_into(post_poll_vote::table)
+            .values(&forms)
+            .get_results::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Adds a single vote, keeping previous votes of the person. Used for multiple choice votes
+  /// received over federation, which are sent as one activity per option.
+  pub async fn add(pool: &mut DbPool<'_>, form: &PostPollVoteForm) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_poll_vote::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    Ok(())
+  }
+
+  /// The options which the person voted for in this poll.
+  pub async fn read_for_person(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    person_id: PersonId,
+  ) -> LemmyResult<Vec<PostPollOptionId>> {
+    let conn = &mut get_conn(pool).await?;
+    post_poll_vote::table
+      .filter(post_poll_vote::post_id.eq(post_id))
+      .filter(post_poll_vote::person_id.eq(person_id))
+      .select(post_poll_vote::post_poll_option_id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+    post_poll::{PostPoll, PostPollInsertForm, PostPollOption, PostPollVote},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_poll_votes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let alice = Person::create(pool, &PersonInsertForm::test_form(instance.id, "alice")).await?;
+    let bob = Person::create(pool, &PersonInsertForm::test_form(instance.id, "bob")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "poll_community".into(), "pubkey".into()),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("A poll".into(), alice.id, community.id),
+    )
+    .await?;
+
+    assert_eq!(None, PostPoll::read_for_post(pool, post.id).await?);
+
+    let form = PostPollInsertForm {
+      multiple_choice: Some(true),
+      ..PostPollInsertForm::new(post.id)
+    };
+    let (poll, options) =
+      PostPoll::create_with_options(pool, &form, &["yes".into(), "no".into()]).await?;
+    assert!(poll.multiple_choice);
+    assert!(!poll.is_closed());
+    let option_ids = options.iter().map(|o| o.id).collect::<Vec<_>>();
+    let &[yes, no] = option_ids.as_slice() else {
+      return Err(LemmyErrorType::NotFound.into());
+    };
+
+    PostPollVote::vote(pool, post.id, alice.id, &[yes, no]).await?;
+    PostPollVote::vote(pool, post.id, bob.id, &[no]).await?;
+    // Voting again replaces the previous votes
+    PostPollVote::vote(pool, post.id, bob.id, &[yes]).await?;
+    assert_eq!(
+      vec![yes],
+      PostPollVote::read_for_person(pool, post.id, bob.id).await?
+    );
+
+    let poll = PostPoll::update_counts(pool, post.id).await?;
+    assert_eq!(2, poll.voter_count);
+    let counts = PostPollOption::read_for_post(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|o| (o.name, o.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(vec![("yes".to_string(), 2), ("no".to_string(), 1)], counts);
+
+    Post::delete(pool, post.id).await?;
+    assert_eq!(None, PostPoll::read_for_post(pool, post.id).await?);
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,165 @@
+use crate::{
+  newtypes::PostId,
+  source::{
+    post::Post,
+    post_revision::{PostRevision, PostRevisionInsertForm},
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::post_revision;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PostRevision {
+  /// Stores the previous version of a post, if the edit changed its title, body, url or nsfw flag.
+  pub async fn create_if_changed(
+    pool: &mut DbPool<'_>,
+    orig: &Post,
+    updated: &Post,
+  ) -> LemmyResult<Option<Self>> {
+    if orig.name == updated.name
+      && orig.body == updated.body
+      && orig.url == updated.url
+      && orig.nsfw == updated.nsfw
+    {
+      return Ok(None);
+    }
+    let form = PostRevisionInsertForm {
+      post_id: orig.id,
+      name: orig.name.clone(),
+      body: orig.body.clone(),
+      url: orig.url.clone(),
+      nsfw: orig.nsfw,
+      published_at: orig.updated_at.unwrap_or(orig.published_at),
+    };
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_revision::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .map(Some)
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Lists the previous versions of a post, newest first.
+  pub async fn list(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_revision::table
+      .filter(post_revision::post_id.eq(post_id))
+      .order_by(post_revision::published_at.desc())
+      .then_order_by(post_revision::id.desc())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm, PostUpdateForm},
+    post_revision::PostRevision,
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_revision() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "ron")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        instance.id,
+        "test_revisions".to_string(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("first title".into(), person.id, community.id),
+    )
+    .await?;
+
+    // Changing something other than title, body, url or nsfw doesn't create a revision
+    let featured = Post::update(
+      pool,
+      post.id,
+      &PostUpdateForm {
+        featured_community: Some(true),
+        ..Default::default()
+      },
+    )
+    .await?;
+    assert_eq!(
+      None,
+      PostRevision::create_if_changed(pool, &post, &featured).await?
+    );
+
+    let edited = Post::update(
+      pool,
+      post.id,
+      &PostUpdateForm {
+        name: Some("second title".into()),
+        body: Some(Some("a body".into())),
+        ..Default::default()
+      },
+    )
+    .await?;
+    let revision = PostRevision::create_if_changed(pool, &featured, &edited).await?;
+    assert_eq!(
+      Some("first title"),
+      revision.as_ref().map(|r| r.name.as_str())
+    );
+
+    let edited2 = Post::update(
+      pool,
+      post.id,
+      &PostUpdateForm {
+        name: Some("third title".into()),
+        ..Default::default()
+      },
+    )
+    .await?;
+    PostRevision::create_if_changed(pool, &edited, &edited2).await?;
+
+    // Mods can only change the nsfw flag, which also creates a revision
+    let edited3 = Post::update(
+      pool,
+      post.id,
+      &PostUpdateForm {
+        nsfw: Some(true),
+        ..Default::default()
+      },
+    )
+    .await?;
+    PostRevision::create_if_changed(pool, &edited2, &edited3).await?;
+
+    let revisions = PostRevision::list(pool, post.id).await?;
+    let names: Vec<_> = revisions.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(vec!["third title", "second title", "first title"], names);
+    assert_eq!(
+      vec![None, Some("a body".to_string()), None],
+      revisions.iter().map(|r| r.body.clone()).collect::<Vec<_>>()
+    );
+    assert!(revisions.iter().all(|r| !r.nsfw));
+
+    // Permadeleting the post creator also removes the history
+    Post::permadelete_for_creator(pool, person.id).await?;
+    assert!(PostRevision::list(pool, post.id).await?.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
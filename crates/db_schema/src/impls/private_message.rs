@@ -103,6 +103,18 @@ impl PrivateMessage {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 
+  /// All messages which the person sent and didn't delete, oldest first.
+  pub async fn list_sent(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    private_message::table
+      .filter(private_message::creator_id.eq(creator_id))
+      .filter(private_message::deleted.eq(false))
+      .order_by(private_message::published_at)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Dont let creator know that recipient deleted the message
   pub fn clear_deleted_by_recipient(&mut self, my_person: Option<&Person>) {
     if Some(self.creator_id) == my_person.map(|p| p.id) {
@@ -0,0 +1,201 @@
+use crate::{
+  newtypes::{CommunityId, RuleId},
+  source::rule::{Rule, RuleInsertForm, RuleUpdateForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into, upsert::excluded};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::rule;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::collections::HashSet;
+
+impl Crud for Rule {
+  type InsertForm = RuleInsertForm;
+  type UpdateForm = RuleUpdateForm;
+  type IdType = RuleId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(rule::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(pool: &mut DbPool<'_>, id: RuleId, form: &Self::UpdateForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(rule::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl Rule {
+  /// Lists the rules of the given community in order, or the rules of the local site if
+  /// `community_id` is `None`.
+  pub async fn read_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: Option<CommunityId>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = rule::table.filter(rule::deleted.eq(false)).into_boxed();
+    query = match community_id {
+      Some(community_id) => query.filter(rule::community_id.eq(community_id)),
+      None => query.filter(rule::community_id.is_null()),
+    };
+    query
+      .order_by((rule::position, rule::id))
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Syncs the rules of a remote community. Rules which are missing from `forms` are marked as
+  /// deleted.
+  pub async fn update_many(
+    pool: &mut DbPool<'_>,
+    mut forms: Vec<RuleInsertForm>,
+    existing_rules: Vec<Rule>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let new_rule_ids = forms
+      .iter()
+      .map(|rule| rule.ap_id.clone())
+      .collect::<HashSet<_>>();
+    let delete_forms = existing_rules
+      .into_iter()
+      .filter(|rule| !new_rule_ids.contains(&rule.ap_id))
+      .map(|r| RuleInsertForm {
+        ap_id: r.ap_id,
+        community_id: r.community_id,
+        title: r.title,
+        description: r.description,
+        position: Some(r.position),
+        deleted: Some(true),
+      });
+    forms.extend(delete_forms);
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          insert_into(rule::table)
+            .values(&forms)
+            .on_conflict(rule::ap_id)
+            .do_update()
+            .set((
+              rule::title.eq(excluded(rule::title)),
+              rule::description.eq(excluded(rule::description)),
+              rule::position.eq(excluded(rule::position)),
+              rule::deleted.eq(excluded(rule::deleted)),
+            ))
+            .execute(conn)
+            .await?;
+
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await?;
+
+    Ok(())
+  }
+
+  pub async fn read_apub(pool: &mut DbPool<'_>, ap_id: &DbUrl) -> LemmyResult<Rule> {
+    let conn = &mut get_conn(pool).await?;
+    rule::table
+      .filter(rule::ap_id.eq(ap_id))
+      .filter(rule::deleted.eq(false))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    rule::{Rule, RuleInsertForm, RuleUpdateForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rules() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "test_rules".to_string(), "pubkey".to_string()),
+    )
+    .await?;
+
+    let form = |ap_id: &str, community_id, title: &str, position| -> LemmyResult<RuleInsertForm> {
+      Ok(RuleInsertForm {
+        ap_id: Url::parse(ap_id)?.into(),
+        community_id,
+        title: title.to_string(),
+        description: None,
+        position: Some(position),
+        deleted: None,
+      })
+    };
+    let be_nice = Rule::create(
+      pool,
+      &form(
+        "https://my_domain.tld/rule/1",
+        Some(community.id),
+        "Be nice",
+        1,
+      )?,
+    )
+    .await?;
+    let no_spam = Rule::create(
+      pool,
+      &form(
+        "https://my_domain.tld/rule/2",
+        Some(community.id),
+        "No spam",
+        0,
+      )?,
+    )
+    .await?;
+    let site_rule = Rule::create(
+      pool,
+      &form("https://my_domain.tld/rule/3", None, "Follow the law", 0)?,
+    )
+    .await?;
+
+    // Community rules are ordered by position, and site rules are listed separately
+    let rules = Rule::read_for_community(pool, Some(community.id)).await?;
+    assert_eq!(vec![no_spam.clone(), be_nice.clone()], rules);
+    let rules = Rule::read_for_community(pool, None).await?;
+    assert_eq!(vec![site_rule.clone()], rules);
+
+    let update_form = RuleUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Rule::update(pool, no_spam.id, &update_form).await?;
+    let rules = Rule::read_for_community(pool, Some(community.id)).await?;
+    assert_eq!(vec![be_nice], rules);
+
+    Rule::delete(pool, site_rule.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,170 @@
+use crate::{
+  newtypes::LocalUserId,
+  source::shared_state::{IdempotencyKey, RateLimitBucket},
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{IntervalDsl, case_when, insert_into},
+};
+use diesel_async::RunQueryDsl;
+use futures::future::BoxFuture;
+use lemmy_db_schema_file::schema::{idempotency_key, rate_limit_bucket};
+use lemmy_diesel_utils::{
+  connection::{ActualDbPool, DbPool, get_conn},
+  utils::{now, seconds_to_pg_interval},
+};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  rate_limit::SharedRateLimitStore,
+};
+use std::time::Duration;
+
+impl RateLimitBucket {
+  /// Counts a request in the bucket, or starts a new bucket if there is none or if it is expired.
+  pub async fn increment(
+    pool: &mut DbPool<'_>,
+    key: &str,
+    interval: Duration,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let interval = seconds_to_pg_interval(interval.as_secs().try_into()?);
+    let not_expired = rate_limit_bucket::expires_at.gt(now());
+    insert_into(rate_limit_bucket::table)
+      .values((
+        rate_limit_bucket::key.eq(key),
+        rate_limit_bucket::count.eq(1),
+        rate_limit_bucket::expires_at.eq(now() + interval),
+      ))
+      .on_conflict(rate_limit_bucket::key)
+      .do_update()
+      .set((
+        rate_limit_bucket::count
+          .eq(case_when(not_expired, rate_limit_bucket::count + 1).otherwise(1)),
+        rate_limit_bucket::expires_at
+          .eq(case_when(not_expired, rate_limit_bucket::expires_at).otherwise(now() + interval)),
+      ))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Removes a request from the bucket, used when the request failed with a server error.
+  pub async fn decrement(pool: &mut DbPool<'_>, key: &str) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      rate_limit_bucket::table
+        .find(key)
+        .filter(rate_limit_bucket::count.gt(0)),
+    )
+    .set(rate_limit_bucket::count.eq(rate_limit_bucket::count - 1))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(rate_limit_bucket::table.filter(rate_limit_bucket::expires_at.lt(now())))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
+
+/// Keeps rate limit buckets in the database, so that they are shared between multiple Lemmy
+/// processes.
+pub struct DbRateLimitStore(pub ActualDbPool);
+
+impl SharedRateLimitStore for DbRateLimitStore {
+  fn increment(
+    &self,
+    key: String,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>> {
+    Box::pin(async move {
+      let bucket = RateLimitBucket::increment(&mut (&self.0).into(), &key, interval).await?;
+      Ok((bucket.count.try_into()?, bucket.expires_at))
+    })
+  }
+
+  fn decrement(&self, key: String) -> BoxFuture<'_, LemmyResult<()>> {
+    Box::pin(async move { RateLimitBucket::decrement(&mut (&self.0).into(), &key).await })
+  }
+}
+
+impl IdempotencyKey {
+  /// Stores the key, returns false if the same key was already used by this user.
+  pub async fn insert_new(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    key: &str,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    let inserted = insert_into(idempotency_key::table)
+      .values((
+        idempotency_key::local_user_id.eq(local_user_id),
+        idempotency_key::key.eq(key),
+      ))
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    Ok(inserted == 1)
+  }
+
+  /// Deletes keys older than two minutes, same as the in-memory idempotency set.
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      idempotency_key::table.filter(idempotency_key::published_at.lt(now() - 2.minutes())),
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rate_limit_bucket() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let key = "Post/user/test_rate_limit_bucket";
+    let interval = Duration::from_secs(60);
+
+    let first = RateLimitBucket::increment(pool, key, interval).await?;
+    assert_eq!(1, first.count);
+    let second = RateLimitBucket::increment(pool, key, interval).await?;
+    assert_eq!(2, second.count);
+    assert_eq!(first.expires_at, second.expires_at);
+
+    // Rolled back requests are not counted
+    RateLimitBucket::decrement(pool, key).await?;
+    let third = RateLimitBucket::increment(pool, key, interval).await?;
+    assert_eq!(2, third.count);
+
+    // An expired bucket starts over
+    let expired_key = "Post/user/test_rate_limit_bucket_expired";
+    RateLimitBucket::increment(pool, expired_key, Duration::ZERO).await?;
+    let restarted = RateLimitBucket::increment(pool, expired_key, interval).await?;
+    assert_eq!(1, restarted.count);
+
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      rate_limit_bucket::table.filter(rate_limit_bucket::key.eq_any([key, expired_key])),
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,384 @@
+use crate::{
+  newtypes::{CommunityId, WebhookDeliveryId, WebhookId},
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookDeliveryUpdateForm,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+    webhook_delivery_keys as key,
+  },
+  utils::limit_fetch,
+};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  PgArrayExpressionMethods,
+  QueryDsl,
+  dsl::exists,
+  insert_into,
+};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::{
+  enums::WebhookEventType,
+  schema::{community_actions, local_user, webhook, webhook_delivery},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for Webhook {
+  type InsertForm = WebhookInsertForm;
+  type UpdateForm = WebhookUpdateForm;
+  type IdType = WebhookId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook::table.find(webhook_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl Webhook {
+  /// Lists the webhooks of a community, or the site-wide webhooks if no community is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    community_id: Option<CommunityId>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table.order_by(webhook::id).into_boxed();
+    let query = if let Some(community_id) = community_id {
+      query.filter(webhook::community_id.eq(community_id))
+    } else {
+      query.filter(webhook::community_id.is_null())
+    };
+    query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Returns the enabled webhooks which are subscribed to the event. Site-wide webhooks receive
+  /// events from all communities. Webhooks are skipped if their creator is not an admin or mod
+  /// anymore.
+  pub async fn list_for_event(
+    pool: &mut DbPool<'_>,
+    event: WebhookEventType,
+    community_id: Option<CommunityId>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+
+    let creator_is_admin = local_user::table
+      .filter(local_user::person_id.eq(webhook::creator_id))
+      .filter(local_user::admin);
+    let creator_is_mod = community_actions::table
+      .filter(community_actions::person_id.eq(webhook::creator_id))
+      .filter(
+        community_actions::community_id
+          .nullable()
+          .eq(webhook::community_id),
+      )
+      .filter(community_actions::became_moderator_at.is_not_null());
+
+    let query = webhook::table
+      .filter(webhook::enabled)
+      .filter(webhook::events.contains(vec![event]))
+      .filter(exists(creator_is_admin).or(exists(creator_is_mod)))
+      .into_boxed();
+    let query = if let Some(community_id) = community_id {
+      query.filter(
+        webhook::community_id
+          .is_null()
+          .or(webhook::community_id.eq(community_id)),
+      )
+    } else {
+      query.filter(webhook::community_id.is_null())
+    };
+    query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl WebhookDelivery {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &WebhookDeliveryInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook_delivery::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, id: WebhookDeliveryId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    webhook_delivery::table
+      .find(id)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    id: WebhookDeliveryId,
+    form: &WebhookDeliveryUpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook_delivery::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// The delivery log of a webhook, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let query = webhook_delivery::table
+      .filter(webhook_delivery::webhook_id.eq(webhook_id))
+      .limit(limit)
+      .into_boxed();
+    let paginated_query = Self::paginate(query, &page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::published_at)
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Deliveries which are due for another attempt, together with their webhook.
+  pub async fn list_pending(
+    pool: &mut DbPool<'_>,
+    limit: i64,
+  ) -> LemmyResult<Vec<(Self, Webhook)>> {
+    let conn = &mut get_conn(pool).await?;
+    webhook_delivery::table
+      .inner_join(webhook::table)
+      .filter(webhook::enabled)
+      .filter(webhook_delivery::next_attempt_at.le(now().nullable()))
+      .order_by(webhook_delivery::next_attempt_at)
+      .limit(limit)
+      .load::<(Self, Webhook)>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl PaginationCursorConversion for WebhookDelivery {
+  type PaginatedType = WebhookDelivery;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    WebhookDelivery::read(pool, WebhookDeliveryId(cursor.id()?)).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    webhook::{
+      Webhook,
+      WebhookDelivery,
+      WebhookDeliveryInsertForm,
+      WebhookDeliveryUpdateForm,
+      WebhookInsertForm,
+    },
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_db_schema_file::enums::WebhookEventType;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_webhooks_for_event() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let admin = Person::create(pool, &PersonInsertForm::test_form(instance.id, "admin")).await?;
+    LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form_admin(admin.id),
+      vec![],
+    )
+    .await?;
+    let mod_ = Person::create(pool, &PersonInsertForm::test_form(instance.id, "mod")).await?;
+    LocalUser::create(pool, &LocalUserInsertForm::test_form(mod_.id), vec![]).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "webhooks".into(), "pubkey".into()),
+    )
+    .await?;
+    let other_community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "other".into(), "pubkey".into()),
+    )
+    .await?;
+    CommunityActions::join(pool, &CommunityModeratorForm::new(community.id, mod_.id)).await?;
+
+    let url: Url = "http://localhost:8080/hook".parse()?;
+    let site_hook = Webhook::create(
+      pool,
+      &WebhookInsertForm::new(
+        admin.id,
+        url.clone().into(),
+        "secret".into(),
+        vec![WebhookEventType::ReportCreated, WebhookEventType::ModAction],
+      ),
+    )
+    .await?;
+    let community_hook = Webhook::create(
+      pool,
+      &WebhookInsertForm {
+        community_id: Some(community.id),
+        ..WebhookInsertForm::new(
+          mod_.id,
+          url.clone().into(),
+          "secret".into(),
+          vec![WebhookEventType::ReportCreated],
+        )
+      },
+    )
+    .await?;
+    // Not a mod of this community, so it never gets any events
+    Webhook::create(
+      pool,
+      &WebhookInsertForm {
+        community_id: Some(other_community.id),
+        ..WebhookInsertForm::new(
+          mod_.id,
+          url.into(),
+          "secret".into(),
+          vec![WebhookEventType::ReportCreated],
+        )
+      },
+    )
+    .await?;
+
+    let ids = |hooks: Vec<Webhook>| hooks.into_iter().map(|h| h.id).collect::<Vec<_>>();
+    let report = WebhookEventType::ReportCreated;
+    assert_eq!(
+      vec![site_hook.id, community_hook.id],
+      ids(Webhook::list_for_event(pool, report, Some(community.id)).await?)
+    );
+    assert_eq!(
+      vec![site_hook.id],
+      ids(Webhook::list_for_event(pool, report, Some(other_community.id)).await?)
+    );
+    assert_eq!(
+      vec![site_hook.id],
+      ids(Webhook::list_for_event(pool, report, None).await?)
+    );
+    assert!(
+      Webhook::list_for_event(pool, WebhookEventType::PostCreated, Some(community.id))
+        .await?
+        .is_empty()
+    );
+    assert_eq!(vec![site_hook.id], ids(Webhook::list(pool, None).await?));
+
+    // Only deliveries which are due get retried
+    let due = WebhookDelivery::create(
+      pool,
+      &WebhookDeliveryInsertForm::new(
+        site_hook.id,
+        report,
+        "{}".into(),
+        Some(Utc::now() - Duration::minutes(1)),
+      ),
+    )
+    .await?;
+    WebhookDelivery::create(
+      pool,
+      &WebhookDeliveryInsertForm::new(
+        site_hook.id,
+        report,
+        "{}".into(),
+        Some(Utc::now() + Duration::minutes(1)),
+      ),
+    )
+    .await?;
+    let pending = WebhookDelivery::list_pending(pool, 10).await?;
+    assert_eq!(
+      vec![due.id],
+      pending.iter().map(|(d, _)| d.id).collect::<Vec<_>>()
+    );
+
+    let form = WebhookDeliveryUpdateForm {
+      attempts: Some(1),
+      status_code: Some(Some(200)),
+      delivered_at: Some(Some(Utc::now())),
+      next_attempt_at: Some(None),
+      ..Default::default()
+    };
+    WebhookDelivery::update(pool, due.id, &form).await?;
+    assert!(WebhookDelivery::list_pending(pool, 10).await?.is_empty());
+    assert_eq!(
+      2,
+      WebhookDelivery::list(pool, site_hook.id, None, None)
+        .await?
+        .items
+        .len()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -135,6 +135,22 @@ pub enum SearchType {
   MultiCommunities,
 }
 
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The sort order of search results.
+pub enum SearchSortType {
+  /// Best matches for the search term first. Posts, comments and communities are ranked with
+  /// full-text search, other types are sorted by `New`.
+  Relevance,
+  #[default]
+  New,
+  Old,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -261,6 +277,8 @@ pub type Person1AliasAllColumnsTuple = (
   AliasedField<aliases::Person1, person::post_score>,
   AliasedField<aliases::Person1, person::comment_count>,
   AliasedField<aliases::Person1, person::comment_score>,
+  AliasedField<aliases::Person1, person::also_known_as>,
+  AliasedField<aliases::Person1, person::moved_to>,
 );
 
 #[cfg(feature = "full")]
@@ -288,6 +306,8 @@ pub type Person2AliasAllColumnsTuple = (
   AliasedField<aliases::Person2, person::post_score>,
   AliasedField<aliases::Person2, person::comment_count>,
   AliasedField<aliases::Person2, person::comment_score>,
+  AliasedField<aliases::Person2, person::also_known_as>,
+  AliasedField<aliases::Person2, person::moved_to>,
 );
 
 #[cfg(feature = "full")]
@@ -54,7 +54,10 @@ impl ActivitySendTargets {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, QueryableByName, Selectable, Identifiable)
+)]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "full", diesel(table_name = sent_activity))]
 pub struct SentActivity {
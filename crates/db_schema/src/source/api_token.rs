@@ -0,0 +1,109 @@
+use crate::newtypes::{ApiTokenId, CommunityId, LocalUserId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::{AsExpression, FromSqlRow};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::api_token;
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay, skip_serializing_none};
+use std::{
+  fmt::{Display, Formatter},
+  str::FromStr,
+};
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A personal API token. Unlike login sessions it has a name, and only allows the actions covered
+/// by its scopes.
+pub struct ApiToken {
+  pub id: ApiTokenId,
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// SHA-256 hash of the token. The token itself is only returned once, on creation.
+  #[serde(skip)]
+  pub token_hash: String,
+  pub scopes: Vec<ApiTokenScope>,
+  pub expires_at: Option<DateTime<Utc>>,
+  pub last_used_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub token_hash: String,
+  pub scopes: Vec<ApiTokenScope>,
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenUpdateForm {
+  pub name: Option<String>,
+  pub scopes: Option<Vec<ApiTokenScope>>,
+  pub expires_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// An action which is allowed for an [ApiToken]. Serialized as `read`, `post`, `vote`,
+/// `moderate:community/<id>` or `admin`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, SerializeDisplay, DeserializeFromStr)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Text))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(type = "string", export))]
+pub enum ApiTokenScope {
+  /// Read-only API calls.
+  Read,
+  /// Create and edit posts, comments and private messages, and other actions of the account which
+  /// don't need any of the other scopes.
+  Post,
+  /// Vote on posts, comments and polls.
+  Vote,
+  /// Mod actions in the given community.
+  ModerateCommunity(CommunityId),
+  /// Admin actions.
+  Admin,
+}
+
+const MODERATE_COMMUNITY_PREFIX: &str = "moderate:community/";
+
+impl Display for ApiTokenScope {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ApiTokenScope::Read => write!(f, "read"),
+      ApiTokenScope::Post => write!(f, "post"),
+      ApiTokenScope::Vote => write!(f, "vote"),
+      ApiTokenScope::ModerateCommunity(id) => write!(f, "{MODERATE_COMMUNITY_PREFIX}{}", id.0),
+      ApiTokenScope::Admin => write!(f, "admin"),
+    }
+  }
+}
+
+impl FromStr for ApiTokenScope {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(match s {
+      "read" => ApiTokenScope::Read,
+      "post" => ApiTokenScope::Post,
+      "vote" => ApiTokenScope::Vote,
+      "admin" => ApiTokenScope::Admin,
+      _ => {
+        let community_id = s
+          .strip_prefix(MODERATE_COMMUNITY_PREFIX)
+          .and_then(|id| id.parse().ok())
+          .ok_or_else(|| format!("Invalid api token scope: {s}"))?;
+        ApiTokenScope::ModerateCommunity(CommunityId(community_id))
+      }
+    })
+  }
+}
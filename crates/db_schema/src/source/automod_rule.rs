@@ -0,0 +1,93 @@
+use crate::newtypes::{AutomodRuleId, CommunityId, CommunityTagId, LanguageId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::AutomodAction;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::automod_rule;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An automod rule of a community. It matches new posts for which all of the given conditions
+/// match, and then takes all of its actions.
+pub struct AutomodRule {
+  pub id: AutomodRuleId,
+  pub community_id: CommunityId,
+  /// Shown as reason in the modlog and in reports.
+  pub name: String,
+  pub enabled: bool,
+  /// Case insensitive regex for the post title.
+  pub title_regex: Option<String>,
+  /// Case insensitive regex for the post body.
+  pub body_regex: Option<String>,
+  /// Case insensitive regex for the post url.
+  pub url_regex: Option<String>,
+  /// Matches if the post url belongs to one of these domains, or their subdomains.
+  pub domains: Vec<String>,
+  /// Matches if the post has one of these languages.
+  pub language_ids: Vec<LanguageId>,
+  /// Matches if the post creator registered less than this many days ago.
+  pub max_account_age_days: Option<i32>,
+  /// Matches if the post score of the creator is at most this value.
+  pub max_person_post_score: Option<i32>,
+  pub actions: Vec<AutomodAction>,
+  /// Needed for [AutomodAction::ApplyTag].
+  pub community_tag_id: Option<CommunityTagId>,
+  /// Needed for [AutomodAction::Reply].
+  pub reply: Option<String>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleInsertForm {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub actions: Vec<AutomodAction>,
+  #[new(default)]
+  pub enabled: Option<bool>,
+  #[new(default)]
+  pub title_regex: Option<String>,
+  #[new(default)]
+  pub body_regex: Option<String>,
+  #[new(default)]
+  pub url_regex: Option<String>,
+  #[new(default)]
+  pub domains: Option<Vec<String>>,
+  #[new(default)]
+  pub language_ids: Option<Vec<LanguageId>>,
+  #[new(default)]
+  pub max_account_age_days: Option<i32>,
+  #[new(default)]
+  pub max_person_post_score: Option<i32>,
+  #[new(default)]
+  pub community_tag_id: Option<CommunityTagId>,
+  #[new(default)]
+  pub reply: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleUpdateForm {
+  pub name: Option<String>,
+  pub enabled: Option<bool>,
+  pub title_regex: Option<Option<String>>,
+  pub body_regex: Option<Option<String>>,
+  pub url_regex: Option<Option<String>>,
+  pub domains: Option<Vec<String>>,
+  pub language_ids: Option<Vec<LanguageId>>,
+  pub max_account_age_days: Option<Option<i32>>,
+  pub max_person_post_score: Option<Option<i32>>,
+  pub actions: Option<Vec<AutomodAction>>,
+  pub community_tag_id: Option<Option<CommunityTagId>>,
+  pub reply: Option<Option<String>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
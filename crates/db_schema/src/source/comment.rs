@@ -70,6 +70,8 @@ pub struct Comment {
   /// This field is a dupe of post.community_id, but necessary for join performance.
   #[serde(skip)]
   pub community_id: CommunityId,
+  #[serde(skip)]
+  pub best_rank: f32,
 }
 
 #[derive(Debug, Clone, derive_new::new, Serialize, Deserialize)]
@@ -0,0 +1,48 @@
+use crate::newtypes::{CommentDraftId, CommentId, LanguageId, PostId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::comment_draft;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_draft))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An unfinished comment, only visible to its creator.
+pub struct CommentDraft {
+  pub id: CommentDraftId,
+  pub creator_id: PersonId,
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+  pub content: String,
+  pub language_id: Option<LanguageId>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_draft))]
+pub struct CommentDraftInsertForm {
+  pub creator_id: PersonId,
+  pub post_id: PostId,
+  pub content: String,
+  #[new(default)]
+  pub parent_id: Option<CommentId>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_draft))]
+pub struct CommentDraftUpdateForm {
+  pub content: Option<String>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -1,4 +1,4 @@
-use crate::newtypes::{CommentId, CommentReportId};
+use crate::newtypes::{CommentId, CommentReportId, RuleId};
 use chrono::{DateTime, Utc};
 use lemmy_db_schema_file::PersonId;
 #[cfg(feature = "full")]
@@ -30,6 +30,8 @@ pub struct CommentReport {
   pub updated_at: Option<DateTime<Utc>>,
   pub violates_instance_rules: bool,
   pub conclusion: Option<String>,
+  /// The rule which the reported item violates, if the reporter picked one.
+  pub rule_id: Option<RuleId>,
 }
 
 #[derive(Clone)]
@@ -41,6 +43,7 @@ pub struct CommentReportForm {
   pub original_comment_text: String,
   pub reason: String,
   pub violates_instance_rules: bool,
+  pub rule_id: Option<RuleId>,
 }
 
 #[derive(Clone, Default)]
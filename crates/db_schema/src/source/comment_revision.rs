@@ -0,0 +1,29 @@
+use crate::newtypes::{CommentId, CommentRevisionId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::comment_revision;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_revision))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A previous version of a comment, saved when the comment was edited.
+pub struct CommentRevision {
+  pub id: CommentRevisionId,
+  pub comment_id: CommentId,
+  pub content: String,
+  /// When this version was published, either on creation or by an earlier edit.
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_revision))]
+pub struct CommentRevisionInsertForm {
+  pub comment_id: CommentId,
+  pub content: String,
+  pub published_at: DateTime<Utc>,
+}
@@ -1,4 +1,7 @@
-use crate::{newtypes::CommunityId, source::placeholder_apub_url};
+use crate::{
+  newtypes::{CommunityFlairId, CommunityId},
+  source::placeholder_apub_url,
+};
 use chrono::{DateTime, Utc};
 use lemmy_db_schema_file::{
   InstanceId,
@@ -94,6 +97,14 @@ pub struct Community {
   pub report_count: i16,
   pub unresolved_report_count: i16,
   pub local_removed: bool,
+  /// Whether new posts need to be approved by a mod before they are published.
+  pub posts_require_approval: bool,
+  /// If set, approval is only required for accounts which registered less than this many days ago
+  /// (or which match the post score limit).
+  pub post_approval_max_account_age_days: Option<i32>,
+  /// If set, approval is only required for accounts with at most this post score (or which match
+  /// the account age limit).
+  pub post_approval_max_person_post_score: Option<i32>,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -145,6 +156,12 @@ pub struct CommunityInsertForm {
   pub summary: Option<String>,
   #[new(default)]
   pub local_removed: Option<bool>,
+  #[new(default)]
+  pub posts_require_approval: Option<bool>,
+  #[new(default)]
+  pub post_approval_max_account_age_days: Option<i32>,
+  #[new(default)]
+  pub post_approval_max_person_post_score: Option<i32>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -173,6 +190,9 @@ pub struct CommunityUpdateForm {
   pub visibility: Option<CommunityVisibility>,
   pub summary: Option<Option<String>>,
   pub local_removed: Option<bool>,
+  pub posts_require_approval: Option<bool>,
+  pub post_approval_max_account_age_days: Option<Option<i32>>,
+  pub post_approval_max_person_post_score: Option<Option<i32>>,
 }
 
 #[skip_serializing_none]
@@ -218,6 +238,8 @@ pub struct CommunityActions {
   /// immediately.
   #[serde(skip)]
   pub follow_activity_id: Option<DbUrl>,
+  /// The user flair which this person has in the community.
+  pub flair_id: Option<CommunityFlairId>,
 }
 
 #[derive(Clone, derive_new::new)]
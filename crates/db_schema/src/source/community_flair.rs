@@ -0,0 +1,69 @@
+use crate::newtypes::{CommunityFlairId, CommunityId, CustomEmojiId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::{AsExpression, FromSqlRow, sql_types::Nullable};
+use lemmy_db_schema_file::enums::TagColor;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_flair;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A user flair that is created by community moderators. Users can assign it to themselves if it
+/// is self assignable, otherwise only mods can assign it.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_flair))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommunityFlair {
+  pub id: CommunityFlairId,
+  pub ap_id: DbUrl,
+  /// The community that this flair belongs to
+  pub community_id: CommunityId,
+  pub text: String,
+  pub color: TagColor,
+  /// An emoji which is shown in front of the text. Only available for local communities.
+  pub custom_emoji_id: Option<CustomEmojiId>,
+  pub self_assignable: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+  pub deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_flair))]
+pub struct CommunityFlairInsertForm {
+  pub ap_id: DbUrl,
+  pub community_id: CommunityId,
+  pub text: String,
+  pub color: Option<TagColor>,
+  pub custom_emoji_id: Option<CustomEmojiId>,
+  pub self_assignable: Option<bool>,
+  pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_flair))]
+pub struct CommunityFlairUpdateForm {
+  pub text: Option<String>,
+  pub color: Option<TagColor>,
+  pub custom_emoji_id: Option<Option<CustomEmojiId>>,
+  pub self_assignable: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+  pub deleted: Option<bool>,
+}
+
+/// The flair of a post or comment creator. We wrap this in a struct so we can implement
+/// FromSqlRow<Json> for it.
+#[derive(Clone, serde::Serialize, serde::Deserialize, Debug, PartialEq, Default)]
+#[serde(transparent)]
+#[cfg_attr(feature = "full", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "full", diesel(sql_type = Nullable<diesel::sql_types::Json>))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommunityFlairView(pub Option<CommunityFlair>);
@@ -0,0 +1,40 @@
+use crate::newtypes::{ContentImportId, LocalUserId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{content_import, content_import_failure};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// An import of posts and comments from the data archive of another account. The items are
+/// resolved in the background and saved for the importing user.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = content_import))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ContentImport {
+  pub id: ContentImportId,
+  pub local_user_id: LocalUserId,
+  pub published_at: DateTime<Utc>,
+  /// Set once all items were processed.
+  pub finished_at: Option<DateTime<Utc>>,
+  pub total_items: i32,
+  /// The number of items which were already processed, including failed ones.
+  pub processed_items: i32,
+}
+
+/// An item of a content import which couldn't be resolved.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = content_import_failure))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ContentImportFailure {
+  pub id: i32,
+  pub content_import_id: ContentImportId,
+  pub ap_id: String,
+  pub reason: String,
+}
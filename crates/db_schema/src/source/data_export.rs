@@ -0,0 +1,24 @@
+use crate::newtypes::{DataExportId, LocalUserId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::data_export;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A ZIP archive with all data of a user, which is built in the background. The path of the
+/// archive on disk is not included here, as it is only needed for download.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = data_export))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct DataExport {
+  pub id: DataExportId,
+  pub local_user_id: LocalUserId,
+  pub published_at: DateTime<Utc>,
+  /// Set once the archive is ready for download.
+  pub finished_at: Option<DateTime<Utc>>,
+  pub failed: bool,
+}
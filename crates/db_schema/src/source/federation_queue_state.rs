@@ -25,4 +25,10 @@ pub struct FederationQueueState {
   pub fail_count: i32,
   /// timestamp of the last retry attempt (when the last failing activity was resent)
   pub last_retry_at: Option<DateTime<Utc>>,
+  /// An admin requested to reset the retry backoff, it is applied when the worker restarts
+  pub reset_requested: bool,
+  /// An admin requested to skip all activities up to and including this id
+  pub skip_to_id: Option<ActivityId>,
+  /// Marked dead by an admin, no activities are sent until it is marked alive again
+  pub marked_dead: bool,
 }
@@ -36,6 +36,26 @@ pub struct LocalSiteRateLimit {
   pub updated_at: Option<DateTime<Utc>>,
   pub import_user_settings_max_requests: i32,
   pub import_user_settings_interval_seconds: i32,
+  /// Accounts younger than this are rate limited with `new_account_limit_percent`.
+  pub new_account_max_age_days: i32,
+  /// The percentage of the above limits which is allowed for new accounts.
+  pub new_account_limit_percent: i32,
+  /// The percentage of the above limits which is allowed for accounts that are not new.
+  pub established_account_limit_percent: i32,
+  /// The percentage of the above limits which is allowed for bot accounts.
+  pub bot_account_limit_percent: i32,
+  /// The percentage of the above limits which is allowed for admins and community moderators.
+  pub moderator_limit_percent: i32,
+  pub vote_max_requests: i32,
+  pub vote_interval_seconds: i32,
+  pub follow_max_requests: i32,
+  pub follow_interval_seconds: i32,
+  pub report_max_requests: i32,
+  pub report_interval_seconds: i32,
+  pub private_message_max_requests: i32,
+  pub private_message_interval_seconds: i32,
+  pub resolve_object_max_requests: i32,
+  pub resolve_object_interval_seconds: i32,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -71,6 +91,36 @@ pub struct LocalSiteRateLimitInsertForm {
   pub import_user_settings_max_requests: Option<i32>,
   #[new(default)]
   pub import_user_settings_interval_seconds: Option<i32>,
+  #[new(default)]
+  pub new_account_max_age_days: Option<i32>,
+  #[new(default)]
+  pub new_account_limit_percent: Option<i32>,
+  #[new(default)]
+  pub established_account_limit_percent: Option<i32>,
+  #[new(default)]
+  pub bot_account_limit_percent: Option<i32>,
+  #[new(default)]
+  pub moderator_limit_percent: Option<i32>,
+  #[new(default)]
+  pub vote_max_requests: Option<i32>,
+  #[new(default)]
+  pub vote_interval_seconds: Option<i32>,
+  #[new(default)]
+  pub follow_max_requests: Option<i32>,
+  #[new(default)]
+  pub follow_interval_seconds: Option<i32>,
+  #[new(default)]
+  pub report_max_requests: Option<i32>,
+  #[new(default)]
+  pub report_interval_seconds: Option<i32>,
+  #[new(default)]
+  pub private_message_max_requests: Option<i32>,
+  #[new(default)]
+  pub private_message_interval_seconds: Option<i32>,
+  #[new(default)]
+  pub resolve_object_max_requests: Option<i32>,
+  #[new(default)]
+  pub resolve_object_interval_seconds: Option<i32>,
 }
 
 #[derive(Clone, Default)]
@@ -91,5 +141,20 @@ pub struct LocalSiteRateLimitUpdateForm {
   pub search_interval_seconds: Option<i32>,
   pub import_user_settings_max_requests: Option<i32>,
   pub import_user_settings_interval_seconds: Option<i32>,
+  pub new_account_max_age_days: Option<i32>,
+  pub new_account_limit_percent: Option<i32>,
+  pub established_account_limit_percent: Option<i32>,
+  pub bot_account_limit_percent: Option<i32>,
+  pub moderator_limit_percent: Option<i32>,
+  pub vote_max_requests: Option<i32>,
+  pub vote_interval_seconds: Option<i32>,
+  pub follow_max_requests: Option<i32>,
+  pub follow_interval_seconds: Option<i32>,
+  pub report_max_requests: Option<i32>,
+  pub report_interval_seconds: Option<i32>,
+  pub private_message_max_requests: Option<i32>,
+  pub private_message_interval_seconds: Option<i32>,
+  pub resolve_object_max_requests: Option<i32>,
+  pub resolve_object_interval_seconds: Option<i32>,
   pub updated_at: Option<Option<DateTime<Utc>>>,
 }
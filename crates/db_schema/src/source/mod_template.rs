@@ -0,0 +1,47 @@
+use crate::newtypes::{CommunityId, ModTemplateId, RuleId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::mod_template;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A saved removal reason or canned reply of a community. Moderators can select it when removing,
+/// locking or warning, instead of typing the reason each time. Only visible to moderators.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_template))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ModTemplate {
+  pub id: ModTemplateId,
+  pub community_id: CommunityId,
+  pub title: String,
+  /// Markdown text, which is used as reason for the mod action.
+  pub body: String,
+  /// The rule which the template refers to.
+  pub rule_id: Option<RuleId>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_template))]
+pub struct ModTemplateInsertForm {
+  pub community_id: CommunityId,
+  pub title: String,
+  pub body: String,
+  pub rule_id: Option<RuleId>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_template))]
+pub struct ModTemplateUpdateForm {
+  pub title: Option<String>,
+  pub body: Option<String>,
+  pub rule_id: Option<Option<RuleId>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
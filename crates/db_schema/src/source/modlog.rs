@@ -1,4 +1,4 @@
-use crate::newtypes::{CommentId, CommunityId, ModlogId, PostId};
+use crate::newtypes::{CommentId, CommunityId, ModTemplateId, ModlogId, PostId};
 use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
 use i_love_jesus::CursorKeysModule;
@@ -38,6 +38,8 @@ pub struct Modlog {
   pub published_at: DateTime<Utc>,
   pub bulk_action_parent_id: Option<ModlogId>,
   pub child_count: i32,
+  /// The saved removal reason or canned reply which was selected for this action.
+  pub mod_template_id: Option<ModTemplateId>,
 }
 
 #[derive(derive_new::new)]
@@ -63,4 +65,6 @@ pub struct ModlogInsertForm<'a> {
   pub(crate) target_instance_id: Option<InstanceId>,
   #[new(default)]
   pub(crate) expires_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub mod_template_id: Option<ModTemplateId>,
 }
@@ -59,6 +59,10 @@ pub struct Person {
   pub comment_count: i32,
   #[serde(skip)]
   pub comment_score: i32,
+  /// Other accounts of the same person, which are allowed to move to this account.
+  pub also_known_as: Vec<DbUrl>,
+  /// If set, the account was moved to this new account.
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -96,6 +100,10 @@ pub struct PersonInsertForm {
   pub matrix_user_id: Option<String>,
   #[new(default)]
   pub bot_account: Option<bool>,
+  #[new(default)]
+  pub also_known_as: Option<Vec<DbUrl>>,
+  #[new(default)]
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Clone, Default)]
@@ -116,6 +124,8 @@ pub struct PersonUpdateForm {
   pub inbox_url: Option<DbUrl>,
   pub matrix_user_id: Option<Option<String>>,
   pub bot_account: Option<bool>,
+  pub also_known_as: Option<Vec<DbUrl>>,
+  pub moved_to: Option<Option<DbUrl>>,
 }
 
 #[skip_serializing_none]
@@ -88,6 +88,9 @@ pub struct Post {
   pub federation_pending: bool,
   pub embed_video_width: Option<i32>,
   pub embed_video_height: Option<i32>,
+  /// The post is waiting for approval by a community mod. Until then it is only visible to the
+  /// creator and mods, and not federated.
+  pub approval_pending: bool,
 }
 
 // TODO: FromBytes, ToBytes are only needed to develop wasm plugin, could be behind feature flag
@@ -144,6 +147,8 @@ pub struct PostInsertForm {
   pub scheduled_publish_time_at: Option<DateTime<Utc>>,
   #[new(default)]
   pub federation_pending: Option<bool>,
+  #[new(default)]
+  pub approval_pending: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -174,6 +179,7 @@ pub struct PostUpdateForm {
   pub alt_text: Option<Option<String>>,
   pub scheduled_publish_time_at: Option<Option<DateTime<Utc>>>,
   pub federation_pending: Option<bool>,
+  pub approval_pending: Option<bool>,
 }
 
 #[skip_serializing_none]
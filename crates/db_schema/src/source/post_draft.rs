@@ -0,0 +1,63 @@
+use crate::newtypes::{CommunityId, LanguageId, PostDraftId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::post_draft;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_draft))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An unfinished post, only visible to its creator.
+pub struct PostDraft {
+  pub id: PostDraftId,
+  pub creator_id: PersonId,
+  pub community_id: CommunityId,
+  pub name: String,
+  /// The url is only validated once the draft gets published.
+  pub url: Option<String>,
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_draft))]
+pub struct PostDraftInsertForm {
+  pub creator_id: PersonId,
+  pub community_id: CommunityId,
+  pub name: String,
+  #[new(default)]
+  pub url: Option<String>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub alt_text: Option<String>,
+  #[new(default)]
+  pub nsfw: Option<bool>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_draft))]
+pub struct PostDraftUpdateForm {
+  pub community_id: Option<CommunityId>,
+  pub name: Option<String>,
+  pub url: Option<Option<String>>,
+  pub body: Option<Option<String>>,
+  pub alt_text: Option<Option<String>>,
+  pub nsfw: Option<Option<bool>>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,108 @@
+use crate::newtypes::{PostId, PostPollOptionId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{post_poll, post_poll_option, post_poll_vote};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The settings of a post which is a poll.
+pub struct PostPoll {
+  pub post_id: PostId,
+  /// Whether voters can select more than one option.
+  pub multiple_choice: bool,
+  /// After this time no more votes are accepted.
+  pub end_time_at: Option<DateTime<Utc>>,
+  /// Only show the vote counts once the poll is closed.
+  pub hide_results_until_closed: bool,
+  /// When the poll was closed, either manually or because its end time has passed.
+  pub closed_at: Option<DateTime<Utc>>,
+  /// The number of people who voted.
+  pub voter_count: i32,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll))]
+pub struct PostPollInsertForm {
+  pub post_id: PostId,
+  #[new(default)]
+  pub multiple_choice: Option<bool>,
+  #[new(default)]
+  pub end_time_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub hide_results_until_closed: Option<bool>,
+  #[new(default)]
+  pub closed_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub voter_count: Option<i32>,
+  #[new(default)]
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll))]
+pub struct PostPollUpdateForm {
+  pub end_time_at: Option<Option<DateTime<Utc>>>,
+  pub closed_at: Option<Option<DateTime<Utc>>>,
+  pub voter_count: Option<i32>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_option))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An option which can be voted for in a poll.
+pub struct PostPollOption {
+  pub id: PostPollOptionId,
+  pub post_id: PostId,
+  pub name: String,
+  /// The number of votes. Zero if the results are hidden until the poll is closed.
+  pub vote_count: i32,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_option))]
+pub struct PostPollOptionInsertForm {
+  pub post_id: PostId,
+  pub name: String,
+  pub vote_count: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_vote))]
+#[cfg_attr(feature = "full", diesel(primary_key(person_id, post_poll_option_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A vote for a poll option.
+pub struct PostPollVote {
+  pub post_poll_option_id: PostPollOptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_poll_vote))]
+pub struct PostPollVoteForm {
+  pub post_poll_option_id: PostPollOptionId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+}
@@ -0,0 +1,38 @@
+use crate::newtypes::{PostId, PostRevisionId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::post_revision;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_revision))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A previous version of a post, saved when the post was edited.
+pub struct PostRevision {
+  pub id: PostRevisionId,
+  pub post_id: PostId,
+  pub name: String,
+  pub body: Option<String>,
+  pub url: Option<DbUrl>,
+  pub nsfw: bool,
+  /// When this version was published, either on creation or by an earlier edit.
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_revision))]
+pub struct PostRevisionInsertForm {
+  pub post_id: PostId,
+  pub name: String,
+  pub body: Option<String>,
+  pub url: Option<DbUrl>,
+  pub nsfw: bool,
+  pub published_at: DateTime<Utc>,
+}
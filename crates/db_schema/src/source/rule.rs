@@ -0,0 +1,53 @@
+use crate::newtypes::{CommunityId, RuleId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::rule;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A structured rule, which belongs either to a community or to the local site. Reports can
+/// reference the rule which was violated.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = rule))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct Rule {
+  pub id: RuleId,
+  pub ap_id: DbUrl,
+  /// The community that this rule belongs to. If empty, it is a rule of the local site.
+  pub community_id: Option<CommunityId>,
+  pub title: String,
+  pub description: Option<String>,
+  /// Rules are listed in ascending order of this value.
+  pub position: i32,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+  pub deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = rule))]
+pub struct RuleInsertForm {
+  pub ap_id: DbUrl,
+  pub community_id: Option<CommunityId>,
+  pub title: String,
+  pub description: Option<String>,
+  pub position: Option<i32>,
+  pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = rule))]
+pub struct RuleUpdateForm {
+  pub title: Option<String>,
+  pub description: Option<Option<String>>,
+  pub position: Option<i32>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+  pub deleted: Option<bool>,
+}
@@ -0,0 +1,29 @@
+use crate::newtypes::LocalUserId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{idempotency_key, rate_limit_bucket};
+
+/// A rate limit bucket which is shared between multiple Lemmy processes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = rate_limit_bucket))]
+#[cfg_attr(feature = "full", diesel(primary_key(key)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct RateLimitBucket {
+  pub key: String,
+  /// Number of requests since the bucket was started
+  pub count: i32,
+  pub expires_at: DateTime<Utc>,
+}
+
+/// An idempotency key which is shared between multiple Lemmy processes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = idempotency_key))]
+#[cfg_attr(feature = "full", diesel(primary_key(local_user_id, key)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct IdempotencyKey {
+  pub local_user_id: LocalUserId,
+  pub key: String,
+  pub published_at: DateTime<Utc>,
+}
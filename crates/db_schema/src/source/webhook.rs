@@ -0,0 +1,110 @@
+use crate::newtypes::{CommunityId, WebhookDeliveryId, WebhookId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use i_love_jesus::CursorKeysModule;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{webhook, webhook_delivery};
+use lemmy_db_schema_file::{PersonId, enums::WebhookEventType};
+use lemmy_diesel_utils::{dburl::DbUrl, sensitive::SensitiveString};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An outgoing webhook. Events are sent as signed HTTP POST requests to the given url.
+pub struct Webhook {
+  pub id: WebhookId,
+  pub creator_id: PersonId,
+  /// Only events in this community are sent. If empty, events from the whole site are sent, which
+  /// is only allowed for admins.
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  pub url: DbUrl,
+  /// Used to sign the request body with HMAC-SHA256.
+  #[serde(skip)]
+  pub secret: SensitiveString,
+  pub events: Vec<WebhookEventType>,
+  pub enabled: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookInsertForm {
+  pub creator_id: PersonId,
+  pub url: DbUrl,
+  pub secret: String,
+  pub events: Vec<WebhookEventType>,
+  #[new(default)]
+  pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookUpdateForm {
+  pub url: Option<DbUrl>,
+  pub secret: Option<String>,
+  pub events: Option<Vec<WebhookEventType>>,
+  pub enabled: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = webhook_delivery_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A single event sent to a webhook, including the result of the latest attempt.
+pub struct WebhookDelivery {
+  pub id: WebhookDeliveryId,
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  /// The JSON request body.
+  pub payload: String,
+  pub attempts: i32,
+  /// The HTTP status code of the latest attempt.
+  pub status_code: Option<i32>,
+  /// The error of the latest attempt, if it failed.
+  pub error: Option<String>,
+  pub delivered_at: Option<DateTime<Utc>>,
+  /// When the delivery will be retried. Empty if it succeeded or there are no retries left.
+  pub next_attempt_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryInsertForm {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  pub payload: String,
+  pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryUpdateForm {
+  pub attempts: Option<i32>,
+  pub status_code: Option<Option<i32>>,
+  pub error: Option<Option<String>>,
+  pub delivered_at: Option<Option<DateTime<Utc>>>,
+  pub next_attempt_at: Option<Option<DateTime<Utc>>>,
+}
@@ -28,6 +28,7 @@ use lemmy_db_schema_file::{
     comment,
     community,
     community_actions,
+    community_flair,
     community_tag,
     instance_actions,
     local_user,
@@ -245,6 +246,7 @@ pub fn comment_select_remove_deletes() -> _ {
     comment::federation_pending,
     comment::locked,
     comment::community_id,
+    comment::best_rank,
   )
 }
 
@@ -303,6 +305,7 @@ pub fn post_select_remove_deletes() -> _ {
     post::federation_pending,
     post::embed_video_width,
     post::embed_video_height,
+    post::approval_pending,
   )
 }
 
@@ -319,6 +322,22 @@ pub fn post_community_tags_fragment() -> _ {
     .single_value()
 }
 
+#[diesel::dsl::auto_type]
+/// Gets the flair of the creator in the community of a post or comment.
+pub fn creator_community_flair_fragment() -> _ {
+  let sel: SqlLiteral<Json> =
+    diesel::dsl::sql::<diesel::sql_types::Json>("row_to_json(community_flair.*)");
+  community_flair::table
+    .select(sel)
+    .filter(
+      community_flair::id
+        .nullable()
+        .eq(creator_community_actions.field(community_actions::flair_id)),
+    )
+    .filter(community_flair::deleted.eq(false))
+    .single_value()
+}
+
 #[diesel::dsl::auto_type]
 /// Gets the tags available within a specific community
 pub fn community_tags_fragment() -> _ {
@@ -45,6 +45,7 @@ pub enum CommentSortType {
   New,
   Old,
   Controversial,
+  Best,
 }
 
 #[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -70,6 +71,9 @@ pub enum ListingType {
   ModeratorView,
   /// Communities which are recommended by local instance admins
   Suggested,
+  /// Posts from subscribed and related communities, ranked by the user's interests. For other
+  /// listings this is the same as `Subscribed`.
+  ForYou,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -371,4 +375,76 @@ pub enum ModlogKind {
   ModLockComment,
   ModWarnComment,
   ModWarnPost,
+  ModApprovePost,
+  ModRejectPost,
+  AdminResetFederationQueue,
+  AdminSkipFederationActivity,
+  AdminMarkInstanceDead,
+  ModAutomodPost,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WebhookEventTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The events which can trigger an outgoing webhook.
+pub enum WebhookEventType {
+  #[default]
+  PostCreated,
+  ReportCreated,
+  /// Only available for site-wide webhooks.
+  RegistrationApplicationCreated,
+  ModAction,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AutomodActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// What an automod rule does with a matching post.
+pub enum AutomodAction {
+  #[default]
+  Remove,
+  Lock,
+  /// Report the post to the community mods.
+  Report,
+  /// Put the post into the approval queue of the community.
+  HoldForApproval,
+  /// Add the community tag of the rule to the post.
+  ApplyTag,
+  /// Reply with the text of the rule, as distinguished comment.
+  Reply,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailDigestFrequencyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// How often a user receives an email digest with top posts, unread notifications and mod queue
+/// counts.
+pub enum EmailDigestFrequency {
+  #[default]
+  Never,
+  Daily,
+  Weekly,
 }
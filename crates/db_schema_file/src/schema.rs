@@ -5,6 +5,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "actor_type_enum"))]
   pub struct ActorTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "automod_action_enum"))]
+  pub struct AutomodActionEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "comment_sort_type_enum"))]
   pub struct CommentSortTypeEnum;
@@ -21,6 +25,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "community_visibility"))]
   pub struct CommunityVisibility;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "email_digest_frequency_enum"))]
+  pub struct EmailDigestFrequencyEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "federation_mode_enum"))]
   pub struct FederationModeEnum;
@@ -68,6 +76,47 @@ pub mod sql_types {
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "vote_show_enum"))]
   pub struct VoteShowEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "webhook_event_type_enum"))]
+  pub struct WebhookEventTypeEnum;
+}
+
+diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AutomodActionEnum;
+
+    automod_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        name -> Text,
+        enabled -> Bool,
+        title_regex -> Nullable<Text>,
+        body_regex -> Nullable<Text>,
+        url_regex -> Nullable<Text>,
+        domains -> Array<Text>,
+        language_ids -> Array<Int4>,
+        max_account_age_days -> Nullable<Int4>,
+        max_person_post_score -> Nullable<Int4>,
+        actions -> Array<AutomodActionEnum>,
+        community_tag_id -> Nullable<Int4>,
+        reply -> Nullable<Text>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
 }
 
 diesel::table! {
@@ -100,6 +149,7 @@ diesel::table! {
         federation_pending -> Bool,
         locked -> Bool,
         community_id -> Int4,
+        best_rank -> Float4,
     }
 }
 
@@ -113,6 +163,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_draft (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        post_id -> Int4,
+        parent_id -> Nullable<Int4>,
+        content -> Text,
+        language_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     comment_report (id) {
         id -> Int4,
@@ -126,6 +189,16 @@ diesel::table! {
         updated_at -> Nullable<Timestamptz>,
         violates_instance_rules -> Bool,
         conclusion -> Nullable<Text>,
+        rule_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    comment_revision (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        content -> Text,
+        published_at -> Timestamptz,
     }
 }
 
@@ -180,6 +253,9 @@ diesel::table! {
         report_count -> Int2,
         unresolved_report_count -> Int2,
         local_removed -> Bool,
+        posts_require_approval -> Bool,
+        post_approval_max_account_age_days -> Nullable<Int4>,
+        post_approval_max_person_post_score -> Nullable<Int4>,
     }
 }
 
@@ -200,6 +276,7 @@ diesel::table! {
         follow_approver_id -> Nullable<Int4>,
         notifications -> Nullable<CommunityNotificationsModeEnum>,
         follow_activity_id -> Nullable<Text>,
+        flair_id -> Nullable<Int4>,
     }
 }
 
@@ -211,6 +288,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::TagColorEnum;
+
+    community_flair (id) {
+        id -> Int4,
+        ap_id -> Text,
+        community_id -> Int4,
+        text -> Text,
+        color -> TagColorEnum,
+        custom_emoji_id -> Nullable<Int4>,
+        self_assignable -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     community_language (community_id, language_id) {
         community_id -> Int4,
@@ -259,6 +354,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    content_import (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        published_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+        total_items -> Int4,
+        processed_items -> Int4,
+    }
+}
+
+diesel::table! {
+    content_import_failure (id) {
+        id -> Int4,
+        content_import_id -> Int4,
+        ap_id -> Text,
+        reason -> Text,
+    }
+}
+
 diesel::table! {
     custom_emoji (id) {
         id -> Int4,
@@ -280,6 +395,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    data_export (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        published_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+        failed -> Bool,
+        archive_path -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     email_verification (id) {
         id -> Int4,
@@ -314,6 +440,17 @@ diesel::table! {
         fail_count -> Int4,
         last_retry_at -> Nullable<Timestamptz>,
         last_successful_published_time_at -> Nullable<Timestamptz>,
+        reset_requested -> Bool,
+        skip_to_id -> Nullable<Int8>,
+        marked_dead -> Bool,
+    }
+}
+
+diesel::table! {
+    idempotency_key (local_user_id, key) {
+        local_user_id -> Int4,
+        key -> Text,
+        published_at -> Timestamptz,
     }
 }
 
@@ -454,6 +591,21 @@ diesel::table! {
         updated_at -> Nullable<Timestamptz>,
         import_user_settings_max_requests -> Int4,
         import_user_settings_interval_seconds -> Int4,
+        new_account_max_age_days -> Int4,
+        new_account_limit_percent -> Int4,
+        established_account_limit_percent -> Int4,
+        bot_account_limit_percent -> Int4,
+        moderator_limit_percent -> Int4,
+        vote_max_requests -> Int4,
+        vote_interval_seconds -> Int4,
+        follow_max_requests -> Int4,
+        follow_interval_seconds -> Int4,
+        report_max_requests -> Int4,
+        report_interval_seconds -> Int4,
+        private_message_max_requests -> Int4,
+        private_message_interval_seconds -> Int4,
+        resolve_object_max_requests -> Int4,
+        resolve_object_interval_seconds -> Int4,
     }
 }
 
@@ -473,6 +625,7 @@ diesel::table! {
     use super::sql_types::PostListingModeEnum;
     use super::sql_types::CommentSortTypeEnum;
     use super::sql_types::VoteShowEnum;
+    use super::sql_types::EmailDigestFrequencyEnum;
 
     local_user (id) {
         id -> Int4,
@@ -514,6 +667,8 @@ diesel::table! {
         default_items_per_page -> Int4,
         invited_by_local_user_id -> Nullable<Int4>,
         show_media -> Bool,
+        email_digest -> EmailDigestFrequencyEnum,
+        last_email_digest_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -554,6 +709,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mod_template (id) {
+        id -> Int4,
+        community_id -> Int4,
+        title -> Text,
+        body -> Text,
+        rule_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::ModlogKind;
@@ -573,6 +740,7 @@ diesel::table! {
         published_at -> Timestamptz,
         bulk_action_parent_id -> Nullable<Int4>,
         child_count -> Int4,
+        mod_template_id -> Nullable<Int4>,
     }
 }
 
@@ -710,6 +878,8 @@ diesel::table! {
         post_score -> Int4,
         comment_count -> Int4,
         comment_score -> Int4,
+        also_known_as -> Array<Text>,
+        moved_to -> Nullable<Text>,
     }
 }
 
@@ -808,6 +978,7 @@ diesel::table! {
         federation_pending -> Bool,
         embed_video_width -> Nullable<Int4>,
         embed_video_height -> Nullable<Int4>,
+        approval_pending -> Bool,
     }
 }
 
@@ -837,6 +1008,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_draft (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        community_id -> Int4,
+        name -> Text,
+        url -> Nullable<Text>,
+        body -> Nullable<Text>,
+        alt_text -> Nullable<Text>,
+        nsfw -> Nullable<Bool>,
+        language_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    post_poll (post_id) {
+        post_id -> Int4,
+        multiple_choice -> Bool,
+        end_time_at -> Nullable<Timestamptz>,
+        hide_results_until_closed -> Bool,
+        closed_at -> Nullable<Timestamptz>,
+        voter_count -> Int4,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    post_poll_option (id) {
+        id -> Int4,
+        post_id -> Int4,
+        #[max_length = 200]
+        name -> Varchar,
+        vote_count -> Int4,
+    }
+}
+
+diesel::table! {
+    post_poll_vote (person_id, post_poll_option_id) {
+        post_poll_option_id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post_report (id) {
         id -> Int4,
@@ -853,6 +1072,19 @@ diesel::table! {
         updated_at -> Nullable<Timestamptz>,
         violates_instance_rules -> Bool,
         conclusion -> Nullable<Text>,
+        rule_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    post_revision (id) {
+        id -> Int4,
+        post_id -> Int4,
+        name -> Text,
+        body -> Nullable<Text>,
+        url -> Nullable<Text>,
+        nsfw -> Bool,
+        published_at -> Timestamptz,
     }
 }
 
@@ -888,6 +1120,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    rate_limit_bucket (key) {
+        key -> Text,
+        count -> Int4,
+        expires_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     received_activity (ap_id) {
         ap_id -> Text,
@@ -933,6 +1173,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    rule (id) {
+        id -> Int4,
+        ap_id -> Text,
+        community_id -> Nullable<Int4>,
+        title -> Text,
+        description -> Nullable<Text>,
+        position -> Int4,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     secret (id) {
         id -> Int4,
@@ -998,20 +1252,70 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        community_id -> Nullable<Int4>,
+        url -> Text,
+        secret -> Text,
+        events -> Array<WebhookEventTypeEnum>,
+        enabled -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook_delivery (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> WebhookEventTypeEnum,
+        payload -> Text,
+        attempts -> Int4,
+        status_code -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamptz>,
+        next_attempt_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(api_token -> local_user (local_user_id));
+diesel::joinable!(automod_rule -> community (community_id));
+diesel::joinable!(automod_rule -> community_tag (community_tag_id));
 diesel::joinable!(comment -> community (community_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
 diesel::joinable!(comment_actions -> comment (comment_id));
 diesel::joinable!(comment_actions -> person (person_id));
+diesel::joinable!(comment_draft -> comment (parent_id));
+diesel::joinable!(comment_draft -> language (language_id));
+diesel::joinable!(comment_draft -> person (creator_id));
+diesel::joinable!(comment_draft -> post (post_id));
 diesel::joinable!(comment_report -> comment (comment_id));
+diesel::joinable!(comment_report -> rule (rule_id));
+diesel::joinable!(comment_revision -> comment (comment_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_actions -> community (community_id));
+diesel::joinable!(community_actions -> community_flair (flair_id));
+diesel::joinable!(community_flair -> community (community_id));
+diesel::joinable!(community_flair -> custom_emoji (custom_emoji_id));
 diesel::joinable!(community_language -> community (community_id));
 diesel::joinable!(community_language -> language (language_id));
 diesel::joinable!(community_report -> community (community_id));
 diesel::joinable!(community_tag -> community (community_id));
+diesel::joinable!(content_import -> local_user (local_user_id));
+diesel::joinable!(content_import_failure -> content_import (content_import_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
+diesel::joinable!(data_export -> local_user (local_user_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(federation_allowlist -> instance (instance_id));
 diesel::joinable!(federation_blocklist -> instance (instance_id));
@@ -1030,9 +1334,12 @@ diesel::joinable!(local_user_keyword_block -> local_user (local_user_id));
 diesel::joinable!(local_user_language -> language (language_id));
 diesel::joinable!(local_user_language -> local_user (local_user_id));
 diesel::joinable!(login_token -> local_user (user_id));
+diesel::joinable!(mod_template -> community (community_id));
+diesel::joinable!(mod_template -> rule (rule_id));
 diesel::joinable!(modlog -> comment (target_comment_id));
 diesel::joinable!(modlog -> community (target_community_id));
 diesel::joinable!(modlog -> instance (target_instance_id));
+diesel::joinable!(modlog -> mod_template (mod_template_id));
 diesel::joinable!(modlog -> post (target_post_id));
 diesel::joinable!(multi_community -> instance (instance_id));
 diesel::joinable!(multi_community -> person (creator_id));
@@ -1067,7 +1374,17 @@ diesel::joinable!(post_actions -> person (person_id));
 diesel::joinable!(post_actions -> post (post_id));
 diesel::joinable!(post_community_tag -> community_tag (community_tag_id));
 diesel::joinable!(post_community_tag -> post (post_id));
+diesel::joinable!(post_draft -> community (community_id));
+diesel::joinable!(post_draft -> language (language_id));
+diesel::joinable!(post_draft -> person (creator_id));
+diesel::joinable!(post_poll -> post (post_id));
+diesel::joinable!(post_poll_option -> post_poll (post_id));
+diesel::joinable!(post_poll_vote -> person (person_id));
+diesel::joinable!(post_poll_vote -> post_poll (post_id));
+diesel::joinable!(post_poll_vote -> post_poll_option (post_poll_option_id));
 diesel::joinable!(post_report -> post (post_id));
+diesel::joinable!(post_report -> rule (rule_id));
+diesel::joinable!(post_revision -> post (post_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
@@ -1079,19 +1396,31 @@ diesel::joinable!(report_combined -> post (post_id));
 diesel::joinable!(report_combined -> post_report (post_report_id));
 diesel::joinable!(report_combined -> private_message (private_message_id));
 diesel::joinable!(report_combined -> private_message_report (private_message_report_id));
+diesel::joinable!(rule -> community (community_id));
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
+diesel::joinable!(webhook -> community (community_id));
+diesel::joinable!(webhook -> person (creator_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+  api_token,
+  automod_rule,
   comment,
   comment_actions,
+  comment_draft,
   comment_report,
+  comment_revision,
   community,
   community_actions,
+  community_flair,
   community_language,
   community_report,
   community_tag,
+  content_import,
+  content_import_failure,
+  data_export,
   email_verification,
   federation_allowlist,
   federation_blocklist,
@@ -1107,6 +1436,7 @@ diesel::allow_tables_to_appear_in_same_query!(
   local_user_keyword_block,
   local_user_language,
   login_token,
+  mod_template,
   modlog,
   multi_community,
   multi_community_entry,
@@ -1122,13 +1452,21 @@ diesel::allow_tables_to_appear_in_same_query!(
   post,
   post_actions,
   post_community_tag,
+  post_draft,
+  post_poll,
+  post_poll_option,
+  post_poll_vote,
   post_report,
+  post_revision,
   private_message,
   private_message_report,
   registration_application,
   report_combined,
+  rule,
   site,
   site_language,
+  webhook,
+  webhook_delivery,
   person_actions,
   image_details,
 );
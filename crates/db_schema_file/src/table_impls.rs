@@ -3,6 +3,7 @@ use crate::schema::{
   community_actions,
   instance_actions,
   person_actions,
+  post,
   post_actions,
 };
 
@@ -33,3 +34,9 @@ impl diesel_uplete::SupportedTable for post_actions::table {
   type Key = (post_actions::person_id, post_actions::post_id);
   type AdditionalIgnoredColumns = ();
 }
+
+// Needed to count the votes of a user per community
+diesel::allow_columns_to_appear_in_same_group_by_clause!(
+  post::community_id,
+  post_actions::vote_is_upvote,
+);
@@ -1,5 +1,8 @@
 use crate::CommentView;
-use lemmy_db_schema::newtypes::{CommentId, CommunityId, LanguageId, PostId};
+use lemmy_db_schema::{
+  newtypes::{CommentDraftId, CommentId, CommunityId, LanguageId, ModTemplateId, PostId},
+  source::{comment_draft::CommentDraft, comment_revision::CommentRevision},
+};
 use lemmy_db_schema_file::{
   PersonId,
   enums::{CommentSortType, ListingType},
@@ -68,6 +71,77 @@ pub struct GetComment {
   pub id: CommentId,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Save an unfinished comment. Drafts are only visible to their creator, and never federated.
+pub struct CreateCommentDraft {
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+  pub content: Option<String>,
+  pub language_id: Option<LanguageId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a comment draft.
+pub struct EditCommentDraft {
+  pub draft_id: CommentDraftId,
+  pub content: Option<String>,
+  pub language_id: Option<LanguageId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a comment draft.
+pub struct DeleteCommentDraft {
+  pub draft_id: CommentDraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Publish a comment draft. This goes through the same checks as creating a comment, and deletes
+/// the draft on success.
+pub struct PublishCommentDraft {
+  pub draft_id: CommentDraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommentDraftResponse {
+  pub draft: CommentDraft,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The comment drafts of the current user, most recently changed first.
+pub struct ListCommentDraftsResponse {
+  pub drafts: Vec<CommentDraft>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the edit history of a comment. Only available to the creator, mods and admins.
+pub struct GetCommentHistory {
+  pub comment_id: CommentId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The previous versions of a comment, newest first.
+pub struct GetCommentHistoryResponse {
+  pub revisions: Vec<CommentRevision>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -111,6 +185,13 @@ pub struct LockComment {
   pub comment_id: CommentId,
   pub locked: bool,
   pub reason: String,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -135,6 +216,13 @@ pub struct RemoveComment {
   /// Setting this will override whatever `removed` was set to,
   /// leave as null or unset to act just on the comment itself.
   pub remove_children: Option<bool>,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -165,4 +253,11 @@ pub struct EditComment {
 pub struct CreateCommentWarning {
   pub comment_id: CommentId,
   pub reason: String,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
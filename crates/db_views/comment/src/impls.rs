@@ -1,4 +1,5 @@
 use crate::{CommentSlimView, CommentView};
+use chrono::{DateTime, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
@@ -53,7 +54,7 @@ use lemmy_db_schema_file::{
     my_local_user_admin_join,
     my_person_actions_join,
   },
-  schema::{comment, community, person, post},
+  schema::{comment, community, instance, person, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -62,10 +63,20 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    offset_from_cursor,
     paginate_response,
+    paginate_response_with_offset,
   },
   traits::Crud,
-  utils::{Subpath, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    Subpath,
+    functions::{language_search_query, search_query, ts_rank},
+    fuzzy_search,
+    now,
+    seconds_to_pg_interval,
+    text_search::{SearchTable, has_search_lexemes, search_vector},
+    ts_match,
+  },
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -172,6 +183,12 @@ pub struct CommentQuery<'a> {
   pub local_user: Option<&'a LocalUser>,
   pub max_depth: Option<i32>,
   pub search_term: Option<String>,
+  /// Order by full-text search relevance instead of `sort`. Only used with `search_term`.
+  pub sort_by_relevance: Option<bool>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  /// Only comments in communities which belong to the instance with this domain.
+  pub instance_domain: Option<String>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -215,7 +232,7 @@ impl CommentQuery<'_> {
     // we ignore hidden.
     let listing_type = self.listing_type.unwrap_or_default();
     query = match listing_type {
-      ListingType::Subscribed => query.filter(filter_is_subscribed()),
+      ListingType::Subscribed | ListingType::ForYou => query.filter(filter_is_subscribed()),
       ListingType::Local => {
         // Always filter out unlisted comms unless you follow them
         query = query.filter(filter_unlisted_or_followed());
@@ -272,14 +289,37 @@ impl CommentQuery<'_> {
       query = query.filter(filter_blocked());
     };
 
-    if let Some(language_ids) = language_ids {
-      query = query.filter(comment::language_id.eq_any(language_ids));
+    // The search term, using full-text search with the stemming rules of the comment language
+    let mut search_rank = None;
+    if let Some(search_term) = self.search_term.filter(|s| !s.trim().is_empty()) {
+      let search_language_ids = language_ids
+        .iter()
+        .flatten()
+        .map(|l| l.0)
+        .collect::<Vec<_>>();
+      if has_search_lexemes(pool, &search_term, search_language_ids.clone()).await? {
+        let language_query = language_search_query(search_term.clone(), comment::language_id);
+        search_rank = Some(ts_rank(
+          search_vector(SearchTable::Comment),
+          language_query.clone(),
+        ));
+        query = query
+          .filter(ts_match(
+            search_vector(SearchTable::Comment),
+            search_query(search_term, search_language_ids),
+          ))
+          .filter(ts_match(
+            search_vector(SearchTable::Comment),
+            language_query,
+          ));
+      } else {
+        // Full-text search can't find anything for stop words, so match them as substrings
+        query = query.filter(comment::content.ilike(fuzzy_search(&search_term)));
+      }
     }
 
-    // The search term
-    if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
-      query = query.filter(comment::content.ilike(searcher));
+    if let Some(language_ids) = language_ids {
+      query = query.filter(comment::language_id.eq_any(language_ids));
     }
 
     if !self.local_user.show_nsfw(site) {
@@ -300,6 +340,24 @@ impl CommentQuery<'_> {
         query.filter(comment::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
 
+    if let Some(published_after) = self.published_after {
+      query = query.filter(comment::published_at.ge(published_after));
+    }
+
+    if let Some(published_before) = self.published_before {
+      query = query.filter(comment::published_at.lt(published_before));
+    }
+
+    if let Some(instance_domain) = &self.instance_domain {
+      query = query.filter(
+        community::instance_id.eq_any(
+          instance::table
+            .filter(instance::domain.eq(instance_domain.clone()))
+            .select(instance::id),
+        ),
+      );
+    }
+
     // A Max depth given means its a tree fetch
     let limit = if let Some(max_depth) = self.max_depth {
       let depth_limit = if let Some(parent_path) = self.parent_path.as_ref() {
@@ -328,6 +386,21 @@ impl CommentQuery<'_> {
     };
     query = query.limit(limit);
 
+    // Relevance sorting can't use cursor keys, so it is paginated by offset instead
+    if let Some(search_rank) = search_rank
+      && self.sort_by_relevance.unwrap_or_default()
+    {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let query = query
+        .order_by(search_rank.desc())
+        .then_order_by(comment::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = query.load::<CommentView>(conn).await?;
+      return paginate_response_with_offset(res, limit, offset);
+    }
+
     // Only sort by ascending for Old
     let sort = self.sort.unwrap_or(Hot);
     let sort_direction = asc_if(sort == Old);
@@ -352,6 +425,7 @@ impl CommentQuery<'_> {
     pq = match sort {
       Hot => pq.then_order_by(key::hot_rank).then_order_by(key::score),
       Controversial => pq.then_order_by(key::controversy_rank),
+      Best => pq.then_order_by(key::best_rank).then_order_by(key::score),
       Old | New => pq.then_order_by(key::published_at),
       Top => pq.then_order_by(key::score),
     };
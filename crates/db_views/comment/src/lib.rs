@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use lemmy_db_schema::source::{
   comment::{Comment, CommentActions},
   community::{Community, CommunityActions},
+  community_flair::CommunityFlairView,
   community_tag::CommunityTagsView,
   person::{Person, PersonActions},
   post::Post,
@@ -16,6 +17,7 @@ use {
     comment_select_remove_deletes,
     creator_ban_expires_from_community,
     creator_banned_from_community,
+    creator_community_flair_fragment,
     creator_is_admin,
     creator_is_moderator,
     creator_local_home_community_ban_expires,
@@ -67,6 +69,13 @@ pub struct CommentView {
     )
   )]
   pub tags: CommunityTagsView,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression = creator_community_flair_fragment()
+    )
+  )]
+  /// The flair of the creator in this community.
+  pub creator_flair: CommunityFlairView,
   #[cfg_attr(feature = "full",
     diesel(
       select_expression = local_user_can_mod()
@@ -3,12 +3,28 @@ use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
-  newtypes::{CommunityId, CommunityTagId, LanguageId, MultiCommunityId},
-  source::site::Site,
+  newtypes::{
+    AutomodRuleId,
+    CommunityFlairId,
+    CommunityId,
+    CommunityTagId,
+    CustomEmojiId,
+    LanguageId,
+    ModTemplateId,
+    MultiCommunityId,
+    RuleId,
+  },
+  source::{
+    automod_rule::AutomodRule,
+    community_flair::CommunityFlair,
+    mod_template::ModTemplate,
+    rule::Rule,
+    site::Site,
+  },
 };
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityNotificationsMode, CommunityVisibility, ListingType, TagColor},
+  enums::{AutomodAction, CommunityNotificationsMode, CommunityVisibility, ListingType, TagColor},
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -108,6 +124,12 @@ pub struct CreateCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether new posts need to be approved by a mod.
+  pub posts_require_approval: Option<bool>,
+  /// Only require approval for accounts which registered less than this many days ago.
+  pub post_approval_max_account_age_days: Option<i32>,
+  /// Only require approval for accounts with at most this post score.
+  pub post_approval_max_person_post_score: Option<i32>,
 }
 
 #[skip_serializing_none]
@@ -139,6 +161,12 @@ pub struct EditCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether new posts need to be approved by a mod.
+  pub posts_require_approval: Option<bool>,
+  /// Only require approval for accounts which registered less than this many days ago.
+  pub post_approval_max_account_age_days: Option<i32>,
+  /// Only require approval for accounts with at most this post score.
+  pub post_approval_max_person_post_score: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -184,6 +212,25 @@ pub struct GetRandomCommunity {
   pub show_nsfw: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches communities which are recommended for the user, based on shared subscribers with the
+/// user's communities, languages and activity.
+pub struct ListRecommendedCommunities {
+  pub show_nsfw: Option<bool>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The recommended communities, best match first.
+pub struct ListRecommendedCommunitiesResponse {
+  pub communities: Vec<CommunityView>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -363,3 +410,240 @@ pub struct DeleteCommunityTag {
   pub tag_id: CommunityTagId,
   pub delete: bool,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a user flair for a community.
+pub struct CreateCommunityFlair {
+  pub community_id: CommunityId,
+  pub text: String,
+  pub color: Option<TagColor>,
+  pub custom_emoji_id: Option<CustomEmojiId>,
+  /// Allow users to assign this flair to themselves.
+  pub self_assignable: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Make changes to a community flair.
+pub struct EditCommunityFlair {
+  pub flair_id: CommunityFlairId,
+  pub text: Option<String>,
+  pub color: Option<TagColor>,
+  pub custom_emoji_id: Option<CustomEmojiId>,
+  /// Removes the emoji from the flair.
+  pub remove_custom_emoji: Option<bool>,
+  pub self_assignable: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a community flair.
+pub struct DeleteCommunityFlair {
+  pub flair_id: CommunityFlairId,
+  pub delete: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the flairs of a community.
+pub struct ListCommunityFlairs {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListCommunityFlairsResponse {
+  pub flairs: Vec<CommunityFlair>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a rule for a community, or a site rule if `community_id` is empty (admins only).
+pub struct CreateRule {
+  pub community_id: Option<CommunityId>,
+  pub title: String,
+  pub description: Option<String>,
+  /// Rules are listed in ascending order of their position.
+  pub position: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Make changes to a community or site rule.
+pub struct EditRule {
+  pub rule_id: RuleId,
+  pub title: Option<String>,
+  /// An empty description removes it.
+  pub description: Option<String>,
+  pub position: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a community or site rule.
+pub struct DeleteRule {
+  pub rule_id: RuleId,
+  pub delete: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the rules of a community, or the site rules if `community_id` is empty.
+pub struct ListRules {
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListRulesResponse {
+  pub rules: Vec<Rule>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Save a removal reason or canned reply for a community (mods only).
+pub struct CreateModTemplate {
+  pub community_id: CommunityId,
+  pub title: String,
+  /// Markdown text, which is used as reason when the template is selected.
+  pub body: String,
+  pub rule_id: Option<RuleId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Replace the content of a mod template.
+pub struct EditModTemplate {
+  pub mod_template_id: ModTemplateId,
+  pub title: String,
+  pub body: String,
+  /// Leave empty to remove the rule reference.
+  pub rule_id: Option<RuleId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a mod template. Modlog entries which used it are kept.
+pub struct DeleteModTemplate {
+  pub mod_template_id: ModTemplateId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the mod templates of a community (mods only).
+pub struct ListModTemplates {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListModTemplatesResponse {
+  pub mod_templates: Vec<ModTemplate>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Assign a flair to yourself, or as mod to another user. Without `flair_id` the flair is removed.
+pub struct AssignCommunityFlair {
+  pub community_id: CommunityId,
+  /// Defaults to yourself. Only mods can assign flairs to other users.
+  pub person_id: Option<PersonId>,
+  pub flair_id: Option<CommunityFlairId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create an automod rule for a community. Needs at least one condition and one action.
+pub struct CreateAutomodRule {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub title_regex: Option<String>,
+  pub body_regex: Option<String>,
+  pub url_regex: Option<String>,
+  pub domains: Option<Vec<String>>,
+  pub language_ids: Option<Vec<LanguageId>>,
+  pub max_account_age_days: Option<i32>,
+  pub max_person_post_score: Option<i32>,
+  pub actions: Vec<AutomodAction>,
+  pub community_tag_id: Option<CommunityTagId>,
+  pub reply: Option<String>,
+  pub enabled: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit an automod rule. Empty strings erase the optional text fields.
+pub struct EditAutomodRule {
+  pub id: AutomodRuleId,
+  pub name: Option<String>,
+  pub title_regex: Option<String>,
+  pub body_regex: Option<String>,
+  pub url_regex: Option<String>,
+  pub domains: Option<Vec<String>>,
+  pub language_ids: Option<Vec<LanguageId>>,
+  pub max_account_age_days: Option<i32>,
+  pub max_person_post_score: Option<i32>,
+  pub actions: Option<Vec<AutomodAction>>,
+  pub community_tag_id: Option<CommunityTagId>,
+  pub reply: Option<String>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete an automod rule.
+pub struct DeleteAutomodRule {
+  pub id: AutomodRuleId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the automod rules of a community. Only available to mods.
+pub struct ListAutomodRules {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct AutomodRuleResponse {
+  pub rule: AutomodRule,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListAutomodRulesResponse {
+  pub rules: Vec<AutomodRule>,
+}
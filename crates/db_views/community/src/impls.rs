@@ -2,6 +2,7 @@ use crate::{CommunityView, MultiCommunityView};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
+  PgSortExpressionMethods,
   PgTextExpressionMethods,
   QueryDsl,
   SelectableHelper,
@@ -12,10 +13,11 @@ use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
-  impls::local_user::LocalUserOptionHelper,
+  impls::{actor_language::UNDETERMINED_ID, local_user::LocalUserOptionHelper},
   newtypes::{CommunityId, MultiCommunityId},
   source::{
-    community::{Community, community_keys as key},
+    actor_language::LocalUserLanguage,
+    community::{Community, CommunityActions, community_keys as key},
     local_site::LocalSite,
     local_user::LocalUser,
     multi_community::{MultiCommunity, MultiCommunityEntry, multi_community_keys as mkey},
@@ -35,6 +37,7 @@ use lemmy_db_schema_file::{
   schema::{
     community,
     community_actions,
+    community_language,
     instance_actions,
     multi_community,
     multi_community_entry,
@@ -49,12 +52,26 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    offset_from_cursor,
     paginate_response,
+    paginate_response_with_offset,
   },
   traits::Crud,
-  utils::{LowerKey, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    LowerKey,
+    functions::{search_query, ts_rank},
+    fuzzy_search,
+    now,
+    seconds_to_pg_interval,
+    text_search::{SearchTable, has_search_lexemes, search_vector, title_search_vector},
+    ts_match,
+  },
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::collections::HashMap;
+
+/// Maximum number of candidates for community recommendations, from each source.
+const RECOMMENDATION_CANDIDATES: i64 = 50;
 
 impl CommunityView {
   #[diesel::dsl::auto_type(no_type_alias)]
@@ -97,6 +114,111 @@ impl CommunityView {
   }
 }
 
+impl CommunityView {
+  /// Recommends communities which the user doesn't follow yet. Candidates are the communities with
+  /// the most subscribers in common with the user's communities, and the most active communities.
+  /// Only communities which share a language with the user are included.
+  pub async fn list_recommended(
+    pool: &mut DbPool<'_>,
+    my_local_user: Option<&'_ LocalUser>,
+    site: &Site,
+    show_nsfw: bool,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let limit = usize::try_from(limit_fetch(limit, None)?)?;
+    let my_person_id = my_local_user.person_id();
+    let language_ids = LocalUserLanguage::read_opt(pool, my_local_user.map(|l| l.id)).await?;
+    let my_community_ids = if let Some(my_person_id) = my_person_id {
+      CommunityActions::list_subscribed_community_ids(pool, my_person_id).await?
+    } else {
+      vec![]
+    };
+
+    let co_subscribers: HashMap<CommunityId, i64> = if let Some(my_person_id) = my_person_id {
+      CommunityActions::list_co_subscribed(
+        pool,
+        my_person_id,
+        &my_community_ids,
+        RECOMMENDATION_CANDIDATES,
+      )
+      .await?
+      .into_iter()
+      .collect()
+    } else {
+      HashMap::new()
+    };
+    let conn = &mut get_conn(pool).await?;
+
+    let active_community_ids: Vec<CommunityId> = community::table
+      .filter(community::users_active_week.gt(0))
+      .order_by((community::users_active_week.desc(), community::id.asc()))
+      .select(community::id)
+      .limit(RECOMMENDATION_CANDIDATES)
+      .load(conn)
+      .await?;
+
+    let candidate_ids: Vec<_> = co_subscribers
+      .keys()
+      .copied()
+      .chain(active_community_ids)
+      .collect();
+    let mut query = Self::joins(my_person_id)
+      .filter(community::id.eq_any(candidate_ids))
+      .filter(Community::hide_removed_and_deleted())
+      .filter(community::local_removed.eq(false))
+      .filter(community::visibility.ne(CommunityVisibility::Unlisted))
+      // Skip communities which are already followed (or pending), or blocked
+      .filter(community_actions::followed_at.is_null())
+      .filter(community_actions::blocked_at.is_null())
+      .filter(instance_actions::blocked_communities_at.is_null())
+      .select(Self::as_select())
+      .into_boxed();
+    if my_local_user.is_none() {
+      query = query.filter(community::visibility.ne(CommunityVisibility::LocalOnlyPrivate));
+    }
+    if !(my_local_user.show_nsfw(site) || show_nsfw) {
+      query = query.filter(community::nsfw.eq(false));
+    }
+    if let Some(language_ids) = language_ids {
+      let with_language = community_language::table
+        .filter(community_language::language_id.eq_any(language_ids))
+        .select(community_language::community_id);
+      query = query.filter(community::id.eq_any(with_language));
+    }
+
+    let mut res: Vec<(Self, f64)> = query
+      .load::<Self>(conn)
+      .await?
+      .into_iter()
+      .map(|c| {
+        let score = c.recommendation_score(
+          co_subscribers
+            .get(&c.community.id)
+            .copied()
+            .unwrap_or_default(),
+        );
+        (c, score)
+      })
+      .collect();
+    res.sort_by(|(a, a_score), (b, b_score)| {
+      b_score
+        .total_cmp(a_score)
+        .then(a.community.id.0.cmp(&b.community.id.0))
+    });
+    res.truncate(limit);
+    Ok(res.into_iter().map(|(c, _)| c).collect())
+  }
+
+  /// Shared subscribers count the most, followed by the number of weekly active users and monthly
+  /// interactions. Logarithms are used so that huge communities don't always come first.
+  #[expect(clippy::as_conversions)]
+  fn recommendation_score(&self, co_subscribers: i64) -> f64 {
+    2.0 * (co_subscribers as f64).ln_1p()
+      + f64::from(self.community.users_active_week).ln_1p()
+      + 0.5 * f64::from(self.community.interactions_month).ln_1p()
+  }
+}
+
 impl PaginationCursorConversion for CommunityView {
   type PaginatedType = Community;
   fn to_cursor(&self) -> CursorData {
@@ -118,9 +240,12 @@ pub struct CommunityQuery<'a> {
   pub time_range_seconds: Option<i32>,
   pub local_user: Option<&'a LocalUser>,
   pub show_nsfw: Option<bool>,
+  pub nsfw_only: Option<bool>,
   pub multi_community_id: Option<MultiCommunityId>,
   pub search_term: Option<String>,
   pub search_title_only: Option<bool>,
+  /// Order by full-text search relevance instead of `sort`. Only used with `search_term`.
+  pub sort_by_relevance: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -153,7 +278,7 @@ impl CommunityQuery<'_> {
     if let Some(listing_type) = self.listing_type {
       query = match listing_type {
         ListingType::All => query,
-        ListingType::Subscribed => query.filter(filter_is_subscribed()),
+        ListingType::Subscribed | ListingType::ForYou => query.filter(filter_is_subscribed()),
         ListingType::Local => query.filter(community::local.eq(true)),
         ListingType::ModeratorView => {
           query.filter(community_actions::became_moderator_at.is_not_null())
@@ -176,7 +301,9 @@ impl CommunityQuery<'_> {
     // also hidden (based on profile setting)
     query = query.filter(instance_actions::blocked_communities_at.is_null());
     query = query.filter(community_actions::blocked_at.is_null());
-    if !(self.local_user.show_nsfw(site) || self.show_nsfw.unwrap_or_default()) {
+    if self.nsfw_only.unwrap_or_default() {
+      query = query.filter(community::nsfw.eq(true));
+    } else if !(self.local_user.show_nsfw(site) || self.show_nsfw.unwrap_or_default()) {
       query = query.filter(community::nsfw.eq(false));
     }
 
@@ -188,22 +315,42 @@ impl CommunityQuery<'_> {
     }
 
     // The search term
-    if let Some(search_term) = self.search_term {
+    let mut search_rank = None;
+    if let Some(search_term) = self.search_term.filter(|s| !s.trim().is_empty()) {
       let searcher = fuzzy_search(&search_term);
 
-      let name_or_title_filter = community::name
+      let name_filter = community::name
         .ilike(searcher.clone())
         // Also include the ap_id to allow for instance searching
-        .or(community::ap_id.ilike(searcher.clone()))
-        .or(community::title.ilike(searcher.clone()));
-
-      query = if self.search_title_only.unwrap_or_default() {
-        query.filter(name_or_title_filter)
+        .or(community::ap_id.ilike(searcher.clone()));
+
+      query = if !has_search_lexemes(pool, &search_term, vec![UNDETERMINED_ID.0]).await? {
+        // Full-text search can't find anything for stop words, so match them as substrings
+        let title_filter = name_filter.or(community::title.ilike(searcher.clone()));
+        if self.search_title_only.unwrap_or_default() {
+          query.filter(title_filter)
+        } else {
+          query.filter(
+            title_filter
+              .or(community::summary.ilike(searcher.clone()))
+              .or(community::sidebar.ilike(searcher)),
+          )
+        }
       } else {
-        let body_or_description_filter = community::summary
-          .ilike(searcher.clone())
-          .or(community::sidebar.ilike(searcher.clone()));
-        query.filter(name_or_title_filter.or(body_or_description_filter))
+        // Full-text search for the title, summary and sidebar, or only for the title
+        let search_query = search_query(search_term, vec![UNDETERMINED_ID]);
+        let text_filter = ts_match(search_vector(SearchTable::Community), search_query.clone());
+        let title_vector = title_search_vector(SearchTable::Community);
+        if self.search_title_only.unwrap_or_default() {
+          search_rank = Some(ts_rank(title_vector.clone(), search_query.clone()));
+          query.filter(name_filter.or(text_filter.and(ts_match(title_vector, search_query))))
+        } else {
+          search_rank = Some(ts_rank(
+            search_vector(SearchTable::Community),
+            search_query.clone(),
+          ));
+          query.filter(name_filter.or(text_filter))
+        }
       }
     }
 
@@ -213,6 +360,24 @@ impl CommunityQuery<'_> {
         .filter(community::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
 
+    // Relevance sorting can't use cursor keys, so it is paginated by offset instead
+    if let Some(search_rank) = search_rank
+      && self.sort_by_relevance.unwrap_or_default()
+    {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let query = query
+        .order_by(search_rank.desc().nulls_last())
+        .then_order_by(community::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = query
+        .load::<CommunityView>(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::NotFound)?;
+      return paginate_response_with_offset(res, limit, offset);
+    }
+
     // Only sort by ascending for Old or NameAsc sorts.
     let sort = self.sort.unwrap_or_default();
     let sort_direction = asc_if(sort == Old || sort == NameAsc);
@@ -401,6 +566,7 @@ mod tests {
       community::{
         Community,
         CommunityActions,
+        CommunityBlockForm,
         CommunityFollowerForm,
         CommunityInsertForm,
         CommunityModeratorForm,
@@ -413,7 +579,7 @@ mod tests {
       person::{Person, PersonInsertForm},
       site::{Site, SiteInsertForm},
     },
-    traits::Followable,
+    traits::{Blockable, Followable},
   };
   use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
   use lemmy_diesel_utils::{
@@ -787,6 +953,68 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn recommended() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let tegan = data.local_user.person_id;
+
+    // Tom shares a subscription with tegan, so his other community gets recommended
+    for (community, person) in [
+      (&data.communities[0], tegan),
+      (&data.communities[0], data.tom.id),
+      (&data.communities[1], data.tom.id),
+    ] {
+      let form = CommunityFollowerForm::new(community.id, person, CommunityFollowerState::Accepted);
+      CommunityActions::follow(pool, &form).await?;
+    }
+
+    let recommended_ids = |views: Vec<CommunityView>| {
+      views
+        .into_iter()
+        .map(|v| v.community.id)
+        .collect::<HashSet<_>>()
+    };
+
+    let recommended = recommended_ids(
+      CommunityView::list_recommended(pool, Some(&data.local_user), &data.site, false, None)
+        .await?,
+    );
+    assert!(recommended.contains(&data.communities[1].id));
+    assert!(!recommended.contains(&data.communities[0].id));
+    assert!(!recommended.contains(&data.communities[2].id));
+
+    // Nsfw communities are only recommended if requested
+    let form = CommunityUpdateForm {
+      nsfw: Some(true),
+      ..Default::default()
+    };
+    Community::update(pool, data.communities[1].id, &form).await?;
+    let recommended = recommended_ids(
+      CommunityView::list_recommended(pool, Some(&data.local_user), &data.site, false, None)
+        .await?,
+    );
+    assert!(!recommended.contains(&data.communities[1].id));
+    let recommended = recommended_ids(
+      CommunityView::list_recommended(pool, Some(&data.local_user), &data.site, true, None).await?,
+    );
+    assert!(recommended.contains(&data.communities[1].id));
+
+    // Blocked communities are never recommended
+    let block_form = CommunityBlockForm::new(data.communities[1].id, tegan);
+    CommunityActions::block(pool, &block_form).await?;
+    let recommended = recommended_ids(
+      CommunityView::list_recommended(pool, Some(&data.local_user), &data.site, true, None).await?,
+    );
+    assert!(!recommended.contains(&data.communities[1].id));
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn search() -> LemmyResult<()> {
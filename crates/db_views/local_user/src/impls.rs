@@ -6,6 +6,7 @@ use diesel::{
   NullableExpressionMethods,
   QueryDsl,
   SelectableHelper,
+  dsl::IntervalDsl,
 };
 use diesel_async::RunQueryDsl;
 use i_love_jesus::asc_if;
@@ -21,6 +22,7 @@ use lemmy_db_schema::{
 use lemmy_db_schema_file::{
   PersonId,
   aliases::creator_home_instance_actions,
+  enums::EmailDigestFrequency,
   joins::creator_home_instance_actions_join,
   schema::{instance_actions, local_user, oauth_account, person},
 };
@@ -134,6 +136,48 @@ impl LocalUserView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Lists the users whose email digest is due. The digests are sent by an hourly task, so the
+  /// intervals are one hour shorter than the digest period to prevent them from drifting. Users
+  /// with an unverified email and banned users are excluded.
+  pub async fn list_due_email_digests(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let last_digest = local_user::last_email_digest_at;
+    let actions = creator_home_instance_actions;
+    Self::joins()
+      .filter(local_user::email.is_not_null())
+      .filter(local_user::email_verified.eq(true))
+      .filter(local_user::email_digest.ne(EmailDigestFrequency::Never))
+      .filter(person::deleted.eq(false))
+      .filter(
+        actions
+          .field(instance_actions::received_ban_at)
+          .is_null()
+          .or(
+            actions
+              .field(instance_actions::ban_expires_at)
+              .le(now().nullable()),
+          ),
+      )
+      .filter(
+        last_digest
+          .is_null()
+          .or(
+            local_user::email_digest
+              .eq(EmailDigestFrequency::Daily)
+              .and(last_digest.lt((now() - 23.hours()).nullable())),
+          )
+          .or(
+            local_user::email_digest
+              .eq(EmailDigestFrequency::Weekly)
+              .and(last_digest.lt((now() - 167.hours()).nullable())),
+          ),
+      )
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn create_test_user(
     pool: &mut DbPool<'_>,
     name: &str,
@@ -244,11 +288,12 @@ impl PaginationCursorConversion for LocalUserView {
 mod tests {
 
   use super::*;
+  use chrono::{Days, Utc};
   use lemmy_db_schema::{
     assert_length,
     source::{
       instance::{Instance, InstanceActions, InstanceBanForm},
-      local_user::{LocalUser, LocalUserInsertForm},
+      local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
       person::{Person, PersonInsertForm},
     },
     traits::Bannable,
@@ -308,4 +353,79 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  async fn is_due(
+    pool: &mut DbPool<'_>,
+    user: &LocalUserView,
+    form: LocalUserUpdateForm,
+  ) -> LemmyResult<bool> {
+    LocalUser::update(pool, user.local_user.id, &form).await?;
+    let due = LocalUserView::list_due_email_digests(pool).await?;
+    Ok(due.iter().any(|u| u.local_user.id == user.local_user.id))
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn list_due_email_digests() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let alice = LocalUserView::read_person(pool, data.alice.id).await?;
+
+    // Digests are disabled by default
+    let form = LocalUserUpdateForm {
+      email: Some(Some("alice@my_domain.tld".to_string())),
+      ..Default::default()
+    };
+    assert!(!is_due(pool, &alice, form).await?);
+
+    // Digests are only sent to verified emails. No digest was sent yet, so it is due immediately
+    let form = LocalUserUpdateForm {
+      email_digest: Some(EmailDigestFrequency::Daily),
+      ..Default::default()
+    };
+    assert!(!is_due(pool, &alice, form).await?);
+    let form = LocalUserUpdateForm {
+      email_verified: Some(true),
+      ..Default::default()
+    };
+    assert!(is_due(pool, &alice, form).await?);
+
+    let form = LocalUserUpdateForm {
+      last_email_digest_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    assert!(!is_due(pool, &alice, form).await?);
+
+    let form = LocalUserUpdateForm {
+      last_email_digest_at: Some(Some(Utc::now() - Days::new(2))),
+      ..Default::default()
+    };
+    assert!(is_due(pool, &alice, form).await?);
+
+    let form = LocalUserUpdateForm {
+      email_digest: Some(EmailDigestFrequency::Weekly),
+      ..Default::default()
+    };
+    assert!(!is_due(pool, &alice, form).await?);
+
+    // Banned users don't get digests
+    let form = LocalUserUpdateForm {
+      last_email_digest_at: Some(None),
+      ..Default::default()
+    };
+    assert!(is_due(pool, &alice, form).await?);
+    InstanceActions::ban(
+      pool,
+      &InstanceBanForm::new(data.alice.id, data.alice.instance_id, None),
+    )
+    .await?;
+    let form = LocalUserUpdateForm {
+      email_digest: Some(EmailDigestFrequency::Daily),
+      ..Default::default()
+    };
+    assert!(!is_due(pool, &alice, form).await?);
+
+    cleanup(data, pool).await
+  }
 }
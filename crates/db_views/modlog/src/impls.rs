@@ -168,7 +168,7 @@ impl ModlogQuery<'_> {
 
     query = match self.listing_type.unwrap_or(ListingType::All) {
       ListingType::All => query,
-      ListingType::Subscribed => query.filter(filter_is_subscribed()),
+      ListingType::Subscribed | ListingType::ForYou => query.filter(filter_is_subscribed()),
       ListingType::Local => query.filter(community::local.eq(true)),
       ListingType::ModeratorView => {
         query.filter(community_actions::became_moderator_at.is_not_null())
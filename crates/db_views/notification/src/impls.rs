@@ -257,6 +257,7 @@ fn map_to_enum(
       person_actions: v.person_actions,
       comment_actions: v.comment_actions,
       tags: v.tags,
+      creator_flair: v.creator_flair,
       creator_banned_from_community: v.creator_banned_from_community,
       creator_community_ban_expires_at: v.creator_community_ban_expires_at,
       creator_is_admin: v.creator_is_admin,
@@ -275,6 +276,7 @@ fn map_to_enum(
       post_actions: v.post_actions,
       person_actions: v.person_actions,
       tags: v.tags,
+      creator_flair: v.creator_flair,
       creator_banned_from_community: v.creator_banned_from_community,
       creator_community_ban_expires_at: v.creator_community_ban_expires_at,
       creator_is_admin: v.creator_is_admin,
@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use lemmy_db_schema::source::{
   comment::{Comment, CommentActions},
   community::{Community, CommunityActions},
+  community_flair::CommunityFlairView,
   community_tag::CommunityTagsView,
   images::ImageDetails,
   instance::Instance,
@@ -36,6 +37,7 @@ use {
     utils::queries::selects::{
       creator_ban_expires_from_community,
       creator_banned_from_community,
+      creator_community_flair_fragment,
       person1_select,
       post_community_tags_fragment,
     },
@@ -86,6 +88,8 @@ struct NotificationViewInternal {
   modlog: Option<Modlog>,
   #[diesel(select_expression = post_community_tags_fragment())]
   tags: CommunityTagsView,
+  #[diesel(select_expression = creator_community_flair_fragment())]
+  creator_flair: CommunityFlairView,
   #[diesel(select_expression = creator_is_admin())]
   creator_is_admin: bool,
   #[diesel(select_expression = local_user_can_mod())]
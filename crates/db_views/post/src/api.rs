@@ -1,7 +1,18 @@
-use crate::PostView;
+use crate::{PostPollView, PostView};
 use lemmy_db_schema::{
   PostFeatureType,
-  newtypes::{CommentId, CommunityId, CommunityTagId, LanguageId, MultiCommunityId, PostId},
+  newtypes::{
+    CommentId,
+    CommunityId,
+    CommunityTagId,
+    LanguageId,
+    ModTemplateId,
+    MultiCommunityId,
+    PostDraftId,
+    PostId,
+    PostPollOptionId,
+  },
+  source::{post_draft::PostDraft, post_revision::PostRevision},
 };
 use lemmy_db_schema_file::{
   PersonId,
@@ -35,6 +46,129 @@ pub struct CreatePost {
   pub tags: Option<Vec<CommunityTagId>>,
   /// Time when this post should be scheduled. Null means publish immediately.
   pub scheduled_publish_time_at: Option<i64>,
+  /// Makes this post a poll.
+  pub poll: Option<CreatePostPoll>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The poll of a new post.
+pub struct CreatePostPoll {
+  /// Between 2 and 20 unique options.
+  pub options: Vec<String>,
+  /// Allow voting for more than one option.
+  pub multiple_choice: Option<bool>,
+  /// Unix time after which no more votes are accepted. Null means the poll stays open.
+  pub end_time_at: Option<i64>,
+  /// Only show the vote counts once the poll is closed.
+  pub hide_results_until_closed: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the poll of a post.
+pub struct GetPostPoll {
+  pub post_id: PostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Vote in a poll. Replaces your previous votes in the same poll.
+pub struct VotePostPoll {
+  pub post_id: PostId,
+  /// Must contain exactly one option, unless the poll is multiple choice.
+  pub option_ids: Vec<PostPollOptionId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The poll response.
+pub struct PostPollResponse {
+  pub poll_view: PostPollView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Save an unfinished post. Drafts are only visible to their creator, and never federated.
+pub struct CreatePostDraft {
+  pub community_id: CommunityId,
+  pub name: Option<String>,
+  pub url: Option<String>,
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a post draft. Empty strings erase the optional fields.
+pub struct EditPostDraft {
+  pub draft_id: PostDraftId,
+  pub community_id: Option<CommunityId>,
+  pub name: Option<String>,
+  pub url: Option<String>,
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a post draft.
+pub struct DeletePostDraft {
+  pub draft_id: PostDraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Publish a post draft. This goes through the same checks as creating a post, and deletes the
+/// draft on success.
+pub struct PublishPostDraft {
+  pub draft_id: PostDraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PostDraftResponse {
+  pub draft: PostDraft,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The post drafts of the current user, most recently changed first.
+pub struct ListPostDraftsResponse {
+  pub drafts: Vec<PostDraft>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the edit history of a post. Only available to the creator, mods and admins.
+pub struct GetPostHistory {
+  pub post_id: PostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The previous versions of a post, newest first.
+pub struct GetPostHistoryResponse {
+  pub revisions: Vec<PostRevision>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -197,6 +331,7 @@ pub struct ListPostLikes {
   pub limit: Option<i64>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -205,6 +340,13 @@ pub struct LockPost {
   pub post_id: PostId,
   pub locked: bool,
   pub reason: String,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -262,6 +404,13 @@ pub struct RemovePost {
   /// Setting this will override whatever `removed` was set to,
   /// leave as null or unset to act just on the post itself.
   pub remove_children: Option<bool>,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -283,6 +432,33 @@ pub struct MarkManyPostsAsRead {
   pub read: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the posts which are waiting for approval in the communities you moderate.
+pub struct ListCommunityPendingPosts {
+  /// Only show pending posts of this community.
+  pub community_id: Option<CommunityId>,
+  // Only for admins, show pending posts for communities which you dont moderate
+  pub all_communities: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Approve or reject a post which is waiting for approval. Rejected posts get removed.
+pub struct ApproveCommunityPendingPost {
+  pub post_id: PostId,
+  pub approve: bool,
+  /// Shown in the modlog and to the post creator.
+  pub reason: Option<String>,
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -290,6 +466,13 @@ pub struct MarkManyPostsAsRead {
 pub struct CreatePostWarning {
   pub post_id: PostId,
   pub reason: String,
+  /// A saved removal reason or canned reply of the community. If `reason` is empty, the body of
+  /// the template is used instead.
+  pub mod_template_id: Option<ModTemplateId>,
+  /// Post the reason as a distinguished and locked reply.
+  pub reply_with_comment: Option<bool>,
+  /// Send the reason to the creator as private message.
+  pub reply_with_private_message: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -313,4 +496,6 @@ pub struct GetPostResponse {
   /// A list of cross-posts, or other times / communities this link has been posted to.
   pub cross_posts: Vec<PostView>,
   pub moderators: Vec<CommunityModeratorView>,
+  /// Only set if the post is a poll.
+  pub poll: Option<PostPollView>,
 }
@@ -1,4 +1,5 @@
-use crate::PostView;
+use crate::{PostPollView, PostView};
+use chrono::{DateTime, Utc};
 use diesel::{
   self,
   BoolExpressionMethods,
@@ -10,7 +11,7 @@ use diesel::{
   SelectableHelper,
   TextExpressionMethods,
   debug_query,
-  dsl::not,
+  dsl::{IntervalDsl, count_star, not},
   pg::Pg,
   query_builder::AsQuery,
 };
@@ -27,6 +28,7 @@ use lemmy_db_schema::{
     multi_community::MultiCommunityEntry,
     person::Person,
     post::{Post, PostActions, post_actions_keys as pa_key, post_keys as key},
+    post_poll::{PostPoll, PostPollOption, PostPollVote},
     site::Site,
   },
   utils::{
@@ -52,7 +54,16 @@ use lemmy_db_schema_file::{
     my_person_actions_join,
     my_post_actions_join,
   },
-  schema::{community, person, post, post_actions, post_community_tag},
+  schema::{
+    community,
+    community_actions,
+    instance,
+    person,
+    person_liked_combined,
+    post,
+    post_actions,
+    post_community_tag,
+  },
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -61,18 +72,43 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    offset_from_cursor,
     paginate_response,
+    paginate_response_with_offset,
   },
   traits::Crud,
-  utils::{CoalesceKey, Commented, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    CoalesceKey,
+    Commented,
+    functions::{community_affinity, language_search_query, search_query, ts_rank, url_host},
+    fuzzy_search,
+    now,
+    seconds_to_pg_interval,
+    text_search::{SearchTable, has_search_lexemes, search_vector, title_search_vector},
+    ts_match,
+  },
 };
 use lemmy_utils::{
-  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::markdown::clean_url,
 };
+use moka::future::Cache;
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
 use tracing::debug;
 use url::Url;
 
+/// How far back votes are considered for the `ForYou` listing.
+const FOR_YOU_VOTE_HISTORY_DAYS: i32 = 90;
+/// Maximum number of unsubscribed communities which are included in the `ForYou` listing because
+/// they share subscribers with the user's communities.
+const FOR_YOU_RELATED_COMMUNITIES: i64 = 20;
+/// How long the community affinities of a user are cached. Calculating them is expensive, and
+/// they change only slowly.
+#[cfg(debug_assertions)]
+const FOR_YOU_CACHE_DURATION: Duration = Duration::from_secs(0);
+#[cfg(not(debug_assertions))]
+const FOR_YOU_CACHE_DURATION: Duration = Duration::from_secs(10 * 60);
+
 impl PaginationCursorConversion for PostView {
   type PaginatedType = Post;
   fn to_cursor(&self) -> CursorData {
@@ -217,6 +253,12 @@ impl PostView {
             .or(post::creator_id.nullable().eq(my_person_id))
             .or(post::comments.gt(0)),
         )
+        // Posts waiting for approval are only visible to the creator
+        .filter(
+          post::approval_pending
+            .eq(false)
+            .or(post::creator_id.nullable().eq(my_person_id)),
+        )
         // private communities can only by browsed by accepted followers
         .filter(filter_private_or_followed());
     }
@@ -293,6 +335,70 @@ impl PostView {
       .with_lemmy_type(LemmyErrorType::NotFound)?;
     paginate_response(res, limit, page_cursor)
   }
+
+  /// List the posts which are waiting for approval in the communities you moderate, oldest first.
+  pub async fn list_pending_approval(
+    pool: &mut DbPool<'_>,
+    my_person: &Person,
+    community_id: Option<CommunityId>,
+    all_communities: bool,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<PostView>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = PostView::joins(Some(my_person.id), my_person.instance_id)
+      .filter(post::approval_pending)
+      .filter(post::removed.eq(false))
+      .filter(post::deleted.eq(false))
+      .limit(limit)
+      .select(PostView::as_select())
+      .into_boxed();
+
+    // if param is false, only return items for communities where user is a mod
+    if !all_communities {
+      query = query.filter(community_actions::became_moderator_at.is_not_null());
+    }
+    if let Some(community_id) = community_id {
+      query = query.filter(post::community_id.eq(community_id));
+    }
+
+    let paginated_query = PostView::paginate(query, &page_cursor, SortDirection::Asc, pool)
+      .await?
+      .then_order_by(key::published_at)
+      // Tie breaker
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Counts the posts which are waiting for approval in the communities you moderate.
+  pub async fn count_pending_approval(
+    pool: &mut DbPool<'_>,
+    my_person_id: PersonId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    post::table
+      .inner_join(
+        community_actions::table.on(
+          community_actions::community_id
+            .eq(post::community_id)
+            .and(community_actions::person_id.eq(my_person_id)),
+        ),
+      )
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .filter(post::approval_pending)
+      .filter(post::removed.eq(false))
+      .filter(post::deleted.eq(false))
+      .select(count_star())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 #[derive(Clone, Default)]
@@ -307,12 +413,24 @@ pub struct PostQuery<'a> {
   pub show_hidden: Option<bool>,
   pub show_read: Option<bool>,
   pub show_nsfw: Option<bool>,
+  /// Only nsfw posts, or posts in nsfw communities.
+  pub nsfw_only: Option<bool>,
   pub hide_posts_with_media: Option<bool>,
   pub no_comments_only: Option<bool>,
   pub keyword_blocks: Option<Vec<String>>,
   pub search_term: Option<String>,
   pub search_title_only: Option<bool>,
   pub search_url_only: Option<bool>,
+  /// Order by full-text search relevance instead of `sort`. Only used with `search_term`.
+  pub sort_by_relevance: Option<bool>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  /// Only posts which link to this domain or one of its subdomains.
+  pub url_domain: Option<String>,
+  pub has_url: Option<bool>,
+  pub has_image: Option<bool>,
+  /// Only posts in communities which belong to the instance with this domain.
+  pub instance_domain: Option<String>,
   pub page_cursor: Option<PaginationCursor>,
   pub tag_id: Option<CommunityTagId>,
   /// For backwards compat with API v3 (not available on API v4).
@@ -329,6 +447,7 @@ impl PostQuery<'_> {
   /// - Multicommunities
   /// - Moderator view
   /// - Suggested
+  /// - For you (using the given community affinities)
   ///
   ///  A return value of None means ignore, empty vec means filter out everything (IE empty
   /// subscribed, moderated, suggested)
@@ -336,6 +455,7 @@ impl PostQuery<'_> {
     &self,
     pool: &mut DbPool<'_>,
     local_site: &LocalSite,
+    affinities: Option<&[CommunityAffinity]>,
   ) -> LemmyResult<Option<Vec<CommunityId>>> {
     // First, check the given community or multi community id, then if both are none, check the
     // listing types
@@ -372,6 +492,13 @@ impl PostQuery<'_> {
               Some(vec![])
             }
           }
+          ListingType::ForYou => Some(
+            affinities
+              .unwrap_or_default()
+              .iter()
+              .map(|a| a.community_id)
+              .collect(),
+          ),
         }
       }
     };
@@ -386,7 +513,19 @@ impl PostQuery<'_> {
     local_site: &LocalSite,
   ) -> LemmyResult<PagedResponse<PostView>> {
     // Pre-fetching some important items, to prevent costly joins.
-    let community_ids = self.prefetch_community_ids(pool, local_site).await?;
+    let affinities = match self.local_user.person_id() {
+      Some(my_person_id)
+        if self.listing_type == Some(ListingType::ForYou)
+          && self.community_id.is_none()
+          && self.multi_community_id.is_none() =>
+      {
+        Some(CommunityAffinity::list_cached(pool, my_person_id).await?)
+      }
+      _ => None,
+    };
+    let community_ids = self
+      .prefetch_community_ids(pool, local_site, affinities.as_deref())
+      .await?;
     let language_ids = LocalUserLanguage::read_opt(pool, self.local_user.map(|l| l.id)).await?;
 
     let limit = limit_fetch(self.limit, None)?;
@@ -455,11 +594,13 @@ impl PostQuery<'_> {
     }
 
     // The search term
-    if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
-
-      let name_or_title_filter = post::name.ilike(searcher.clone());
-
+    let mut search_rank = None;
+    if let Some(search_term) = self.search_term.filter(|s| !s.trim().is_empty()) {
+      let search_language_ids = language_ids
+        .iter()
+        .flatten()
+        .map(|l| l.0)
+        .collect::<Vec<_>>();
       // A url / cross-post search
       query = if self.search_url_only.unwrap_or_default() {
         // Parse and normalize the url, removing tracking parameters (same logic which is used
@@ -467,15 +608,71 @@ impl PostQuery<'_> {
         let normalized_url = Url::parse(&search_term).map(|u| clean_url(&u).to_string())?;
 
         query.filter(post::url.eq(normalized_url))
-      } else if self.search_title_only.unwrap_or_default() {
-        query.filter(name_or_title_filter)
+      } else if !has_search_lexemes(pool, &search_term, search_language_ids.clone()).await? {
+        // Full-text search can't find anything for stop words, so match them as substrings
+        let searcher = fuzzy_search(&search_term);
+        if self.search_title_only.unwrap_or_default() {
+          query.filter(post::name.ilike(searcher))
+        } else {
+          query.filter(
+            post::name
+              .ilike(searcher.clone())
+              .or(post::body.ilike(searcher)),
+          )
+        }
       } else {
-        let body_or_description_filter = post::body.ilike(searcher.clone());
-        query.filter(name_or_title_filter.or(body_or_description_filter))
+        // Full-text search, using the stemming rules of the post language. The first filter uses
+        // the search index, the second one removes false positives and applies title only.
+        let matched_vector = if self.search_title_only.unwrap_or_default() {
+          title_search_vector(SearchTable::Post)
+        } else {
+          search_vector(SearchTable::Post)
+        };
+        let language_query = language_search_query(search_term.clone(), post::language_id);
+        search_rank = Some(ts_rank(matched_vector.clone(), language_query.clone()));
+        query
+          .filter(ts_match(
+            search_vector(SearchTable::Post),
+            search_query(search_term, search_language_ids),
+          ))
+          .filter(ts_match(matched_vector, language_query))
       }
     }
 
-    if !self.show_nsfw.unwrap_or(self.local_user.show_nsfw(site)) {
+    if let Some(url_domain) = &self.url_domain {
+      let url_domain = url_domain.to_lowercase();
+      let subdomains = url_domain
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+      query = query.filter(
+        url_host(post::url)
+          .eq(url_domain)
+          .or(url_host(post::url).like(format!("%.{subdomains}"))),
+      );
+    }
+
+    if self.has_url.unwrap_or_default() {
+      query = query.filter(post::url.is_not_null());
+    }
+
+    if self.has_image.unwrap_or_default() {
+      query = query.filter(post::url_content_type.like("image/%"));
+    }
+
+    if let Some(instance_domain) = &self.instance_domain {
+      query = query.filter(
+        community::instance_id.eq_any(
+          instance::table
+            .filter(instance::domain.eq(instance_domain.clone()))
+            .select(instance::id),
+        ),
+      );
+    }
+
+    if self.nsfw_only.unwrap_or_default() {
+      query = query.filter(post::nsfw.or(community::nsfw));
+    } else if !self.show_nsfw.unwrap_or(self.local_user.show_nsfw(site)) {
       query = query
         .filter(post::nsfw.eq(false))
         .filter(community::nsfw.eq(false));
@@ -517,6 +714,11 @@ impl PostQuery<'_> {
         .eq(false)
         .or(post::creator_id.nullable().eq(my_person_id)),
     );
+    query = query.filter(
+      post::approval_pending
+        .eq(false)
+        .or(post::creator_id.nullable().eq(my_person_id)),
+    );
 
     // Dont filter blocks or missing languages for moderator view type
     if self.listing_type.unwrap_or_default() != ListingType::ModeratorView {
@@ -547,6 +749,14 @@ impl PostQuery<'_> {
         query.filter(post::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
 
+    if let Some(published_after) = self.published_after {
+      query = query.filter(post::published_at.ge(published_after));
+    }
+
+    if let Some(published_before) = self.published_before {
+      query = query.filter(post::published_at.lt(published_before));
+    }
+
     if let Some(tag_id) = self.tag_id {
       // This filters the post_community_tags_fragment subquery
       let tags = post_community_tag::table
@@ -561,6 +771,50 @@ impl PostQuery<'_> {
       query = query.filter(tags.eq(tag_id));
     }
 
+    // Relevance sorting can't use cursor keys, so it is paginated by offset instead
+    if let Some(search_rank) = search_rank
+      && self.sort_by_relevance.unwrap_or_default()
+    {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let query = query
+        .order_by(search_rank.desc())
+        .then_order_by(post::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = Commented::new(query)
+        .text("PostQuery::list_by_relevance")
+        .load::<PostView>(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::NotFound)?;
+      return paginate_response_with_offset(res, limit, offset);
+    }
+
+    // The personalized ranking can't use cursor keys either, so it is also paginated by offset
+    if let Some(affinities) = affinities {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let (community_ids, weights): (Vec<_>, Vec<_>) = affinities
+        .iter()
+        .map(|a| (a.community_id, a.weight()))
+        .unzip();
+      let affinity = community_affinity(post::community_id, community_ids, weights);
+      let query = query
+        .order_by((post::hot_rank * affinity.clone()).desc())
+        // Hot rank is zero for older posts, so these are ordered by community affinity instead
+        .then_order_by(affinity.desc())
+        .then_order_by(post::published_at.desc())
+        .then_order_by(post::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = Commented::new(query)
+        .text("PostQuery::list_for_you")
+        .load::<PostView>(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::NotFound)?;
+      return paginate_response_with_offset(res, limit, offset);
+    }
+
     // Only sort by ascending for Old
     let sort = self.sort.unwrap_or(PostSortType::Hot);
     let sort_direction = asc_if(sort == PostSortType::Old);
@@ -615,3 +869,150 @@ impl PostQuery<'_> {
     paginate_response(res, limit, self.page_cursor)
   }
 }
+
+/// The interest of a user in a community, which is used to rank posts in the `ForYou` listing.
+/// The weight only depends on these values, so that the ranking is deterministic and can be
+/// explained.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CommunityAffinity {
+  pub community_id: CommunityId,
+  pub subscribed: bool,
+  /// Upvotes minus downvotes which the user recently gave to posts and comments in the community.
+  pub vote_score: i64,
+  /// Number of users who are subscribed to this community and to one of the user's communities.
+  /// Only counted for communities which the user isn't subscribed to.
+  pub co_subscribers: i64,
+}
+
+impl CommunityAffinity {
+  /// Posts are ranked by their hot rank multiplied with this weight, which is the sum of:
+  /// - 1 if subscribed
+  /// - 0.1 per net upvote, between -0.5 and 1
+  /// - 0.1 * ln(1 + co_subscribers), at most 0.5
+  #[expect(clippy::as_conversions)]
+  pub fn weight(&self) -> f32 {
+    let subscribed = if self.subscribed { 1.0 } else { 0.0 };
+    let votes = (self.vote_score as f32 * 0.1).clamp(-0.5, 1.0);
+    let related = ((self.co_subscribers as f32).ln_1p() * 0.1).min(0.5);
+    subscribed + votes + related
+  }
+
+  /// Same as [`CommunityAffinity::list`], but cached for some time so that paging through the
+  /// `ForYou` listing doesn't recalculate it for every page.
+  pub async fn list_cached(pool: &mut DbPool<'_>, person_id: PersonId) -> LemmyResult<Vec<Self>> {
+    static CACHE: LazyLock<Cache<PersonId, Vec<CommunityAffinity>>> = LazyLock::new(|| {
+      Cache::builder()
+        .max_capacity(10000)
+        .time_to_live(FOR_YOU_CACHE_DURATION)
+        .build()
+    });
+    Box::pin(CACHE.try_get_with(person_id, Self::list(pool, person_id)))
+      .await
+      .map_err(|e| LemmyError::from(e.error_type.clone()))
+  }
+
+  /// Lists the communities with a positive weight for the given person, ordered by id.
+  pub async fn list(pool: &mut DbPool<'_>, person_id: PersonId) -> LemmyResult<Vec<Self>> {
+    let subscribed = CommunityActions::list_subscribed_community_ids(pool, person_id).await?;
+    // Communities which have the most subscribers in common with the subscribed communities
+    let co_subscribers = CommunityActions::list_co_subscribed(
+      pool,
+      person_id,
+      &subscribed,
+      FOR_YOU_RELATED_COMMUNITIES,
+    )
+    .await?;
+
+    let conn = &mut get_conn(pool).await?;
+
+    let post_votes: Vec<(CommunityId, Option<bool>, i64)> = post_actions::table
+      .inner_join(post::table)
+      .filter(post_actions::person_id.eq(person_id))
+      .filter(post_actions::vote_is_upvote.is_not_null())
+      .filter(post_actions::voted_at.gt((now() - FOR_YOU_VOTE_HISTORY_DAYS.days()).nullable()))
+      .group_by((post::community_id, post_actions::vote_is_upvote))
+      .select((
+        post::community_id,
+        post_actions::vote_is_upvote,
+        count_star(),
+      ))
+      .load(conn)
+      .await?;
+
+    let comment_votes: Vec<(CommunityId, bool, i64)> = person_liked_combined::table
+      .filter(person_liked_combined::person_id.eq(person_id))
+      .filter(person_liked_combined::comment_id.is_not_null())
+      .filter(person_liked_combined::voted_at.gt(now() - FOR_YOU_VOTE_HISTORY_DAYS.days()))
+      .group_by((
+        person_liked_combined::community_id,
+        person_liked_combined::vote_is_upvote,
+      ))
+      .select((
+        person_liked_combined::community_id,
+        person_liked_combined::vote_is_upvote,
+        count_star(),
+      ))
+      .load(conn)
+      .await?;
+
+    let mut affinities = HashMap::new();
+    for community_id in subscribed {
+      Self::entry(&mut affinities, community_id).subscribed = true;
+    }
+    let votes = post_votes
+      .into_iter()
+      .map(|(community_id, upvote, count)| (community_id, upvote.unwrap_or_default(), count))
+      .chain(comment_votes);
+    for (community_id, upvote, count) in votes {
+      Self::entry(&mut affinities, community_id).vote_score += if upvote { count } else { -count };
+    }
+    for (community_id, count) in co_subscribers {
+      Self::entry(&mut affinities, community_id).co_subscribers = count;
+    }
+
+    let mut affinities: Vec<_> = affinities
+      .into_values()
+      .filter(|a| a.weight() > 0.0)
+      .collect();
+    affinities.sort_by_key(|a| a.community_id.0);
+    Ok(affinities)
+  }
+
+  fn entry(affinities: &mut HashMap<CommunityId, Self>, community_id: CommunityId) -> &mut Self {
+    affinities.entry(community_id).or_insert_with(|| Self {
+      community_id,
+      ..Default::default()
+    })
+  }
+}
+
+impl PostPollView {
+  /// Returns `None` if the post is not a poll. Vote counts are cleared if they are hidden until
+  /// the poll is closed, except for the poll creator.
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    post: &Post,
+    my_person_id: Option<PersonId>,
+  ) -> LemmyResult<Option<Self>> {
+    let Some(poll) = PostPoll::read_for_post(pool, post.id).await? else {
+      return Ok(None);
+    };
+    let mut options = PostPollOption::read_for_post(pool, post.id).await?;
+    let my_votes = if let Some(my_person_id) = my_person_id {
+      PostPollVote::read_for_person(pool, post.id, my_person_id).await?
+    } else {
+      vec![]
+    };
+
+    let results_hidden = poll.results_hidden() && my_person_id != Some(post.creator_id);
+    if results_hidden {
+      options.iter_mut().for_each(|o| o.vote_count = 0);
+    }
+    Ok(Some(PostPollView {
+      poll,
+      options,
+      my_votes,
+      results_hidden,
+    }))
+  }
+}
@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
-use lemmy_db_schema::source::{
-  community::{Community, CommunityActions},
-  community_tag::CommunityTagsView,
-  images::ImageDetails,
-  person::{Person, PersonActions},
-  post::{Post, PostActions},
+use lemmy_db_schema::{
+  newtypes::PostPollOptionId,
+  source::{
+    community::{Community, CommunityActions},
+    community_flair::CommunityFlairView,
+    community_tag::CommunityTagsView,
+    images::ImageDetails,
+    person::{Person, PersonActions},
+    post::{Post, PostActions},
+    post_poll::{PostPoll, PostPollOption},
+  },
 };
 use serde::{Deserialize, Serialize};
 #[cfg(test)]
@@ -20,6 +25,7 @@ use {
     CreatorLocalHomeBanExpiresType,
     creator_ban_expires_from_community,
     creator_banned_from_community,
+    creator_community_flair_fragment,
     creator_is_admin,
     creator_is_moderator,
     creator_local_home_ban_expires,
@@ -70,6 +76,13 @@ pub struct PostView {
     )
   )]
   pub tags: CommunityTagsView,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression = creator_community_flair_fragment()
+    )
+  )]
+  /// The flair of the creator in this community.
+  pub creator_flair: CommunityFlairView,
   #[cfg_attr(feature = "full",
     diesel(
       select_expression = local_user_can_mod()
@@ -108,3 +121,16 @@ pub struct PostView {
   )]
   pub creator_community_ban_expires_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A poll with its options.
+pub struct PostPollView {
+  pub poll: PostPoll,
+  pub options: Vec<PostPollOption>,
+  /// The options which you voted for.
+  pub my_votes: Vec<PostPollOptionId>,
+  /// Whether vote counts are hidden until the poll is closed. In this case all counts are zero.
+  pub results_hidden: bool,
+}
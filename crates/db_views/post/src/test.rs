@@ -1,6 +1,9 @@
 #![expect(clippy::indexing_slicing, clippy::expect_used, clippy::unreachable)]
 
-use crate::{PostView, impls::PostQuery};
+use crate::{
+  PostView,
+  impls::{CommunityAffinity, PostQuery},
+};
 use chrono::{DateTime, Days, Utc};
 use diesel_async::SimpleAsyncConnection;
 use diesel_uplete::UpleteCount;
@@ -21,6 +24,7 @@ use lemmy_db_schema::{
       CommunityPersonBanForm,
       CommunityUpdateForm,
     },
+    community_flair::{CommunityFlair, CommunityFlairInsertForm, CommunityFlairUpdateForm},
     community_tag::{CommunityTag, CommunityTagInsertForm, PostCommunityTag},
     instance::{
       Instance,
@@ -992,6 +996,106 @@ async fn post_listings_deleted(data: &mut Data) -> LemmyResult<()> {
   Ok(())
 }
 
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_listings_approval_pending(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  Post::update(
+    pool,
+    data.bot_post.id,
+    &PostUpdateForm {
+      approval_pending: Some(true),
+      ..Default::default()
+    },
+  )
+  .await?;
+
+  // Pending post is only shown to creator
+  for (local_user, expect_contains_pending) in [
+    (None, false),
+    (Some(&data.tegan.local_user), false),
+    (Some(&data.bot.local_user), true),
+  ] {
+    let contains_pending = PostQuery {
+      local_user,
+      ..data.default_post_query()
+    }
+    .list(pool, &data.site, &data.local_site)
+    .await?
+    .iter()
+    .any(|p| p.post.id == data.bot_post.id);
+
+    assert_eq!(expect_contains_pending, contains_pending);
+  }
+
+  // Mods see it in the approval queue
+  let pending =
+    PostView::list_pending_approval(pool, &data.tegan.person, None, false, None, None).await?;
+  assert!(pending.is_empty());
+  let form = CommunityModeratorForm::new(data.community.id, data.tegan.person.id);
+  CommunityActions::join(pool, &form).await?;
+  let pending =
+    PostView::list_pending_approval(pool, &data.tegan.person, None, false, None, None).await?;
+  assert_eq!(vec![POST_BY_BOT], names(&pending));
+
+  Ok(())
+}
+
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_listing_creator_flair(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  let post_view = PostView::read(pool, data.post.id, None, data.instance.id, false).await?;
+  assert_eq!(None, post_view.creator_flair.0);
+
+  let form = CommunityFlairInsertForm {
+    ap_id: Url::parse(&format!("{}/flair/officer", data.community.ap_id))?.into(),
+    community_id: data.community.id,
+    text: "Officer".to_string(),
+    color: Some(TagColor::Color03),
+    custom_emoji_id: None,
+    self_assignable: Some(true),
+    deleted: None,
+  };
+  let flair = CommunityFlair::create(pool, &form).await?;
+  CommunityActions::set_flair(
+    pool,
+    data.tegan.person.id,
+    data.community.id,
+    Some(flair.id),
+  )
+  .await?;
+
+  let post_view = PostView::read(pool, data.post.id, None, data.instance.id, false).await?;
+  assert_eq!(Some(&flair), post_view.creator_flair.0.as_ref());
+
+  // Flair is only shown for the creator of the post
+  let bot_post_view = PostView::read(pool, data.bot_post.id, None, data.instance.id, false).await?;
+  assert_eq!(None, bot_post_view.creator_flair.0);
+
+  // Deleted flairs are hidden
+  let form = CommunityFlairUpdateForm {
+    deleted: Some(true),
+    ..Default::default()
+  };
+  CommunityFlair::update(pool, flair.id, &form).await?;
+  let post_view = PostView::read(pool, data.post.id, None, data.instance.id, false).await?;
+  assert_eq!(None, post_view.creator_flair.0);
+
+  CommunityActions::set_flair(pool, data.tegan.person.id, data.community.id, None).await?;
+  let flair =
+    CommunityFlair::read_for_person(pool, data.tegan.person.id, data.community.id).await?;
+  assert_eq!(None, flair);
+
+  Ok(())
+}
+
 #[test_context(Data)]
 #[tokio::test]
 #[serial]
@@ -2396,6 +2500,112 @@ async fn post_listing_multi_community(data: &mut Data) -> LemmyResult<()> {
   Ok(())
 }
 
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_listing_for_you(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  // A community which shares a subscriber with the main community
+  let form = CommunityInsertForm::new(
+    data.instance.id,
+    "test_community_related".to_string(),
+    "pubkey".to_string(),
+  );
+  let related_community = Community::create(pool, &form).await?;
+  let form = PostInsertForm::new(POST.to_string(), data.john.person.id, related_community.id);
+  let related_post = Post::create(pool, &form).await?;
+
+  // A community which tegan doesn't like
+  let form = CommunityInsertForm::new(
+    data.instance.id,
+    "test_community_disliked".to_string(),
+    "pubkey".to_string(),
+  );
+  let disliked_community = Community::create(pool, &form).await?;
+  let form = PostInsertForm::new(POST.to_string(), data.john.person.id, disliked_community.id);
+  let disliked_post = Post::create(pool, &form).await?;
+  let form = PostLikeForm::new(disliked_post.id, data.tegan.person.id, Some(false));
+  PostActions::like(pool, &form).await?;
+
+  for (community_id, person_id) in [
+    (data.community.id, data.tegan.person.id),
+    (data.community.id, data.john.person.id),
+    (related_community.id, data.john.person.id),
+  ] {
+    let form =
+      CommunityFollowerForm::new(community_id, person_id, CommunityFollowerState::Accepted);
+    CommunityActions::follow(pool, &form).await?;
+  }
+
+  let affinities = CommunityAffinity::list(pool, data.tegan.person.id).await?;
+  assert_length!(2, affinities);
+  assert_eq!(data.community.id, affinities[0].community_id);
+  assert!(affinities[0].subscribed);
+  assert_eq!(
+    CommunityAffinity {
+      community_id: related_community.id,
+      subscribed: false,
+      vote_score: 0,
+      co_subscribers: 1,
+    },
+    affinities[1]
+  );
+  assert!(affinities[0].weight() > affinities[1].weight());
+
+  let for_you_query = PostQuery {
+    listing_type: Some(ListingType::ForYou),
+    local_user: Some(&data.tegan.local_user),
+    ..Default::default()
+  };
+  let listing = for_you_query
+    .clone()
+    .list(pool, &data.site, &data.local_site)
+    .await?;
+  let listing_communities = listing
+    .iter()
+    .map(|l| l.community.id)
+    .collect::<HashSet<_>>();
+  assert_eq!(
+    HashSet::from([data.community.id, related_community.id]),
+    listing_communities
+  );
+  // Posts from the subscribed community are ranked higher
+  assert_eq!(Some(related_post.id), listing.last().map(|l| l.post.id));
+
+  // Paginating gives the same order
+  let first_page = PostQuery {
+    limit: Some(1),
+    ..for_you_query.clone()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  let second_page = PostQuery {
+    limit: Some(1),
+    page_cursor: first_page.next_page.clone(),
+    ..for_you_query
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(listing[0].post.id, first_page[0].post.id);
+  assert_eq!(listing[1].post.id, second_page[0].post.id);
+
+  // Not available without login
+  let listing = PostQuery {
+    listing_type: Some(ListingType::ForYou),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(listing.is_empty());
+
+  Community::delete(pool, related_community.id).await?;
+  Community::delete(pool, disliked_community.id).await?;
+
+  Ok(())
+}
+
 #[test_context(Data)]
 #[tokio::test]
 #[serial]
@@ -2441,3 +2651,157 @@ async fn search(data: &mut Data) -> LemmyResult<()> {
 
   Ok(())
 }
+
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn search_full_text(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  // Stemming matches "tag" to "tags"
+  let search_stemmed = PostQuery {
+    search_term: Some("tag".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&search_stemmed));
+
+  let search_title_only_stemmed = PostQuery {
+    search_term: Some("tag".into()),
+    search_title_only: Some(true),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&search_title_only_stemmed));
+
+  // Quoted phrases, exclusions and `OR`
+  let search_or = PostQuery {
+    search_term: Some("\"with tags\" OR bot -blocked".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(
+    HashSet::from([POST_WITH_TAGS, POST_BY_BOT]),
+    names(&search_or).into_iter().collect::<HashSet<_>>()
+  );
+
+  let search_excluded = PostQuery {
+    search_term: Some("\"with tags\" -body".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(search_excluded.is_empty());
+
+  // Stop words are still found
+  let search_stop_word = PostQuery {
+    search_term: Some("with".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&search_stop_word));
+
+  // The post with the search terms in both title and body is ranked first
+  let search_relevance = PostQuery {
+    search_term: Some("post OR tags".into()),
+    sort_by_relevance: Some(true),
+    limit: Some(2),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_length!(2, search_relevance);
+  assert_eq!(POST_WITH_TAGS, search_relevance[0].post.name);
+  assert!(search_relevance.prev_page.is_none());
+
+  // The next page continues at the offset
+  let search_relevance_2 = PostQuery {
+    search_term: Some("post OR tags".into()),
+    sort_by_relevance: Some(true),
+    limit: Some(2),
+    page_cursor: search_relevance.next_page.clone(),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_length!(2, search_relevance_2);
+  assert!(search_relevance_2.prev_page.is_some());
+  let ids_1 = search_relevance
+    .iter()
+    .map(|p| p.post.id)
+    .collect::<HashSet<_>>();
+  assert!(
+    search_relevance_2
+      .iter()
+      .all(|p| !ids_1.contains(&p.post.id))
+  );
+
+  Ok(())
+}
+
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn search_filters(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  let has_url = PostQuery {
+    has_url: Some(true),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&has_url));
+
+  let url_domain = PostQuery {
+    url_domain: Some("google.com".to_string()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&url_domain));
+
+  // Only subdomains of the given domain match
+  let other_domain = PostQuery {
+    url_domain: Some("gle.com".to_string()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(other_domain.is_empty());
+
+  let instance_domain = PostQuery {
+    instance_domain: Some(data.instance.domain.clone()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(
+    vec![POST_WITH_TAGS, POST_BY_BOT, POST],
+    names(&instance_domain)
+  );
+
+  let published_before = PostQuery {
+    published_before: Some(data.post.published_at),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(!names(&published_before).contains(&POST));
+
+  let published_after = PostQuery {
+    published_after: Some(data.post.published_at),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(names(&published_after).contains(&POST));
+
+  Ok(())
+}
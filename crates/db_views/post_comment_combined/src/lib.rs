@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use lemmy_db_schema::source::{
   comment::{Comment, CommentActions},
   community::{Community, CommunityActions},
+  community_flair::CommunityFlairView,
   community_tag::CommunityTagsView,
   images::ImageDetails,
   person::{Person, PersonActions},
@@ -19,6 +20,7 @@ use {
     comment_select_remove_deletes,
     creator_ban_expires_from_community,
     creator_banned_from_community,
+    creator_community_flair_fragment,
     creator_is_admin,
     creator_is_moderator,
     creator_local_home_community_ban_expires,
@@ -56,6 +58,8 @@ pub struct PostCommentCombinedViewInternal {
   pub item_creator_is_admin: bool,
   #[diesel(select_expression = post_community_tags_fragment())]
   pub tags: CommunityTagsView,
+  #[diesel(select_expression = creator_community_flair_fragment())]
+  pub creator_flair: CommunityFlairView,
   #[diesel(select_expression = local_user_can_mod())]
   pub can_mod: bool,
   #[diesel(select_expression = creator_local_home_community_banned())]
@@ -101,6 +105,7 @@ impl InternalToCombinedView for PostCommentCombinedViewInternal {
         person_actions: v.person_actions,
         creator_is_admin: v.item_creator_is_admin,
         tags: v.tags,
+        creator_flair: v.creator_flair,
         can_mod: v.can_mod,
         creator_banned: v.creator_banned,
         creator_ban_expires_at: v.creator_ban_expires_at,
@@ -119,6 +124,7 @@ impl InternalToCombinedView for PostCommentCombinedViewInternal {
         person_actions: v.person_actions,
         creator_is_admin: v.item_creator_is_admin,
         tags: v.tags,
+        creator_flair: v.creator_flair,
         can_mod: v.can_mod,
         creator_banned: v.creator_banned,
         creator_ban_expires_at: v.creator_ban_expires_at,
@@ -11,6 +11,7 @@ use lemmy_db_schema::{
     PostReportId,
     PrivateMessageId,
     PrivateMessageReportId,
+    RuleId,
   },
 };
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -40,6 +41,8 @@ pub struct ListReports {
   pub show_community_rule_violations: Option<bool>,
   /// If true, view all your created reports. Works for non-admins/mods also.
   pub my_reports_only: Option<bool>,
+  /// Only show post and comment reports which reference this rule.
+  pub rule_id: Option<RuleId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -68,6 +71,9 @@ pub struct CreateCommentReport {
   /// The comment violates rules of the local instance. This report will only be shown to local
   /// admins, not to community mods and will not be federated.
   pub violates_instance_rules: Option<bool>,
+  /// The community or site rule which is violated. Referencing a site rule implies
+  /// `violates_instance_rules`.
+  pub rule_id: Option<RuleId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -89,6 +95,9 @@ pub struct CreatePostReport {
   /// The post violates rules of the local instance. This report will only be shown to local
   /// admins, not to community mods and will not be federated.
   pub violates_instance_rules: Option<bool>,
+  /// The community or site rule which is violated. Referencing a site rule implies
+  /// `violates_instance_rules`.
+  pub rule_id: Option<RuleId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -28,6 +28,7 @@ use lemmy_db_schema::{
     PostId,
     PostReportId,
     PrivateMessageReportId,
+    RuleId,
   },
   source::{
     combined::report::{ReportCombined, report_combined_keys as key},
@@ -202,6 +203,8 @@ pub struct ReportCombinedQuery {
   /// For admins, also show reports with `violates_instance_rules=false`
   pub show_community_rule_violations: Option<bool>,
   pub my_reports_only: Option<bool>,
+  /// Only show post and comment reports which reference this rule
+  pub rule_id: Option<RuleId>,
   pub sort: Option<ReportSortType>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
@@ -241,6 +244,14 @@ impl ReportCombinedQuery {
       query = query.filter(report_combined::report_creator_id.eq(user.person.id));
     }
 
+    if let Some(rule_id) = self.rule_id {
+      query = query.filter(
+        post_report::rule_id
+          .eq(rule_id)
+          .or(comment_report::rule_id.eq(rule_id)),
+      );
+    }
+
     if let Some(type_) = self.type_ {
       query = match type_ {
         ReportType::All => query,
@@ -453,6 +464,7 @@ mod tests {
         PrivateMessageReportForm,
         UpdatePrivateMessageReportForm,
       },
+      rule::{Rule, RuleInsertForm},
     },
     traits::{Bannable, Reportable},
   };
@@ -595,6 +607,7 @@ mod tests {
       original_post_body: None,
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     let inserted_post_report = PostReport::report(pool, &sara_report_post_form).await?;
 
@@ -605,6 +618,7 @@ mod tests {
       original_comment_text: "A test comment rv".into(),
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     CommentReport::report(pool, &sara_report_comment_form).await?;
 
@@ -830,6 +844,7 @@ mod tests {
       original_post_body: None,
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
 
     PostReport::report(pool, &sara_report_form).await?;
@@ -843,6 +858,7 @@ mod tests {
       original_post_body: None,
       reason: "from jessica".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
 
     let inserted_jessica_report = PostReport::report(pool, &jessica_report_form).await?;
@@ -961,6 +977,7 @@ mod tests {
       original_comment_text: "this was it at time of creation".into(),
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
 
     CommentReport::report(pool, &sara_report_form).await?;
@@ -972,6 +989,7 @@ mod tests {
       original_comment_text: "this was it at time of creation".into(),
       reason: "from jessica".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
 
     let inserted_jessica_report = CommentReport::report(pool, &jessica_report_form).await?;
@@ -1172,6 +1190,7 @@ mod tests {
       original_post_body: None,
       reason: "from sara".into(),
       violates_instance_rules: true,
+      rule_id: None,
     };
     PostReport::report(pool, &report_form).await?;
 
@@ -1201,6 +1220,7 @@ mod tests {
       original_comment_text: "this was it at time of creation".into(),
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     let comment_report = CommentReport::report(pool, &report_form).await?;
 
@@ -1246,6 +1266,70 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn filter_by_rule() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let rule_form = RuleInsertForm {
+      ap_id: data.community.ap_id.join("rule/no_spam")?.into(),
+      community_id: Some(data.community.id),
+      title: "No spam".into(),
+      description: None,
+      position: None,
+      deleted: None,
+    };
+    let rule = Rule::create(pool, &rule_form).await?;
+
+    // sara reports the post for breaking the rule, and the comment without a rule
+    let post_report_form = PostReportForm {
+      creator_id: data.sara.id,
+      post_id: data.post.id,
+      original_post_name: "Orig post".into(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "from sara".into(),
+      violates_instance_rules: false,
+      rule_id: Some(rule.id),
+    };
+    let post_report = PostReport::report(pool, &post_report_form).await?;
+    let comment_report_form = CommentReportForm {
+      creator_id: data.sara.id,
+      comment_id: data.comment.id,
+      original_comment_text: "this was it at time of creation".into(),
+      reason: "from sara".into(),
+      violates_instance_rules: false,
+      rule_id: None,
+    };
+    CommentReport::report(pool, &comment_report_form).await?;
+
+    let reports = ReportCombinedQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_length!(2, reports);
+
+    // Only the post report references the rule
+    let reports = ReportCombinedQuery {
+      rule_id: Some(rule.id),
+      ..Default::default()
+    }
+    .list(pool, &data.timmy_view)
+    .await?;
+    assert_length!(1, reports);
+    if let ReportCombinedView::Post(v) = &reports[0] {
+      assert_eq!(post_report.id, v.post_report.id);
+      assert_eq!(Some(rule.id), v.post_report.rule_id);
+    } else {
+      panic!("wrong type");
+    }
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn my_reports_only() -> LemmyResult<()> {
@@ -1260,6 +1344,7 @@ mod tests {
       original_comment_text: "this was it at time of creation".into(),
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     CommentReport::report(pool, &sara_report_form).await?;
 
@@ -1270,6 +1355,7 @@ mod tests {
       original_comment_text: "this was it at time of creation".into(),
       reason: "from timmy".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     CommentReport::report(pool, &timmy_report_form).await?;
 
@@ -1316,6 +1402,7 @@ mod tests {
       original_post_body: None,
       reason: "from sara".into(),
       violates_instance_rules: false,
+      rule_id: None,
     };
     let inserted_sara_report = PostReport::report(pool, &sara_report_form).await?;
 
@@ -1,12 +1,27 @@
-use crate::{ResolveObjectView, SiteView};
+use crate::{FederatedInstanceView, ResolveObjectView, SiteView};
 #[cfg(feature = "full")]
 use activitypub_federation::protocol::helpers::deserialize_skip_error;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
+  SearchSortType,
   SearchType,
-  newtypes::{CommunityId, LanguageId, MultiCommunityId, OAuthProviderId, TaglineId},
+  newtypes::{
+    ActivityId,
+    ApiTokenId,
+    CommunityId,
+    LanguageId,
+    MultiCommunityId,
+    OAuthProviderId,
+    TaglineId,
+    WebhookId,
+  },
   source::{
+    api_token::{ApiToken, ApiTokenScope},
     comment::Comment,
     community::Community,
+    content_import::{ContentImport, ContentImportFailure},
+    data_export::DataExport,
+    images::LocalImage,
     instance::Instance,
     language::Language,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
@@ -17,6 +32,7 @@ use lemmy_db_schema::{
     post::Post,
     private_message::PrivateMessage,
     tagline::Tagline,
+    webhook::Webhook,
   },
 };
 use lemmy_db_schema_file::{
@@ -24,6 +40,7 @@ use lemmy_db_schema_file::{
   PersonId,
   enums::{
     CommentSortType,
+    EmailDigestFrequency,
     FederationMode,
     ImageMode,
     ListingType,
@@ -31,6 +48,7 @@ use lemmy_db_schema_file::{
     PostSortType,
     RegistrationMode,
     VoteShow,
+    WebhookEventType,
   },
 };
 use lemmy_db_views_comment::CommentView;
@@ -40,7 +58,7 @@ use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::PersonView;
 use lemmy_db_views_post::PostView;
-use lemmy_diesel_utils::{pagination::PaginationCursor, sensitive::SensitiveString};
+use lemmy_diesel_utils::{dburl::DbUrl, pagination::PaginationCursor, sensitive::SensitiveString};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use url::Url;
@@ -69,6 +87,77 @@ pub struct AdminBlockInstanceParams {
   pub expires_at: Option<i64>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The outgoing federation queue of an instance.
+pub struct AdminFederationQueueView {
+  pub instance: FederatedInstanceView,
+  /// The next activities in the queue which are sent to this instance.
+  pub pending_activities: Vec<PendingActivity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An activity which is waiting to be sent. The activity content is left out, as it may be
+/// private.
+pub struct PendingActivity {
+  pub id: ActivityId,
+  pub ap_id: DbUrl,
+  pub actor_apub_id: Option<DbUrl>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct AdminListFederationQueues {
+  pub domain_filter: Option<String>,
+  /// Only show instances where sending activities fails, or which are marked dead.
+  pub failing_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Mark an instance as dead, so that no activities are sent to it, or mark it as alive again.
+pub struct AdminMarkInstanceDead {
+  pub instance_id: InstanceId,
+  pub dead: bool,
+  pub reason: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Reset the retry backoff of an instance, so that the failing activity is retried right away.
+///
+/// The federation worker applies this within a minute.
+pub struct AdminResetFederationQueue {
+  pub instance_id: InstanceId,
+  pub reason: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Mark all activities up to and including the given one as sent, for example to skip an activity
+/// which the instance always rejects. Activities which were already sent can't be skipped.
+///
+/// The federation worker applies this within a minute.
+pub struct AdminSkipFederationActivity {
+  pub instance_id: InstanceId,
+  pub activity_id: ActivityId,
+  pub reason: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -147,6 +236,21 @@ pub struct CreateSite {
   pub rate_limit_search_interval_seconds: Option<i32>,
   pub rate_limit_import_user_settings_max_requests: Option<i32>,
   pub rate_limit_import_user_settings_interval_seconds: Option<i32>,
+  pub rate_limit_new_account_max_age_days: Option<i32>,
+  pub rate_limit_new_account_limit_percent: Option<i32>,
+  pub rate_limit_established_account_limit_percent: Option<i32>,
+  pub rate_limit_bot_account_limit_percent: Option<i32>,
+  pub rate_limit_moderator_limit_percent: Option<i32>,
+  pub rate_limit_vote_max_requests: Option<i32>,
+  pub rate_limit_vote_interval_seconds: Option<i32>,
+  pub rate_limit_follow_max_requests: Option<i32>,
+  pub rate_limit_follow_interval_seconds: Option<i32>,
+  pub rate_limit_report_max_requests: Option<i32>,
+  pub rate_limit_report_interval_seconds: Option<i32>,
+  pub rate_limit_private_message_max_requests: Option<i32>,
+  pub rate_limit_private_message_interval_seconds: Option<i32>,
+  pub rate_limit_resolve_object_max_requests: Option<i32>,
+  pub rate_limit_resolve_object_interval_seconds: Option<i32>,
   pub federation_enabled: Option<bool>,
   pub registration_mode: Option<RegistrationMode>,
   pub oauth_registration: Option<bool>,
@@ -265,6 +369,31 @@ pub struct EditSite {
   /// The number of settings imports or exports allowed in a given time frame.
   pub rate_limit_import_user_settings_max_requests: Option<i32>,
   pub rate_limit_import_user_settings_interval_seconds: Option<i32>,
+  /// Accounts younger than this many days use the new account rate limits.
+  pub rate_limit_new_account_max_age_days: Option<i32>,
+  /// The percentage of the above rate limits which is allowed for new accounts.
+  pub rate_limit_new_account_limit_percent: Option<i32>,
+  /// The percentage of the above rate limits which is allowed for established accounts.
+  pub rate_limit_established_account_limit_percent: Option<i32>,
+  /// The percentage of the above rate limits which is allowed for bot accounts.
+  pub rate_limit_bot_account_limit_percent: Option<i32>,
+  /// The percentage of the above rate limits which is allowed for admins and moderators.
+  pub rate_limit_moderator_limit_percent: Option<i32>,
+  /// The number of post, comment and poll votes allowed in a given time frame.
+  pub rate_limit_vote_max_requests: Option<i32>,
+  pub rate_limit_vote_interval_seconds: Option<i32>,
+  /// The number of community follows allowed in a given time frame.
+  pub rate_limit_follow_max_requests: Option<i32>,
+  pub rate_limit_follow_interval_seconds: Option<i32>,
+  /// The number of reports allowed in a given time frame.
+  pub rate_limit_report_max_requests: Option<i32>,
+  pub rate_limit_report_interval_seconds: Option<i32>,
+  /// The number of private messages allowed in a given time frame.
+  pub rate_limit_private_message_max_requests: Option<i32>,
+  pub rate_limit_private_message_interval_seconds: Option<i32>,
+  /// The number of remote object fetches allowed in a given time frame.
+  pub rate_limit_resolve_object_max_requests: Option<i32>,
+  pub rate_limit_resolve_object_interval_seconds: Option<i32>,
   /// Whether to enable federation.
   pub federation_enabled: Option<bool>,
   /// A list of blocked URLs
@@ -319,6 +448,8 @@ pub enum GetFederatedInstancesKind {
   Linked,
   Allowed,
   Blocked,
+  /// Instances where sending activities fails, or which are marked dead.
+  Failing,
 }
 
 #[skip_serializing_none]
@@ -401,6 +532,25 @@ pub struct DeleteAccount {
   pub delete_content: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Set the other accounts which are allowed to move to your account, for example
+/// `name@example.com`. Replaces any existing aliases.
+pub struct SetAccountAliases {
+  pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Move your account to another account, for example `name@example.com`. The new account needs to
+/// list your account as an alias. Followers and community subscriptions are moved to it.
+pub struct MoveAccount {
+  pub target: String,
+  pub password: SensitiveString,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -425,6 +575,61 @@ pub struct ListLoginsResponse {
   pub logins: Vec<LoginToken>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a personal API token, which can be used instead of a login session.
+pub struct CreateApiToken {
+  pub name: String,
+  /// One or more of `read`, `post`, `vote`, `moderate:community/<id>` and `admin`.
+  pub scopes: Vec<ApiTokenScope>,
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CreateApiTokenResponse {
+  pub api_token: ApiToken,
+  /// The token to use in the `Authorization: Bearer` header. It can't be retrieved again.
+  pub token: SensitiveString,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a personal API token. The token itself stays the same.
+pub struct EditApiToken {
+  pub id: ApiTokenId,
+  pub name: Option<String>,
+  pub scopes: Option<Vec<ApiTokenScope>>,
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Revoke a personal API token.
+pub struct DeleteApiToken {
+  pub id: ApiTokenId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ApiTokenResponse {
+  pub api_token: ApiToken,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListApiTokensResponse {
+  pub api_tokens: Vec<ApiToken>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -540,6 +745,9 @@ pub struct SaveUserSettings {
   pub show_media: Option<bool>,
   /// Sends notifications to your email.
   pub send_notifications_to_email: Option<bool>,
+  /// How often to send you an email digest of top posts, unread notifications and, for mods, the
+  /// mod queue.
+  pub email_digest: Option<EmailDigestFrequency>,
   /// Whether this account is a bot account. Users can hide these accounts easily if they wish.
   pub bot_account: Option<bool>,
   /// Whether to show bot accounts.
@@ -657,6 +865,76 @@ pub struct EditTagline {
   pub content: String,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a webhook. Without community it receives events from the whole site, which requires
+/// admin permissions. Otherwise it receives events from the community, and requires mod
+/// permissions.
+pub struct CreateWebhook {
+  pub community_id: Option<CommunityId>,
+  pub url: String,
+  /// Used to sign the request body with HMAC-SHA256, so that the receiver can verify it.
+  pub secret: String,
+  pub events: Vec<WebhookEventType>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a webhook.
+pub struct EditWebhook {
+  pub id: WebhookId,
+  pub url: Option<String>,
+  pub secret: Option<String>,
+  pub events: Option<Vec<WebhookEventType>>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a webhook.
+pub struct DeleteWebhook {
+  pub id: WebhookId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the webhooks of a community, or the site-wide webhooks if no community is given.
+pub struct ListWebhooks {
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListWebhooksResponse {
+  pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct WebhookResponse {
+  pub webhook: Webhook,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches the delivery log of a webhook.
+pub struct ListWebhookDeliveries {
+  pub webhook_id: WebhookId,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "plugins", derive(FromBytes))]
 #[cfg_attr(feature = "plugins", encoding(Json))]
@@ -695,12 +973,19 @@ pub struct ResolveObject {
 pub struct Search {
   /// The search query. Can be a plain text, or an object ID which will be resolved
   /// (eg `https://lemmy.world/comment/1` or `!fediverse@lemmy.ml`).
+  ///
+  /// Plain text can contain these operators: `author:name@instance`, `community:name@instance`,
+  /// `site:instance`, `before:YYYY-MM-DD`, `after:YYYY-MM-DD`, `has:url`, `has:image`,
+  /// `domain:example.com` and `nsfw:yes|no`. Posts, comments and communities are matched with
+  /// full-text search, which supports quoted phrases, `-exclusions` and `OR`.
   pub search_term: String,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
   pub creator_id: Option<PersonId>,
   pub creator_username: Option<String>,
   pub type_: Option<SearchType>,
+  /// The sort order of results. Defaults to new.
+  pub sort: Option<SearchSortType>,
   /// Filter to within a given time range, in seconds.
   /// IE 60 would give results for the past minute.
   pub time_range_seconds: Option<i32>,
@@ -787,6 +1072,9 @@ pub struct UserSettingsBackup {
   pub blocking_keywords: Vec<String>,
   #[serde(default)]
   pub discussion_languages: Vec<String>,
+  /// The account which was exported. On import it is added as an alias of the importing account,
+  /// so that the old account can be moved.
+  pub ap_id: Option<Url>,
 }
 
 #[skip_serializing_none]
@@ -801,6 +1089,32 @@ pub struct ExportDataResponse {
   pub liked: Vec<Url>,
   pub moderates: Vec<Url>,
   pub settings: UserSettingsBackup,
+  /// Saved posts and comments, including their content.
+  pub saved: Vec<PostOrCommentOrPrivateMessage>,
+  pub private_messages_sent: Vec<PrivateMessage>,
+  /// Images uploaded to this instance. The files themselves are only included in the archive.
+  pub uploads: Vec<LocalImage>,
+  pub person: Person,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The state of your most recent data archive. Empty if you never requested one.
+pub struct DataArchiveResponse {
+  pub data_export: Option<DataExport>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The progress of your most recent content import. Empty if you never started one.
+pub struct ContentImportResponse {
+  pub content_import: Option<ContentImport>,
+  /// Items which couldn't be imported, together with the reason.
+  pub failures: Vec<ContentImportFailure>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
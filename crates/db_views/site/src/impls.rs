@@ -4,6 +4,7 @@ use crate::{
   api::{GetFederatedInstances, GetFederatedInstancesKind, UserSettingsBackup},
 };
 use diesel::{
+  BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
   OptionalExtension,
@@ -104,6 +105,7 @@ pub async fn user_backup_list_to_user_settings_backup(
     .collect();
   let vec_into = |vec: Vec<_>| vec.into_iter().map(Into::into).collect();
   Ok(UserSettingsBackup {
+    ap_id: Some(local_user_view.person.ap_id.into()),
     display_name: local_user_view.person.display_name,
     bio: local_user_view.person.bio,
     avatar: local_user_view.person.avatar.map(Into::into),
@@ -160,6 +162,11 @@ impl FederatedInstanceView {
       GetFederatedInstancesKind::Blocked => {
         query.filter(federation_blocklist::instance_id.is_not_null())
       }
+      GetFederatedInstancesKind::Failing => query.filter(
+        federation_queue_state::fail_count
+          .gt(0)
+          .or(federation_queue_state::marked_dead.eq(true)),
+      ),
     };
 
     let mut pq = Self::paginate(query, &data.page_cursor, SortDirection::Desc, pool).await?;
@@ -242,6 +249,9 @@ mod tests {
       last_successful_id: None,
       last_successful_published_time_at: None,
       last_retry_at: None,
+      reset_requested: false,
+      skip_to_id: None,
+      marked_dead: false,
     };
     FederationQueueState::upsert(pool, &queue_state).await?;
 
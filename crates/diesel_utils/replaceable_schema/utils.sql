@@ -15,6 +15,18 @@ CREATE FUNCTION r.controversy_rank (upvotes numeric, downvotes numeric)
     END
     END;
 
+-- Lower bound of the Wilson score confidence interval for the fraction of upvotes, with 80%
+-- confidence (z = 1.281551565545). Items with few votes are ranked lower than items with the
+-- same ratio but more votes.
+CREATE FUNCTION r.best_rank (upvotes numeric, downvotes numeric)
+    RETURNS real
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN CASE WHEN upvotes + downvotes <= 0 THEN
+        0
+    ELSE
+        (upvotes + 0.8211872076 - 1.2815515655 * sqrt(upvotes * downvotes / (upvotes + downvotes) + 0.4105936038)) / (upvotes + downvotes + 1.6423744152)
+    END;
+
 CREATE FUNCTION r.hot_rank (score numeric, published_at timestamp with time zone)
     RETURNS real
     LANGUAGE sql
@@ -45,6 +57,12 @@ CREATE FUNCTION r.scaled_rank (score numeric, published_at timestamp with time z
         r.hot_rank (score, published_at) / log(2 + interactions_month)
 );
 
+-- Weight of the community in the `ForYou` post listing, 0 if it isn't included
+CREATE FUNCTION r.community_affinity (community_id int, community_ids int[], weights real[])
+    RETURNS real
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN coalesce(weights[array_position(community_ids, community_id)], 0);
+
 -- For tables with `deleted` and `removed` columns, this function determines which rows to include in a count.
 CREATE FUNCTION r.is_counted (item record)
     RETURNS bool
@@ -64,6 +82,64 @@ CREATE FUNCTION r.local_url (url_path text)
 current_setting('lemmy.protocol_and_hostname') || url_path
 );
 
+-- Converts a web search query (with support for quoted phrases, `-exclusions` and `OR`) into a
+-- tsquery which matches content indexed with the text search configuration of any of the given
+-- languages. An empty array means all languages, and undetermined language is always included.
+-- Returns null if the query contains no lexemes.
+--
+-- This is only precise enough to find candidates using the search indexes, because exclusions can
+-- be stemmed differently in each configuration. Rows must also match the query of their own
+-- language, see `r.language_search_query`.
+CREATE FUNCTION r.search_query (query text, language_ids int[])
+    RETURNS tsquery
+    LANGUAGE sql
+    STABLE PARALLEL SAFE
+    AS $$
+    SELECT
+        string_agg('(' || q::text || ')', ' | ')::tsquery
+    FROM (
+        SELECT DISTINCT
+            websearch_to_tsquery(language_search_config (id), query) AS q
+        FROM
+            unnest(
+                CASE WHEN cardinality(language_ids) = 0 THEN
+                    ARRAY (
+                        SELECT
+                            id
+                        FROM
+                            LANGUAGE)
+                ELSE
+                    language_ids || 0
+                END) AS id) AS queries
+    WHERE
+        numnode (q) > 0
+$$;
+
+-- Returns the lowercase host of a URL, without user info and port.
+CREATE FUNCTION r.url_host (url text)
+    RETURNS text
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE
+    AS $$
+    SELECT
+        lower(substring(url FROM '^[^:/?#]+://(?:[^/?#@]*@)?(\[[^]]*\]|[^/?#:]*)'))
+$$;
+
+-- Converts a web search query into a tsquery for the text search configuration of a single
+-- language. Returns null if the query contains no lexemes.
+CREATE FUNCTION r.language_search_query (query text, language_id int)
+    RETURNS tsquery
+    LANGUAGE sql
+    STABLE PARALLEL SAFE
+    AS $$
+    SELECT
+        q
+    FROM
+        websearch_to_tsquery(language_search_config (language_id), query) AS q
+    WHERE
+        numnode (q) > 0
+$$;
+
 -- This function creates statement-level triggers for all operation types. It's designed this way
 -- because of these limitations:
 --   * A trigger that uses transition tables can only handle 1 operation type.
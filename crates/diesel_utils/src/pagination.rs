@@ -318,6 +318,59 @@ where
   })
 }
 
+/// The highest offset for [offset_from_cursor], as large offsets are slow to query.
+#[cfg(feature = "full")]
+const MAX_OFFSET: i64 = 10_000;
+
+/// Read the offset from a cursor created by [paginate_response_with_offset]. This is only used for
+/// sort orders which can't be expressed with cursor keys, like full-text search relevance.
+#[cfg(feature = "full")]
+pub fn offset_from_cursor(cursor: &Option<PaginationCursor>) -> LemmyResult<i64> {
+  if let Some(cursor) = cursor {
+    let offset = cursor.clone().into_internal()?.data.plain().parse()?;
+    if !(0..=MAX_OFFSET).contains(&offset) {
+      return Err(LemmyErrorType::CouldntParsePaginationToken.into());
+    }
+    Ok(offset)
+  } else {
+    Ok(0)
+  }
+}
+
+/// Add prev/next cursors to an offset-based query result.
+#[cfg(feature = "full")]
+pub fn paginate_response_with_offset<
+  #[cfg(feature = "ts-rs")] T: ts_rs::TS,
+  #[cfg(not(feature = "ts-rs"))] T,
+>(
+  data: Vec<T>,
+  limit: i64,
+  offset: i64,
+) -> LemmyResult<PagedResponse<T>> {
+  let make_cursor = |offset: i64| {
+    PaginationCursor::from_internal(PaginationCursorInternal {
+      back: false,
+      data: CursorData::new_plain(offset.to_string()),
+      recovery: false,
+    })
+  };
+  let prev_page = if offset > 0 {
+    Some(make_cursor((offset - limit).max(0))?)
+  } else {
+    None
+  };
+  let next_page = if i64::try_from(data.len())? < limit || offset + limit > MAX_OFFSET {
+    None
+  } else {
+    Some(make_cursor(offset + limit)?)
+  };
+  Ok(PagedResponse {
+    items: data,
+    next_page,
+    prev_page,
+  })
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -346,6 +399,27 @@ mod test {
     Ok(())
   }
 
+  #[test]
+  fn test_offset_cursor() -> LemmyResult<()> {
+    let first = paginate_response_with_offset(vec![1, 2], 2, 0)?;
+    assert!(first.prev_page.is_none());
+    assert_eq!(2, offset_from_cursor(&first.next_page)?);
+
+    let last = paginate_response_with_offset(vec![3], 2, 2)?;
+    assert!(last.next_page.is_none());
+    assert_eq!(0, offset_from_cursor(&last.prev_page)?);
+
+    let max = paginate_response_with_offset(vec![1, 2], 2, MAX_OFFSET)?;
+    assert!(max.next_page.is_none());
+    let too_large = PaginationCursor::from_internal(PaginationCursorInternal {
+      back: false,
+      data: CursorData::new_plain((MAX_OFFSET + 1).to_string()),
+      recovery: false,
+    })?;
+    assert!(offset_from_cursor(&Some(too_large)).is_err());
+    Ok(())
+  }
+
   #[test]
   fn test_internal_format() -> LemmyResult<()> {
     assert_eq!(
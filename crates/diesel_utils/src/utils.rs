@@ -219,9 +219,10 @@ pub fn diesel_url_create(opt: Option<&str>) -> LemmyResult<Option<DbUrl>> {
 }
 
 pub mod functions {
+  use super::text_search::{Tsquery, Tsvector};
   use diesel::{
     define_sql_function,
-    sql_types::{Int4, Text, Timestamptz},
+    sql_types::{Array, Float, Int4, Nullable, Text, Timestamptz},
   };
 
   define_sql_function! {
@@ -234,6 +235,11 @@ pub mod functions {
     fn scaled_rank(score: Int4, time: Timestamptz, interactions_month: Int4) -> Float;
   }
 
+  define_sql_function! {
+    #[sql_name = "r.community_affinity"]
+    fn community_affinity(community_id: Int4, community_ids: Array<Int4>, weights: Array<Float>) -> Float;
+  }
+
   define_sql_function!(fn lower(x: Text) -> Text);
 
   define_sql_function!(fn random() -> Text);
@@ -250,7 +256,105 @@ pub mod functions {
 
   define_sql_function!(#[sql_name = "coalesce"] fn coalesce_2_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
 
+  // greatest ignores nulls, and is only null if all arguments are
+  define_sql_function!(#[sql_name = "greatest"] fn greatest_2_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
+
   define_sql_function!(#[sql_name = "coalesce"] fn coalesce_3_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>, z: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
+
+  define_sql_function! {
+    #[sql_name = "r.search_query"]
+    fn search_query(query: Text, language_ids: Array<Int4>) -> Nullable<Tsquery>;
+  }
+
+  define_sql_function! {
+    #[sql_name = "r.language_search_query"]
+    fn language_search_query(query: Text, language_id: Int4) -> Nullable<Tsquery>;
+  }
+
+  define_sql_function! {
+    #[sql_name = "r.url_host"]
+    fn url_host(url: Nullable<Text>) -> Nullable<Text>;
+  }
+
+  define_sql_function!(fn ts_rank(vector: Tsvector, query: Nullable<Tsquery>) -> Nullable<Float>);
+}
+
+/// Full-text search SQL types which are not included in diesel.
+pub mod text_search {
+  use super::functions::search_query;
+  use crate::connection::{DbPool, get_conn};
+  use diesel::{
+    ExpressionMethods,
+    dsl::{select, sql},
+    expression::SqlLiteral,
+  };
+  use diesel_async::RunQueryDsl;
+  use lemmy_utils::error::LemmyResult;
+
+  #[derive(Clone, Copy, diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "tsvector"))]
+  pub struct Tsvector;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "tsquery"))]
+  pub struct Tsquery;
+
+  /// The tables which have a generated `search_vector` column.
+  #[derive(Clone, Copy)]
+  pub enum SearchTable {
+    Post,
+    Comment,
+    Community,
+  }
+
+  /// The generated `search_vector` column of the table. It is left out of the diesel schema, so
+  /// that it isn't loaded along with every row.
+  pub fn search_vector(table: SearchTable) -> SqlLiteral<Tsvector> {
+    sql(match table {
+      SearchTable::Post => "post.search_vector",
+      SearchTable::Comment => "comment.search_vector",
+      SearchTable::Community => "community.search_vector",
+    })
+  }
+
+  /// Only the title part of the `search_vector` column, which has weight `A`. Comments have no
+  /// title, so this is empty for them. This doesn't use the search index, so it should be combined
+  /// with [search_vector].
+  pub fn title_search_vector(table: SearchTable) -> SqlLiteral<Tsvector> {
+    sql(match table {
+      SearchTable::Post => "ts_filter(post.search_vector, '{a}')",
+      SearchTable::Comment => "ts_filter(comment.search_vector, '{a}')",
+      SearchTable::Community => "ts_filter(community.search_vector, '{a}')",
+    })
+  }
+
+  /// Whether the search term contains any lexemes for full-text search in the given languages.
+  /// Search terms which only consist of stop words like "the" result in an empty query, which
+  /// never matches anything.
+  pub async fn has_search_lexemes(
+    pool: &mut DbPool<'_>,
+    search_term: &str,
+    language_ids: Vec<i32>,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    Ok(
+      select(search_query(search_term, language_ids).is_not_null())
+        .get_result(conn)
+        .await?,
+    )
+  }
+}
+
+diesel::infix_operator!(TsMatch, " @@ ", backend: Pg);
+
+/// The full-text search match operator, eg `search_vector(SearchTable::Post) @@ search_query(...)`.
+/// It must be used as an operator, not as a function, so that the search indexes are used.
+pub fn ts_match<V, Q>(vector: V, query: Q) -> TsMatch<V, Q>
+where
+  V: Expression<SqlType = text_search::Tsvector>,
+  Q: Expression<SqlType = sql_types::Nullable<text_search::Tsquery>>,
+{
+  TsMatch::new(vector, query)
 }
 
 pub fn now() -> AsExprOf<diesel::dsl::now, diesel::sql_types::Timestamptz> {
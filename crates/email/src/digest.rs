@@ -0,0 +1,108 @@
+use crate::{send::send_email_now, user_email, user_language};
+use lemmy_db_schema::source::{community::Community, person::Person, post::Post};
+use lemmy_db_schema_file::enums::NotificationType;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::dburl::DbUrl;
+use lemmy_utils::{error::LemmyResult, settings::structs::Settings, utils::markdown::escape_html};
+use std::fmt::Write;
+
+pub struct EmailDigestData<'a> {
+  /// Top posts from the subscribed communities since the last digest.
+  pub posts: Vec<(&'a Post, &'a Community)>,
+  /// Unread replies and mentions.
+  pub notifications: Vec<DigestNotification<'a>>,
+  /// Only for moderators and admins.
+  pub mod_queue: Option<ModQueueCounts>,
+}
+
+pub struct DigestNotification<'a> {
+  pub kind: NotificationType,
+  pub creator: &'a Person,
+  pub link: DbUrl,
+}
+
+pub struct ModQueueCounts {
+  pub reports: i64,
+  pub pending_posts: i64,
+  pub pending_follows: i64,
+}
+
+impl EmailDigestData<'_> {
+  fn is_empty(&self) -> bool {
+    self.posts.is_empty()
+      && self.notifications.is_empty()
+      && self
+        .mod_queue
+        .as_ref()
+        .is_none_or(|m| m.reports == 0 && m.pending_posts == 0 && m.pending_follows == 0)
+  }
+}
+
+/// Send an email digest to the user and wait until it is sent. Nothing is sent if the digest
+/// would be empty.
+pub async fn send_email_digest(
+  local_user_view: &LocalUserView,
+  data: EmailDigestData<'_>,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  if local_user_view.banned || data.is_empty() {
+    return Ok(());
+  }
+
+  let lang = user_language(&local_user_view.local_user);
+  let protocol_and_hostname = settings.get_protocol_and_hostname();
+  let mut body = String::new();
+
+  if !data.posts.is_empty() {
+    write!(body, "<h2>{}</h2><ul>", lang.email_digest_top_posts())?;
+    for (post, community) in data.posts {
+      write!(
+        body,
+        "<li><a href=\"{}\">{}</a> - {}</li>",
+        post.local_url(settings)?,
+        escape_html(&post.name),
+        escape_html(community.title.as_ref().unwrap_or(&community.name))
+      )?;
+    }
+    body.push_str("</ul>");
+  }
+
+  if !data.notifications.is_empty() {
+    write!(body, "<h2>{}</h2><ul>", lang.notifications())?;
+    for n in data.notifications {
+      let title = match n.kind {
+        NotificationType::Mention => lang.mention_from_x(&n.creator.name),
+        _ => lang.reply_from_x(&n.creator.name),
+      };
+      write!(body, "<li><a href=\"{}\">{title}</a></li>", n.link)?;
+    }
+    write!(
+      body,
+      "</ul><a href=\"{protocol_and_hostname}/inbox\">{}</a>",
+      lang.email_digest_open_inbox()
+    )?;
+  }
+
+  if let Some(m) = data.mod_queue {
+    write!(
+      body,
+      "<h2>{}</h2><p>{}</p>",
+      lang.email_digest_mod_queue(),
+      lang.email_digest_mod_queue_counts(m.reports, m.pending_posts, m.pending_follows)
+    )?;
+  }
+
+  let settings_link = format!("{protocol_and_hostname}/settings");
+  write!(body, "<p>{}</p>", lang.email_digest_footer(settings_link))?;
+
+  let subject = lang.email_digest_subject(&settings.hostname);
+  let email = user_email(local_user_view)?;
+  send_email_now(
+    subject,
+    email,
+    local_user_view.person.name.clone(),
+    body,
+    settings,
+  )
+  .await
+}
@@ -656,6 +656,46 @@ fn create_modlog_items(
           ),
           settings,
         ),
+        ModlogKind::ModApprovePost => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Approved post {target_post_name}"),
+          settings,
+        ),
+        ModlogKind::ModRejectPost => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Rejected post {target_post_name}"),
+          settings,
+        ),
+        ModlogKind::ModAutomodPost => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Automod acted on post {target_post_name}"),
+          settings,
+        ),
+        ModlogKind::AdminResetFederationQueue => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Reset federation queue for instance {target_instance_domain}"),
+          settings,
+        ),
+        ModlogKind::AdminSkipFederationActivity => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Skipped federation activities for instance {target_instance_domain}"),
+          settings,
+        ),
+        ModlogKind::AdminMarkInstanceDead => build_modlog_item(
+          r,
+          &modlog_url,
+          if r.modlog.is_revert {
+            format!("Marked instance {target_instance_domain} as alive")
+          } else {
+            format!("Marked instance {target_instance_domain} as dead")
+          },
+          settings,
+        ),
       }
     })
     .collect::<LemmyResult<Vec<Item>>>()?;
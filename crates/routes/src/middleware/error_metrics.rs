@@ -0,0 +1,83 @@
+use actix_web::{
+  Error,
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+};
+use futures_util::future::LocalBoxFuture;
+use lemmy_utils::error::LemmyError;
+use prometheus::{IntCounterVec, Opts, default_registry};
+use std::{
+  future::{Ready, ready},
+  sync::LazyLock,
+};
+
+static API_ERRORS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  #[expect(clippy::expect_used)]
+  create_api_errors_metric().expect("create api error metric")
+});
+
+fn create_api_errors_metric() -> prometheus::Result<IntCounterVec> {
+  let errors = IntCounterVec::new(
+    Opts::new(
+      "lemmy_api_errors_total",
+      "Number of error responses, by error type",
+    ),
+    &["error"],
+  )?;
+  default_registry().register(Box::new(errors.clone()))?;
+  Ok(errors)
+}
+
+/// Counts error responses by their `LemmyErrorType`. Request counts and durations per route are
+/// already recorded by the actix-web-prom middleware.
+pub struct ErrorMetricsMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorMetricsMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type InitError = ();
+  type Transform = ErrorMetricsService<S>;
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(ErrorMetricsService { service }))
+  }
+}
+
+pub struct ErrorMetricsService<S> {
+  service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorMetricsService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let fut = self.service.call(req);
+
+    Box::pin(async move {
+      let res = fut.await?;
+
+      if let Some(error) = res.response().error()
+        && let Some(error) = error.as_error::<LemmyError>()
+      {
+        API_ERRORS
+          .with_label_values(&[error.error_type.to_string()])
+          .inc();
+      }
+      Ok(res)
+    })
+  }
+}
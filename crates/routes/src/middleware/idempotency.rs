@@ -7,15 +7,18 @@ use actix_web::{
   http::Method,
 };
 use futures_util::future::LocalBoxFuture;
-use lemmy_db_schema::newtypes::LocalUserId;
+use lemmy_db_schema::{newtypes::LocalUserId, source::shared_state::IdempotencyKey};
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::connection::ActualDbPool;
 use std::{
   collections::HashSet,
   future::{Ready, ready},
   hash::{Hash, Hasher},
+  rc::Rc,
   sync::{Arc, LazyLock, RwLock},
   time::{Duration, Instant},
 };
+use tracing::warn;
 
 /// https://www.ietf.org/archive/id/draft-ietf-httpapi-idempotency-key-header-01.html
 const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
@@ -67,6 +70,9 @@ impl Hash for Entry {
 #[derive(Clone)]
 pub struct IdempotencySet {
   set: Arc<RwLock<HashSet<Entry>>>,
+  /// If set, keys are stored in the database instead, so that they are shared between multiple
+  /// Lemmy processes. Old keys are deleted by a scheduled task.
+  pool: Option<ActualDbPool>,
 }
 
 impl Default for IdempotencySet {
@@ -89,7 +95,40 @@ impl Default for IdempotencySet {
         lock.shrink_to_fit();
       }
     });
-    Self { set }
+    Self { set, pool: None }
+  }
+}
+
+impl IdempotencySet {
+  pub fn with_database(pool: ActualDbPool) -> Self {
+    Self {
+      pool: Some(pool),
+      ..Default::default()
+    }
+  }
+
+  /// Stores the key, returns false if the same key was already used by this user.
+  #[expect(clippy::expect_used)]
+  async fn insert_new(&self, user_id: LocalUserId, key: String) -> bool {
+    if let Some(pool) = &self.pool {
+      return IdempotencyKey::insert_new(&mut pool.into(), user_id, &key)
+        .await
+        .inspect_err(|e| warn!("Failed to store idempotency key: {e}"))
+        // Let the request through if the database is unavailable
+        .unwrap_or(true);
+    }
+
+    let value = Entry {
+      user_id,
+      key,
+      created: InstantSecs::now(),
+    };
+    if self.set.read().expect("lock failed").contains(&value) {
+      false
+    } else {
+      self.set.write().expect("lock failed").insert(value);
+      true
+    }
   }
 }
 
@@ -105,7 +144,7 @@ impl IdempotencyMiddleware {
 
 impl<S, B> Transform<S, ServiceRequest> for IdempotencyMiddleware
 where
-  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
   S::Future: 'static,
   B: 'static,
 {
@@ -117,20 +156,20 @@ where
 
   fn new_transform(&self, service: S) -> Self::Future {
     ready(Ok(IdempotencyService {
-      service,
+      service: Rc::new(service),
       idempotency_set: self.idempotency_set.clone(),
     }))
   }
 }
 
 pub struct IdempotencyService<S> {
-  service: S,
+  service: Rc<S>,
   idempotency_set: IdempotencySet,
 }
 
 impl<S, B> Service<ServiceRequest> for IdempotencyService<S>
 where
-  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
   S::Future: 'static,
   B: 'static,
 {
@@ -140,7 +179,6 @@ where
 
   forward_ready!(service);
 
-  #[expect(clippy::expect_used)]
   fn call(&self, req: ServiceRequest) -> Self::Future {
     let is_post_or_put = req.method() == Method::POST || req.method() == Method::PUT;
     let idempotency = req
@@ -157,38 +195,22 @@ where
       ext.get().map(|u: &LocalUserView| u.local_user.id)
     };
 
-    if let (Some(key), Some(user_id)) = (idempotency, user_id) {
-      let value = Entry {
-        user_id,
-        key,
-        created: InstantSecs::now(),
-      };
-      if self
-        .idempotency_set
-        .set
-        .read()
-        .expect("lock failed")
-        .contains(&value)
+    let svc = self.service.clone();
+    let idempotency_set = self.idempotency_set.clone();
+
+    Box::pin(async move {
+      if let (Some(key), Some(user_id)) = (idempotency, user_id)
+        && !idempotency_set.insert_new(user_id, key).await
       {
         // Duplicate request, return error
         let (req, _pl) = req.into_parts();
         let response = HttpResponse::UnprocessableEntity()
           .finish()
           .map_into_right_body();
-        return Box::pin(async { Ok(ServiceResponse::new(req, response)) });
-      } else {
-        // New request, store key and continue
-        self
-          .idempotency_set
-          .set
-          .write()
-          .expect("lock failed")
-          .insert(value);
+        return Ok(ServiceResponse::new(req, response));
       }
-    }
-
-    let fut = self.service.call(req);
 
-    Box::pin(async move { fut.await.map(ServiceResponse::map_into_left_body) })
+      svc.call(req).await.map(ServiceResponse::map_into_left_body)
+    })
   }
 }
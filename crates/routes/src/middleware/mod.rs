@@ -1,2 +1,3 @@
+pub mod error_metrics;
 pub mod idempotency;
 pub mod session;
@@ -8,8 +8,14 @@ use actix_web::{
 use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
 use lemmy_api_utils::{
+  api_token::{
+    check_api_token_scope,
+    is_api_token,
+    local_user_view_from_api_token,
+    with_api_token_scopes,
+  },
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{local_user_view_from_jwt, rate_limit_account, read_auth_token},
 };
 use std::{future::ready, rc::Rc};
 
@@ -66,18 +72,39 @@ where
 
     Box::pin(async move {
       let jwt = read_auth_token(req.request())?;
+      let mut api_token_scopes = None;
 
       if let Some(jwt) = &jwt {
         // Ignore any invalid auth so the site can still be used
         // This means it is be impossible to get any error message for invalid jwt. Need
         // to use `/api/v4/account/validate_auth` for that.
-        let local_user_view = local_user_view_from_jwt(jwt, &context).await.ok();
+        let local_user_view = if is_api_token(jwt) {
+          let res = local_user_view_from_api_token(jwt, &context).await.ok();
+          if let Some((_, api_token)) = &res {
+            // Reject calls outside of the token scopes, instead of silently treating them as
+            // unauthenticated.
+            check_api_token_scope(&api_token.scopes, req.method(), req.path())?;
+          }
+          res.map(|(local_user_view, api_token)| {
+            api_token_scopes = Some(api_token.scopes);
+            local_user_view
+          })
+        } else {
+          local_user_view_from_jwt(jwt, &context).await.ok()
+        };
         if let Some(local_user_view) = local_user_view {
+          // Rate limit by account instead of by IP
+          if let Ok(account) = rate_limit_account(&local_user_view, &context).await {
+            req.extensions_mut().insert(account);
+          }
           req.extensions_mut().insert(local_user_view);
         }
       }
 
-      let mut res = svc.call(req).await?;
+      let mut res = match api_token_scopes {
+        Some(scopes) => with_api_token_scopes(scopes, svc.call(req)).await?,
+        None => svc.call(req).await?,
+      };
 
       // Add cache-control header if none is present
       if !res.headers().contains_key(CACHE_CONTROL) {
@@ -1,6 +1,6 @@
 use crate::nodeinfo::{NodeInfo, NodeInfoWellKnown};
 use activitypub_federation::config::Data;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
 use clokwerk::{AsyncScheduler, TimeUnits as CTimeUnits};
 use diesel::{
   BoolExpressionMethods,
@@ -17,47 +17,68 @@ use diesel::{
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use diesel_uplete::uplete;
 use lemmy_api_utils::{
+  automod::run_automod,
   context::LemmyContext,
   plugins::plugin_hook_after,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::send_webmention,
+  utils::{check_community_mod_of_any_or_admin_action, send_webmention},
+  webhooks::{retry_webhook_deliveries, send_webhooks},
 };
 use lemmy_db_schema::{
+  NotificationTypeFilter,
   source::{
     community::Community,
+    data_export::DataExport,
     instance::{Instance, InstanceForm},
-    local_user::LocalUser,
+    local_user::{LocalUser, LocalUserUpdateForm},
     post::{Post, PostUpdateForm},
+    shared_state::{IdempotencyKey, RateLimitBucket},
   },
   utils::DELETED_REPLACEMENT_TEXT,
 };
-use lemmy_db_schema_file::schema::{
-  comment,
-  community,
-  community_actions,
-  federation_blocklist,
-  instance,
-  instance_actions,
-  local_site,
-  local_user,
-  local_user_invite,
-  person,
-  post,
-  received_activity,
-  sent_activity,
-  site,
+use lemmy_db_schema_file::{
+  enums::{EmailDigestFrequency, ListingType, NotificationType, PostSortType, WebhookEventType},
+  schema::{
+    comment,
+    comment_revision,
+    community,
+    community_actions,
+    federation_blocklist,
+    instance,
+    instance_actions,
+    local_site,
+    local_user,
+    local_user_invite,
+    person,
+    post,
+    post_poll,
+    post_revision,
+    received_activity,
+    sent_activity,
+    site,
+  },
 };
+use lemmy_db_views_community_follower_approval::PendingFollowerView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::{NotificationData, impls::NotificationQuery};
+use lemmy_db_views_post::{PostView, impls::PostQuery};
+use lemmy_db_views_report_combined::ReportCombinedViewInternal;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   traits::Crud,
   utils::{functions::coalesce, now},
 };
+use lemmy_email::digest::{DigestNotification, EmailDigestData, ModQueueCounts, send_email_digest};
 use lemmy_utils::{
   DB_BATCH_SIZE,
   error::{LemmyErrorType, LemmyResult},
 };
-use std::time::Duration;
+use prometheus::{GaugeVec, IntCounterVec, Opts, default_registry};
+use std::{
+  sync::LazyLock,
+  time::{Duration, Instant},
+};
 use tracing::{info, warn};
 use url::Url;
 
@@ -66,24 +87,52 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // https://github.com/mdsherry/clokwerk/issues/38
   let mut scheduler = AsyncScheduler::with_tz(Utc);
 
-  // Every 1 minute run plugin hooks
-  scheduler.every(CTimeUnits::minutes(1)).run(async move || {
-    plugin_hook_after("scheduled_task_1_min", &());
+  let context_1 = context.clone();
+  // Every 1 minute retry failed webhook deliveries, delete expired rate limit buckets and
+  // idempotency keys, and run plugin hooks
+  scheduler.every(CTimeUnits::minutes(1)).run(move || {
+    let context = context_1.clone();
+
+    async move {
+      run_task(
+        "retry_webhook_deliveries",
+        retry_webhook_deliveries(&context),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to retry webhook deliveries: {e}"))
+      .ok();
+      run_task(
+        "delete_expired_shared_state",
+        delete_expired_shared_state(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to delete expired shared state: {e}"))
+      .ok();
+      plugin_hook_after("scheduled_task_1_min", &());
+    }
   });
 
   let context_1 = context.clone();
-  // Every 10 minutes update hot ranks, delete expired captchas and publish scheduled posts
+  // Every 10 minutes update hot ranks, delete expired captchas, publish scheduled posts and close
+  // expired polls
   scheduler.every(CTimeUnits::minutes(10)).run(move || {
     let context = context_1.clone();
 
     async move {
-      update_hot_ranks(&mut context.pool())
+      run_task("update_hot_ranks", update_hot_ranks(&mut context.pool()))
         .await
         .inspect_err(|e| warn!("Failed to update hot ranks: {e}"))
         .ok();
-      publish_scheduled_posts(&context)
+      Box::pin(run_task(
+        "publish_scheduled_posts",
+        publish_scheduled_posts(&context),
+      ))
+      .await
+      .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
+      .ok();
+      run_task("close_expired_polls", close_expired_polls(&context))
         .await
-        .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
+        .inspect_err(|e| warn!("Failed to close expired polls: {e}"))
         .ok();
       plugin_hook_after("scheduled_task_10_mins", &());
     }
@@ -95,25 +144,39 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // - Expired bans
   // - Expired instance blocks
   // - Expired invitations
+  // - Email digests
   scheduler.every(CTimeUnits::hour(1)).run(move || {
     let context = context_1.clone();
 
     async move {
-      active_counts(&mut context.pool(), ONE_DAY)
+      run_task("active_counts", active_counts(&mut context.pool(), ONE_DAY))
         .await
         .inspect_err(|e| warn!("Failed to update active counts: {e}"))
         .ok();
-      update_banned_when_expired(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to update expired bans: {e}"))
-        .ok();
-      delete_instance_block_when_expired(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to delete expired instance bans: {e}"))
-        .ok();
-      delete_invitations_when_expired(&mut context.pool())
+      run_task(
+        "update_banned_when_expired",
+        update_banned_when_expired(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to update expired bans: {e}"))
+      .ok();
+      run_task(
+        "delete_instance_block_when_expired",
+        delete_instance_block_when_expired(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to delete expired instance bans: {e}"))
+      .ok();
+      run_task(
+        "delete_invitations_when_expired",
+        delete_invitations_when_expired(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to delete expired invitations: {e}"))
+      .ok();
+      Box::pin(run_task("send_email_digests", send_email_digests(&context)))
         .await
-        .inspect_err(|e| warn!("Failed to delete expired invitations: {e}"))
+        .inspect_err(|e| warn!("Failed to send email digests: {e}"))
         .ok();
       plugin_hook_after("scheduled_task_1_hour", &());
     }
@@ -127,34 +190,57 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // - Delete old denied users
   // - Update instance software
   // - Delete old outgoing activities
+  // - Delete expired data exports
   scheduler.every(CTimeUnits::days(1)).run(move || {
     let context = context_1.reset_request_count();
 
     async move {
-      all_active_counts(&mut context.pool())
+      run_task("all_active_counts", all_active_counts(&mut context.pool()))
         .await
         .inspect_err(|e| warn!("Failed to update active counts: {e}"))
         .ok();
-      update_local_user_count(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to update local user count: {e}"))
-        .ok();
-      overwrite_deleted_posts_and_comments(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to overwrite deleted posts/comments: {e}"))
-        .ok();
-      delete_old_denied_users(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to delete old denied users: {e}"))
-        .ok();
-      update_instance_software(&mut context.pool(), &context)
-        .await
-        .inspect_err(|e| warn!("Failed to update instance software: {e}"))
-        .ok();
-      clear_old_activities(&mut context.pool())
-        .await
-        .inspect_err(|e| warn!("Failed to clear old activities: {e}"))
-        .ok();
+      run_task(
+        "update_local_user_count",
+        update_local_user_count(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to update local user count: {e}"))
+      .ok();
+      run_task(
+        "overwrite_deleted_posts_and_comments",
+        overwrite_deleted_posts_and_comments(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to overwrite deleted posts/comments: {e}"))
+      .ok();
+      run_task(
+        "delete_old_denied_users",
+        delete_old_denied_users(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to delete old denied users: {e}"))
+      .ok();
+      run_task(
+        "update_instance_software",
+        update_instance_software(&mut context.pool(), &context),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to update instance software: {e}"))
+      .ok();
+      run_task(
+        "clear_old_activities",
+        clear_old_activities(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to clear old activities: {e}"))
+      .ok();
+      run_task(
+        "delete_expired_data_exports",
+        delete_expired_data_exports(&mut context.pool()),
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to delete expired data exports: {e}"))
+      .ok();
       plugin_hook_after("scheduled_task_daily", &());
     }
   });
@@ -166,6 +252,67 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   }
 }
 
+struct TaskMetrics {
+  duration: GaugeVec,
+  last_success: GaugeVec,
+  failures: IntCounterVec,
+}
+
+static TASK_METRICS: LazyLock<TaskMetrics> = LazyLock::new(|| {
+  #[expect(clippy::expect_used)]
+  create_task_metrics().expect("create scheduled task metrics")
+});
+
+fn create_task_metrics() -> prometheus::Result<TaskMetrics> {
+  let metrics = TaskMetrics {
+    duration: GaugeVec::new(
+      Opts::new(
+        "lemmy_scheduled_task_duration_seconds",
+        "Duration of the last run of the scheduled task",
+      ),
+      &["task"],
+    )?,
+    last_success: GaugeVec::new(
+      Opts::new(
+        "lemmy_scheduled_task_last_success_timestamp_seconds",
+        "Unix timestamp of the last successful run of the scheduled task",
+      ),
+      &["task"],
+    )?,
+    failures: IntCounterVec::new(
+      Opts::new(
+        "lemmy_scheduled_task_failures_total",
+        "Number of failed runs of the scheduled task",
+      ),
+      &["task"],
+    )?,
+  };
+  let registry = default_registry();
+  registry.register(Box::new(metrics.duration.clone()))?;
+  registry.register(Box::new(metrics.last_success.clone()))?;
+  registry.register(Box::new(metrics.failures.clone()))?;
+  Ok(metrics)
+}
+
+/// Runs a scheduled task and records its duration and result in the Prometheus metrics.
+async fn run_task(name: &str, task: impl Future<Output = LemmyResult<()>>) -> LemmyResult<()> {
+  let start = Instant::now();
+  let res = task.await;
+  let m = &*TASK_METRICS;
+  m.duration
+    .with_label_values(&[name])
+    .set(start.elapsed().as_secs_f64());
+  if res.is_ok() {
+    #[expect(clippy::as_conversions)]
+    m.last_success
+      .with_label_values(&[name])
+      .set(Utc::now().timestamp() as f64);
+  } else {
+    m.failures.with_label_values(&[name]).inc();
+  }
+  res
+}
+
 /// Update the hot_rank columns for the aggregates tables
 /// Runs in batches until all necessary rows are updated once
 async fn update_hot_ranks(pool: &mut DbPool<'_>) -> LemmyResult<()> {
@@ -312,6 +459,17 @@ async fn clear_old_activities(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Delete expired data exports, together with their archives on disk
+async fn delete_expired_data_exports(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  for path in DataExport::delete_expired(pool).await? {
+    tokio::fs::remove_file(&path)
+      .await
+      .inspect_err(|e| warn!("Failed to delete data export {path}: {e}"))
+      .ok();
+  }
+  Ok(())
+}
+
 async fn delete_old_denied_users(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   LocalUser::delete_old_denied_local_users(pool).await?;
   info!("Done.");
@@ -336,6 +494,20 @@ async fn overwrite_deleted_posts_and_comments(pool: &mut DbPool<'_>) -> LemmyRes
   .execute(conn)
   .await?;
 
+  // Also remove the edit history of overwritten posts
+  diesel::delete(
+    post_revision::table.filter(
+      post_revision::post_id.eq_any(
+        post::table
+          .filter(post::deleted.eq(true))
+          .filter(post::updated_at.lt(now().nullable() - 1.months()))
+          .select(post::id),
+      ),
+    ),
+  )
+  .execute(conn)
+  .await?;
+
   info!("Overwriting deleted comments...");
   diesel::update(
     comment::table
@@ -346,6 +518,19 @@ async fn overwrite_deleted_posts_and_comments(pool: &mut DbPool<'_>) -> LemmyRes
   .set(comment::content.eq(DELETED_REPLACEMENT_TEXT))
   .execute(conn)
   .await?;
+
+  diesel::delete(
+    comment_revision::table.filter(
+      comment_revision::comment_id.eq_any(
+        comment::table
+          .filter(comment::deleted.eq(true))
+          .filter(comment::updated_at.lt(now().nullable() - 1.months()))
+          .select(comment::id),
+      ),
+    ),
+  )
+  .execute(conn)
+  .await?;
   info!("Done.");
   Ok(())
 }
@@ -355,6 +540,9 @@ const ONE_WEEK: (&str, &str) = ("1 week", "week");
 const ONE_MONTH: (&str, &str) = ("1 month", "month");
 const SIX_MONTHS: (&str, &str) = ("6 months", "half_year");
 
+/// Maximum number of posts, and of replies and mentions each, in an email digest.
+const EMAIL_DIGEST_LIMIT: i64 = 10;
+
 const ALL_ACTIVE_INTERVALS: [(&str, &str); 4] = [ONE_DAY, ONE_WEEK, ONE_MONTH, SIX_MONTHS];
 
 #[derive(QueryableByName)]
@@ -576,6 +764,13 @@ async fn delete_instance_block_when_expired(pool: &mut DbPool<'_>) -> LemmyResul
   Ok(())
 }
 
+/// Delete rate limit buckets and idempotency keys which are shared between Lemmy processes
+async fn delete_expired_shared_state(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  RateLimitBucket::delete_expired(pool).await?;
+  IdempotencyKey::delete_expired(pool).await?;
+  Ok(())
+}
+
 /// Set invitations to Expired
 async fn delete_invitations_when_expired(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   let conn = &mut get_conn(pool).await?;
@@ -627,6 +822,27 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
     };
     Post::update(&mut context.pool(), post.id, &form).await?;
 
+    // Posts waiting for approval get sent out once they are approved
+    if post.approval_pending {
+      continue;
+    }
+
+    let automod = run_automod(&post, &community, context)
+      .await
+      .inspect_err(|e| warn!("Failed to run automod: {e}"))
+      .unwrap_or_default();
+    if automod.held {
+      continue;
+    }
+    if !automod.removed {
+      send_webhooks(
+        WebhookEventType::PostCreated,
+        Some(post.community_id),
+        &post,
+        context,
+      );
+    }
+
     // send out post via federation and webmention
     let send_activity = SendActivityData::CreatePost(post.clone());
     ActivityChannel::submit_activity(send_activity, context)?;
@@ -635,6 +851,143 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
   Ok(())
 }
 
+/// Close local polls whose end time has passed, and federate the final results. Polls of deleted
+/// or removed posts are left alone, as the update would be federated for a post which is gone.
+async fn close_expired_polls(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+
+  let expired_posts: Vec<_> = post::table
+    .inner_join(post_poll::table)
+    .filter(post::local)
+    .filter(post::deleted.eq(false))
+    .filter(post::removed.eq(false))
+    .filter(post_poll::closed_at.is_null())
+    .filter(post_poll::end_time_at.lt(now().nullable()))
+    .select(Post::as_select())
+    .get_results::<Post>(conn)
+    .await?;
+
+  for post in expired_posts {
+    update(post_poll::table.find(post.id))
+      .set(post_poll::closed_at.eq(now().nullable()))
+      .execute(conn)
+      .await?;
+
+    let send_activity = SendActivityData::UpdatePost(post);
+    ActivityChannel::submit_activity(send_activity, context)?;
+  }
+  Ok(())
+}
+
+/// Send the email digests which are due, with the top posts from subscribed communities, unread
+/// replies and mentions, and the mod queue counts for moderators.
+async fn send_email_digests(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  if site_view.local_site.email_notifications_disabled || context.settings().email.is_none() {
+    return Ok(());
+  }
+
+  let local_users = LocalUserView::list_due_email_digests(&mut context.pool()).await?;
+  info!("Sending {} email digests...", local_users.len());
+  for local_user_view in local_users {
+    // Failed digests are not marked as sent, so that they are retried in the next run
+    if let Err(e) = send_email_digest_for_user(&local_user_view, &site_view, context).await {
+      warn!("Failed to send email digest: {e}");
+      continue;
+    }
+
+    let form = LocalUserUpdateForm {
+      last_email_digest_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    LocalUser::update(&mut context.pool(), local_user_view.local_user.id, &form).await?;
+  }
+  Ok(())
+}
+
+async fn send_email_digest_for_user(
+  local_user_view: &LocalUserView,
+  site_view: &SiteView,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let local_user = &local_user_view.local_user;
+  let person = &local_user_view.person;
+  let since = local_user.last_email_digest_at.unwrap_or_else(|| {
+    if local_user.email_digest == EmailDigestFrequency::Weekly {
+      Utc::now() - TimeDelta::weeks(1)
+    } else {
+      Utc::now() - TimeDelta::days(1)
+    }
+  });
+
+  let posts = PostQuery {
+    listing_type: Some(ListingType::Subscribed),
+    sort: Some(PostSortType::Top),
+    local_user: Some(local_user),
+    published_after: Some(since),
+    limit: Some(EMAIL_DIGEST_LIMIT),
+    ..Default::default()
+  }
+  .list(&mut context.pool(), &site_view.site, &site_view.local_site)
+  .await?
+  .items;
+
+  let mut notifications = vec![];
+  for kind in [NotificationType::Reply, NotificationType::Mention] {
+    let res = NotificationQuery {
+      type_: Some(NotificationTypeFilter::Other(kind)),
+      unread_only: Some(true),
+      show_bot_accounts: Some(local_user.show_bot_accounts),
+      limit: Some(EMAIL_DIGEST_LIMIT),
+      ..Default::default()
+    }
+    .list(&mut context.pool(), person)
+    .await?;
+    notifications.extend(res.items);
+  }
+
+  let mod_queue =
+    if check_community_mod_of_any_or_admin_action(local_user_view, &mut context.pool())
+      .await
+      .is_ok()
+    {
+      Some(ModQueueCounts {
+        reports: ReportCombinedViewInternal::get_report_count(&mut context.pool(), local_user_view)
+          .await?,
+        pending_posts: PostView::count_pending_approval(&mut context.pool(), person.id).await?,
+        pending_follows: PendingFollowerView::count_approval_required(
+          &mut context.pool(),
+          person.id,
+        )
+        .await?,
+      })
+    } else {
+      None
+    };
+
+  let data = EmailDigestData {
+    posts: posts.iter().map(|p| (&p.post, &p.community)).collect(),
+    notifications: notifications
+      .iter()
+      .filter_map(|n| {
+        let (creator, link) = match &n.data {
+          NotificationData::Comment(c) => (&c.creator, c.comment.local_url(context.settings())),
+          NotificationData::Post(p) => (&p.creator, p.post.local_url(context.settings())),
+          _ => return None,
+        };
+        Some(DigestNotification {
+          kind: n.notification.kind,
+          creator,
+          link: link.ok()?.into(),
+        })
+      })
+      .collect(),
+    mod_queue,
+  };
+  send_email_digest(local_user_view, data, context.settings()).await
+}
+
 /// Updates the instance software and version.
 ///
 /// Does so using the /.well-known/nodeinfo protocol described here:
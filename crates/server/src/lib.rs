@@ -14,7 +14,7 @@ use lemmy_api_utils::{
   plugins::LemmyPlugins,
   request::client_builder,
   send_activity::ActivityChannel,
-  utils::local_site_rate_limit_to_rate_limit_config,
+  utils::{local_site_rate_limit_to_rate_limit_config, local_site_rate_limit_to_trust_tier_config},
 };
 use lemmy_apub::{
   FEDERATION_HTTP_FETCH_LIMIT,
@@ -24,12 +24,13 @@ use lemmy_apub::{
 use lemmy_apub_activities::handle_outgoing_activities;
 use lemmy_apub_objects::objects::{community::FETCH_COMMUNITY_COLLECTIONS, instance::ApubSite};
 use lemmy_apub_send::{Opts, SendManager};
-use lemmy_db_schema::source::secret::Secret;
+use lemmy_db_schema::{impls::shared_state::DbRateLimitStore, source::secret::Secret};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::connection::build_db_pool;
 use lemmy_routes::{
   feeds,
   middleware::{
+    error_metrics::ErrorMetricsMiddleware,
     idempotency::{IdempotencyMiddleware, IdempotencySet},
     session::SessionMiddleware,
   },
@@ -47,12 +48,15 @@ use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   rate_limit::RateLimit,
   response::jsonify_plain_text_errors,
-  settings::{SETTINGS, structs::Settings},
+  settings::{
+    SETTINGS,
+    structs::{Settings, SharedStateBackend},
+  },
 };
 use reqwest_middleware::ClientBuilder;
 use reqwest_tracing::TracingMiddleware;
 use serde_json::json;
-use std::{ops::Deref, time::Duration};
+use std::{ops::Deref, sync::Arc, time::Duration};
 use tokio::signal::unix::SignalKind;
 use tracing_actix_web::{DefaultRootSpanBuilder, TracingLogger};
 
@@ -196,7 +200,13 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   // Set up the rate limiter
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
-  let rate_limit_cell = RateLimit::new(rate_limit_config);
+  let mut rate_limit_cell = RateLimit::new(rate_limit_config);
+  if SETTINGS.shared_state == SharedStateBackend::Database {
+    rate_limit_cell = rate_limit_cell.with_shared_store(Arc::new(DbRateLimitStore(pool.clone())));
+  }
+  rate_limit_cell.set_trust_tier_config(local_site_rate_limit_to_trust_tier_config(
+    &site_view.local_site_rate_limit,
+  ));
 
   println!(
     "Starting HTTP server at {}:{}",
@@ -336,7 +346,12 @@ fn create_http_server(
 ) -> LemmyResult<ServerHandle> {
   // These must come before HttpServer creation so they can collect data across threads.
   let prom_api_metrics = new_prometheus_metrics()?;
-  let idempotency_set = IdempotencySet::default();
+  let idempotency_set = match settings.shared_state {
+    SharedStateBackend::Memory => IdempotencySet::default(),
+    SharedStateBackend::Database => {
+      IdempotencySet::with_database(federation_config.inner_pool().clone())
+    }
+  };
 
   // Create Http server
   let bind = (settings.bind, settings.port);
@@ -363,6 +378,10 @@ fn create_http_server(
       .wrap(Condition::new(
         SETTINGS.prometheus.is_some(),
         prom_api_metrics.clone(),
+      ))
+      .wrap(Condition::new(
+        SETTINGS.prometheus.is_some(),
+        ErrorMetricsMiddleware,
       ));
 
     // The routes
@@ -95,6 +95,10 @@ pub enum LemmyErrorType {
   SiteNameLengthOverflow,
   PermissiveRegex,
   InvalidRegex,
+  InvalidSearchOperator(String),
+  /// The search operators can't be applied to the requested search type, eg `has:url` when
+  /// searching for comments.
+  SearchOperatorNotSupported,
   InvalidUrlScheme,
   ContradictingFilters,
   /// Thrown when an API call is submitted with more than 1000 array elements, see
@@ -114,6 +118,46 @@ pub enum LemmyErrorType {
   NotFound,
   PostScheduleTimeMustBeInFuture,
   TooManyScheduledPosts,
+  TooManyDrafts,
+  InvalidPollOptions,
+  InvalidPollVote,
+  PollClosed,
+  InvalidWebhookEvents,
+  InvalidWebhookSecret,
+  InvalidApiTokenName,
+  InvalidApiTokenScopes,
+  /// The API token used for the request lacks the named scope.
+  MissingApiTokenScope(String),
+  /// The action is only allowed with a login session, not with an API token.
+  ApiTokenNotAllowed,
+  InvalidAutomodRuleName,
+  /// An automod rule needs at least one condition and one action, and the data for its actions.
+  InvalidAutomodRule,
+  PostPendingApproval,
+  PostNotPendingApproval,
+  /// Skipping can only move the federation queue forward, not repeat sent activities.
+  ActivityAlreadySent,
+  InvalidFlairText,
+  /// Trust tier rate limit percentages must be positive.
+  InvalidRateLimitPercent,
+  /// The flair can only be assigned by mods.
+  FlairNotSelfAssignable,
+  /// An account can only be moved to an account which lists it in `also_known_as`.
+  AccountMoveAliasMissing,
+  AccountAlreadyMoved,
+  InvalidRuleTitle,
+  /// The rule referenced by a report doesn't belong to the community of the reported item.
+  RuleNotInCommunity,
+  /// The selected mod template doesn't belong to the community of the item.
+  ModTemplateNotInCommunity,
+  /// Another data export of the user is still being built.
+  DataExportInProgress,
+  /// The data export would exceed the maximum archive size.
+  DataExportTooLarge,
+  /// Another content import of the user is still running.
+  ContentImportInProgress,
+  /// The uploaded file is not a valid data archive, or it doesn't contain an `outbox.json`.
+  InvalidDataArchive,
   CannotCombineFederationBlocklistAndAllowlist,
   CouldntParsePaginationToken,
   PluginError(String),
@@ -1,7 +1,13 @@
 //! The content in this file is mostly copy-pasted from library code:
 //! https://github.com/jacob-pro/actix-extensible-rate-limit/blob/master/src/backend/memory.rs
 
-use crate::rate_limit::{ActionType, BucketConfig, input::LemmyInput};
+use crate::rate_limit::{
+  ActionType,
+  BucketConfig,
+  SharedRateLimitStore,
+  TrustTier,
+  input::{LemmyInput, RateLimitKey},
+};
 use actix_extensible_rate_limit::backend::{
   Backend,
   Decision,
@@ -9,6 +15,7 @@ use actix_extensible_rate_limit::backend::{
   memory::DEFAULT_GC_INTERVAL_SECONDS,
 };
 use actix_web::rt::{task::JoinHandle, time::Instant};
+use chrono::Utc;
 use dashmap::DashMap;
 use enum_map::EnumMap;
 use std::{
@@ -16,6 +23,7 @@ use std::{
   sync::{Arc, RwLock},
   time::Duration,
 };
+use tracing::warn;
 
 /// A Fixed Window rate limiter [Backend] that uses [Dashmap](dashmap::DashMap) to store keys
 /// in memory.
@@ -24,6 +32,11 @@ pub struct LemmyBackend {
   map: Arc<DashMap<LemmyInput, Value>>,
   gc_handle: Option<Arc<JoinHandle<()>>>,
   pub(super) configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+  /// Percentage of `max_requests` which is allowed for authenticated users of each trust tier.
+  pub(super) trust_tiers: Arc<RwLock<EnumMap<TrustTier, u32>>>,
+  /// If set, buckets are shared with other Lemmy processes through this store. The local buckets
+  /// are only used as fallback.
+  pub(super) shared_store: Option<Arc<dyn SharedRateLimitStore>>,
 }
 
 struct Value {
@@ -44,9 +57,46 @@ impl LemmyBackend {
       map,
       gc_handle,
       configs: Arc::new(RwLock::new(configs)),
+      trust_tiers: Arc::new(RwLock::new(EnumMap::from_fn(|_| 100))),
+      shared_store: None,
     }
   }
 
+  /// Counts the request in the local bucket, returns the new count and the expiry of the bucket.
+  #[expect(clippy::expect_used)]
+  fn increment_in_memory(
+    &self,
+    input: LemmyInput,
+    now: Instant,
+    interval: Duration,
+  ) -> (u64, Instant) {
+    let mut count = 1;
+    let mut expiry = now
+      .checked_add(interval)
+      .expect("Interval unexpectedly large");
+    self
+      .map
+      .entry(input)
+      .and_modify(|v| {
+        // If this bucket hasn't yet expired, increment and extract the count/expiry
+        if v.ttl > now {
+          v.count += 1;
+          count = v.count;
+          expiry = v.ttl;
+        } else {
+          // If this bucket has expired we will reset the count to 1 and set a new TTL.
+          v.ttl = expiry;
+          v.count = count;
+        }
+      })
+      .or_insert_with(|| Value {
+        // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
+        ttl: expiry,
+        count,
+      });
+    (count, expiry)
+  }
+
   fn garbage_collector(map: Arc<DashMap<LemmyInput, Value>>, interval: Duration) -> JoinHandle<()> {
     assert!(
       interval.as_secs_f64() > 0f64,
@@ -67,7 +117,6 @@ impl Backend<LemmyInput> for LemmyBackend {
   type RollbackToken = LemmyInput;
   type Error = Infallible;
 
-  #[expect(clippy::expect_used)]
   async fn request(
     &self,
     input: LemmyInput,
@@ -75,34 +124,34 @@ impl Backend<LemmyInput> for LemmyBackend {
     #[expect(clippy::expect_used)]
     let config = self.configs.read().expect("read rwlock")[input.1];
 
-    let max_requests: u64 = config.max_requests.into();
+    let mut max_requests: u64 = config.max_requests.into();
+    if let RateLimitKey::Account(_, tier) = input.0 {
+      #[expect(clippy::expect_used)]
+      let percent: u64 = self.trust_tiers.read().expect("read rwlock")[tier].into();
+      max_requests = max_requests * percent / 100;
+    }
     let interval = Duration::from_secs(config.interval.into());
 
     let now = Instant::now();
-    let mut count = 1;
-    let mut expiry = now
-      .checked_add(interval)
-      .expect("Interval unexpectedly large");
-    self
-      .map
-      .entry(input)
-      .and_modify(|v| {
-        // If this bucket hasn't yet expired, increment and extract the count/expiry
-        if v.ttl > now {
-          v.count += 1;
-          count = v.count;
-          expiry = v.ttl;
-        } else {
-          // If this bucket has expired we will reset the count to 1 and set a new TTL.
-          v.ttl = expiry;
-          v.count = count;
+    let (count, expiry) = match &self.shared_store {
+      Some(store) => match store.increment(input.shared_key(), interval).await {
+        Ok((count, expires_at)) => {
+          let expiry = expires_at
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .ok()
+            .and_then(|remaining| now.checked_add(remaining))
+            .unwrap_or(now);
+          (count, expiry)
         }
-      })
-      .or_insert_with(|| Value {
-        // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
-        ttl: expiry,
-        count,
-      });
+        Err(e) => {
+          // Keep rate limiting in memory while the shared store is unavailable
+          warn!("Failed to use shared rate limit store: {e}");
+          self.increment_in_memory(input, now, interval)
+        }
+      },
+      None => self.increment_in_memory(input, now, interval),
+    };
     let allow = count <= max_requests;
     let output = SimpleOutput {
       limit: max_requests,
@@ -113,6 +162,13 @@ impl Backend<LemmyInput> for LemmyBackend {
   }
 
   async fn rollback(&self, token: Self::RollbackToken) -> Result<(), Self::Error> {
+    if let Some(store) = &self.shared_store {
+      store
+        .decrement(token.shared_key())
+        .await
+        .inspect_err(|e| warn!("Failed to use shared rate limit store: {e}"))
+        .ok();
+    }
     self.map.entry(token).and_modify(|v| {
       v.count = v.count.saturating_sub(1);
     });
@@ -170,6 +226,26 @@ mod tests {
           max_requests: 0,
           interval: 0,
         },
+        ActionType::Vote => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
+        ActionType::Follow => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
+        ActionType::Report => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
+        ActionType::PrivateMessage => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
+        ActionType::ResolveObject => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
     }
   }
 
@@ -269,4 +345,36 @@ mod tests {
     assert_eq!(output.remaining, 4);
     Ok(())
   }
+
+  #[actix_web::test]
+  #[expect(clippy::expect_used)]
+  async fn test_trust_tier() -> LemmyResult<()> {
+    tokio::time::pause();
+    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 4), true);
+    backend.trust_tiers.write().expect("write rwlock")[TrustTier::NewAccount] = 50;
+    let new_account = LemmyInput(
+      RateLimitKey::Account(1, TrustTier::NewAccount),
+      ActionType::Message,
+    );
+    let established_account = LemmyInput(
+      RateLimitKey::Account(2, TrustTier::EstablishedAccount),
+      ActionType::Message,
+    );
+    // New accounts only get half of the configured requests
+    for _ in 0..2 {
+      let (decision, _, _) = backend.request(new_account).await?;
+      assert!(decision.is_allowed());
+    }
+    let (decision, output, _) = backend.request(new_account).await?;
+    assert!(decision.is_denied());
+    assert_eq!(output.limit, 2);
+    // Established accounts are limited separately, with the full amount of requests
+    for _ in 0..4 {
+      let (decision, _, _) = backend.request(established_account).await?;
+      assert!(decision.is_allowed());
+    }
+    let (decision, _, _) = backend.request(established_account).await?;
+    assert!(decision.is_denied());
+    Ok(())
+  }
 }
@@ -1,4 +1,4 @@
-use crate::rate_limit::ActionType;
+use crate::rate_limit::{ActionType, TrustTier};
 use std::{
   future::Ready,
   net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -6,10 +6,32 @@ use std::{
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct LemmyInput(pub(crate) RateLimitIpAddr, pub(crate) ActionType);
+pub struct LemmyInput(pub(crate) RateLimitKey, pub(crate) ActionType);
+
+impl LemmyInput {
+  /// Key of the bucket in a shared rate limit store.
+  pub(crate) fn shared_key(&self) -> String {
+    let key = match self.0 {
+      RateLimitKey::Ip(RateLimitIpAddr::V4(addr)) => addr.to_string(),
+      RateLimitKey::Ip(RateLimitIpAddr::V6([a, b, c, d])) => {
+        format!("{a:x}:{b:x}:{c:x}:{d:x}::/64")
+      }
+      RateLimitKey::Account(local_user_id, _) => format!("user/{local_user_id}"),
+    };
+    format!("{}/{key}", self.1)
+  }
+}
 
 pub(crate) type LemmyInputFuture = Ready<Result<LemmyInput, actix_web::Error>>;
 
+/// Authenticated requests are limited per account, so that users behind a shared IP don't throttle
+/// each other. Anonymous requests are limited per IP.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum RateLimitKey {
+  Ip(RateLimitIpAddr),
+  Account(i32, TrustTier),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub(crate) enum RateLimitIpAddr {
   V4(Ipv4Addr),
@@ -31,8 +53,8 @@ impl From<IpAddr> for RateLimitIpAddr {
 }
 
 /// Generate a raw byte key for backend which uses less memory.
-pub(crate) fn raw_ip_key(ip_str: Option<&str>) -> RateLimitIpAddr {
-  parse_ip(ip_str).into()
+pub(crate) fn raw_ip_key(ip_str: Option<&str>) -> RateLimitKey {
+  RateLimitKey::Ip(parse_ip(ip_str).into())
 }
 
 fn parse_ip(addr: Option<&str>) -> IpAddr {
@@ -56,17 +78,33 @@ mod tests {
     // Check that IPv4 addresses are preserved
     assert_eq!(
       raw_ip_key(Some("142.250.187.206")),
-      "142.250.187.206".parse::<IpAddr>()?.into()
+      RateLimitKey::Ip("142.250.187.206".parse::<IpAddr>()?.into())
     );
     // Check that IPv6 addresses are grouped into /64 subnets
     assert_eq!(
       raw_ip_key(Some("2a00:1450:4009:81f::200e")),
-      RateLimitIpAddr::V6([0x2a00, 0x1450, 0x4009, 0x81f])
+      RateLimitKey::Ip(RateLimitIpAddr::V6([0x2a00, 0x1450, 0x4009, 0x81f]))
     );
     assert_eq!(
       raw_ip_key(Some("[2a00:1450:4009:81f::200e]:123")),
-      RateLimitIpAddr::V6([0x2a00, 0x1450, 0x4009, 0x81f])
+      RateLimitKey::Ip(RateLimitIpAddr::V6([0x2a00, 0x1450, 0x4009, 0x81f]))
     );
     Ok(())
   }
+
+  #[test]
+  fn test_shared_key() {
+    let input = LemmyInput(raw_ip_key(Some("142.250.187.206")), ActionType::Post);
+    assert_eq!(input.shared_key(), "Post/142.250.187.206");
+    let input = LemmyInput(
+      raw_ip_key(Some("2a00:1450:4009:81f::200e")),
+      ActionType::Post,
+    );
+    assert_eq!(input.shared_key(), "Post/2a00:1450:4009:81f::/64");
+    let input = LemmyInput(
+      RateLimitKey::Account(5, TrustTier::NewAccount),
+      ActionType::Comment,
+    );
+    assert_eq!(input.shared_key(), "Comment/user/5");
+  }
 }
@@ -1,11 +1,16 @@
-use crate::rate_limit::{
-  backend::LemmyBackend,
-  input::{LemmyInput, LemmyInputFuture, raw_ip_key},
+use crate::{
+  error::LemmyResult,
+  rate_limit::{
+    backend::LemmyBackend,
+    input::{LemmyInput, LemmyInputFuture, RateLimitKey, raw_ip_key},
+  },
 };
 use actix_extensible_rate_limit::{RateLimiter, backend::SimpleOutput};
-use actix_web::dev::ServiceRequest;
+use actix_web::{HttpMessage, dev::ServiceRequest};
+use chrono::{DateTime, Utc};
 use enum_map::{EnumMap, enum_map};
-use std::future::ready;
+use futures::future::BoxFuture;
+use std::{future::ready, sync::Arc, time::Duration};
 use strum::{AsRefStr, Display};
 
 mod backend;
@@ -20,6 +25,29 @@ pub enum ActionType {
   Comment,
   Search,
   ImportUserSettings,
+  Vote,
+  Follow,
+  Report,
+  PrivateMessage,
+  ResolveObject,
+}
+
+/// Authenticated users are rate limited per account, with limits depending on their trust tier.
+#[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr, Eq, PartialEq, Hash)]
+pub enum TrustTier {
+  NewAccount,
+  EstablishedAccount,
+  Bot,
+  /// Admins and community moderators
+  Moderator,
+}
+
+/// Stored in the request extensions for authenticated requests, so that they are rate limited by
+/// account instead of by IP.
+#[derive(Debug, Copy, Clone)]
+pub struct RateLimitAccount {
+  pub local_user_id: i32,
+  pub trust_tier: TrustTier,
 }
 
 #[derive(PartialEq, Debug, Copy, Clone)]
@@ -28,6 +56,21 @@ pub struct BucketConfig {
   pub interval: u32,
 }
 
+/// Storage for rate limit buckets which is shared between multiple Lemmy processes. Without it,
+/// each process keeps its own buckets in memory.
+pub trait SharedRateLimitStore: Send + Sync {
+  /// Counts a request in the bucket for `key`, starting a new bucket if there is none or if it is
+  /// expired. Returns the number of requests in the bucket and the time when it expires.
+  fn increment(
+    &self,
+    key: String,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>>;
+
+  /// Removes a request from the bucket for `key`.
+  fn decrement(&self, key: String) -> BoxFuture<'_, LemmyResult<()>>;
+}
+
 #[derive(Clone)]
 pub struct RateLimit {
   backend: LemmyBackend,
@@ -40,6 +83,12 @@ impl RateLimit {
     }
   }
 
+  /// Shares the rate limit buckets with other Lemmy processes through the given store.
+  pub fn with_shared_store(mut self, store: Arc<dyn SharedRateLimitStore>) -> Self {
+    self.backend.shared_store = Some(store);
+    self
+  }
+
   pub fn with_debug_config() -> Self {
     Self::new(enum_map! {
       ActionType::Message => BucketConfig {
@@ -70,6 +119,26 @@ impl RateLimit {
         max_requests: 1,
         interval: 24 * 60 * 60,
       },
+      ActionType::Vote => BucketConfig {
+        max_requests: 120,
+        interval: 60,
+      },
+      ActionType::Follow => BucketConfig {
+        max_requests: 30,
+        interval: 600,
+      },
+      ActionType::Report => BucketConfig {
+        max_requests: 10,
+        interval: 600,
+      },
+      ActionType::PrivateMessage => BucketConfig {
+        max_requests: 12,
+        interval: 600,
+      },
+      ActionType::ResolveObject => BucketConfig {
+        max_requests: 30,
+        interval: 600,
+      },
     })
   }
 
@@ -78,6 +147,12 @@ impl RateLimit {
     *self.backend.configs.write().expect("write rwlock") = configs;
   }
 
+  /// Sets the percentage of `max_requests` which authenticated users of each tier are allowed.
+  #[expect(clippy::expect_used)]
+  pub fn set_trust_tier_config(&self, trust_tiers: EnumMap<TrustTier, u32>) {
+    *self.backend.trust_tiers.write().expect("write rwlock") = trust_tiers;
+  }
+
   fn build_rate_limiter(
     &self,
     action_type: ActionType,
@@ -135,13 +210,45 @@ impl RateLimit {
   {
     self.build_rate_limiter(ActionType::ImportUserSettings)
   }
+  pub fn vote(
+    &self,
+  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
+  {
+    self.build_rate_limiter(ActionType::Vote)
+  }
+  pub fn follow(
+    &self,
+  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
+  {
+    self.build_rate_limiter(ActionType::Follow)
+  }
+  pub fn report(
+    &self,
+  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
+  {
+    self.build_rate_limiter(ActionType::Report)
+  }
+  pub fn private_message(
+    &self,
+  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
+  {
+    self.build_rate_limiter(ActionType::PrivateMessage)
+  }
+  pub fn resolve_object(
+    &self,
+  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
+  {
+    self.build_rate_limiter(ActionType::ResolveObject)
+  }
 }
 
 fn new_input(action_type: ActionType) -> impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static {
   move |req| {
     ready({
-      let info = req.connection_info();
-      let key = raw_ip_key(info.realip_remote_addr());
+      let key = match req.extensions().get::<RateLimitAccount>() {
+        Some(account) => RateLimitKey::Account(account.local_user_id, account.trust_tier),
+        None => raw_ip_key(req.connection_info().realip_remote_addr()),
+      };
 
       Ok(LemmyInput(key, action_type))
     })
@@ -14,6 +14,10 @@ use url::Url;
 pub struct Settings {
   /// settings related to the postgresql database
   pub database: DatabaseConfig,
+  /// Where to store rate limit buckets and idempotency keys. Set this to `database` when running
+  /// multiple Lemmy processes behind a load balancer, so that they all share the same state.
+  #[doku(as = "String", example = "memory")]
+  pub shared_state: SharedStateBackend,
   /// Pictrs image server configuration.
   #[default(Some(Default::default()))]
   pub(crate) pictrs: Option<PictrsConfig>,
@@ -54,6 +58,12 @@ pub struct Settings {
   pub json_logging: bool,
   /// Data for loading Lemmy plugins
   pub plugins: Vec<PluginSettings>,
+  /// Directory where user data exports are stored until they expire after a week. When running
+  /// multiple Lemmy processes, this must be storage which is shared between all of them, as the
+  /// download may be handled by a different process than the one which created the export.
+  #[default("data_exports")]
+  #[doku(example = "/var/lib/lemmy/data_exports")]
+  pub data_export_dir: String,
 }
 
 impl Settings {
@@ -97,6 +107,16 @@ pub struct DatabaseConfig {
   pub pool_size: usize,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Document)]
+#[serde(rename_all = "lowercase")]
+pub enum SharedStateBackend {
+  /// Keep the state in memory of the current process
+  #[default]
+  Memory,
+  /// Keep the state in unlogged tables of the PostgreSQL database
+  Database,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Document, SmartDefault)]
 #[serde(default, deny_unknown_fields)]
 pub struct EmailConfig {
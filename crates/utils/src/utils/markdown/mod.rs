@@ -32,6 +32,11 @@ pub fn markdown_to_html(text: &str) -> String {
   MARKDOWN_PARSER.parse(text).xrender()
 }
 
+/// Escapes plain text like post titles, so that it can be included in HTML.
+pub fn escape_html(text: &str) -> String {
+  markdown_it::common::utils::escape_html(text).into_owned()
+}
+
 pub fn markdown_check_for_blocked_urls(text: &str, blocklist: &RegexSet) -> LemmyResult<()> {
   if blocklist.is_match(text) {
     return Err(LemmyErrorType::BlockedUrl.into());
@@ -2,7 +2,7 @@ use crate::error::{LemmyErrorExt, LemmyErrorType, LemmyResult, MAX_API_PARAM_ELE
 use invisible_characters::INVISIBLE_CHARS;
 use itertools::Itertools;
 use regex::{Regex, RegexBuilder, RegexSet};
-use std::sync::LazyLock;
+use std::{collections::HashSet, sync::LazyLock};
 use unicode_segmentation::UnicodeSegmentation;
 use url::{ParseError, Url};
 
@@ -27,6 +27,15 @@ const MIN_LENGTH_BLOCKING_KEYWORD: usize = 3;
 const MAX_LENGTH_BLOCKING_KEYWORD: usize = 50;
 const ACTOR_NAME_MAX_LENGTH: usize = 20;
 pub const DISPLAY_NAME_MAX_LENGTH: usize = 50;
+const POLL_OPTIONS_MAX: usize = 20;
+const POLL_OPTION_MAX_LENGTH: usize = 200;
+const WEBHOOK_SECRET_MIN_LENGTH: usize = 16;
+const WEBHOOK_SECRET_MAX_LENGTH: usize = 200;
+const API_TOKEN_NAME_MAX_LENGTH: usize = 50;
+const AUTOMOD_RULE_NAME_MAX_LENGTH: usize = 100;
+const POST_TITLE_MAX_LENGTH: usize = 200;
+const FLAIR_TEXT_MAX_LENGTH: usize = 64;
+const RULE_TITLE_MAX_LENGTH: usize = 200;
 
 fn has_newline(name: &str) -> bool {
   name.contains('\n')
@@ -100,6 +109,22 @@ pub fn is_valid_post_title(title: &str) -> LemmyResult<()> {
   }
 }
 
+/// A poll needs at least two options, and option names must be unique.
+pub fn is_valid_poll_options(options: &[String]) -> LemmyResult<()> {
+  let unique = options.iter().map(|o| o.trim()).collect::<HashSet<_>>();
+  let check = (2..=POLL_OPTIONS_MAX).contains(&options.len())
+    && unique.len() == options.len()
+    && options.iter().all(|o| {
+      let length = o.trim().chars().count();
+      (1..=POLL_OPTION_MAX_LENGTH).contains(&length) && !has_newline(o)
+    });
+  if !check {
+    Err(LemmyErrorType::InvalidPollOptions.into())
+  } else {
+    Ok(())
+  }
+}
+
 /// This could be post bodies, comments, notes, or any description field
 pub fn is_valid_body_field(body: &str, post: bool) -> LemmyResult<()> {
   if post {
@@ -207,6 +232,87 @@ pub fn is_valid_url(url: &Url) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Webhooks are sent with plain HTTP requests, so other url schemes are not allowed.
+pub fn is_valid_webhook_url(url: &Url) -> LemmyResult<()> {
+  if !["http", "https"].contains(&url.scheme()) {
+    return Err(LemmyErrorType::InvalidUrlScheme.into());
+  }
+  is_valid_url(url)
+}
+
+pub fn is_valid_webhook_secret(secret: &str) -> LemmyResult<()> {
+  min_length_check(
+    secret,
+    WEBHOOK_SECRET_MIN_LENGTH,
+    LemmyErrorType::InvalidWebhookSecret,
+  )?;
+  max_length_check(
+    secret,
+    WEBHOOK_SECRET_MAX_LENGTH,
+    LemmyErrorType::InvalidWebhookSecret,
+  )
+}
+
+pub fn is_valid_api_token_name(name: &str) -> LemmyResult<()> {
+  min_length_check(name.trim(), 1, LemmyErrorType::InvalidApiTokenName)?;
+  max_length_check(
+    name,
+    API_TOKEN_NAME_MAX_LENGTH,
+    LemmyErrorType::InvalidApiTokenName,
+  )
+}
+
+pub fn is_valid_automod_rule_name(name: &str) -> LemmyResult<()> {
+  min_length_check(name.trim(), 1, LemmyErrorType::InvalidAutomodRuleName)?;
+  max_length_check(
+    name,
+    AUTOMOD_RULE_NAME_MAX_LENGTH,
+    LemmyErrorType::InvalidAutomodRuleName,
+  )
+}
+
+pub fn is_valid_flair_text(text: &str) -> LemmyResult<()> {
+  min_length_check(text.trim(), 1, LemmyErrorType::InvalidFlairText)?;
+  max_length_check(
+    text,
+    FLAIR_TEXT_MAX_LENGTH,
+    LemmyErrorType::InvalidFlairText,
+  )?;
+  if has_newline(text) {
+    Err(LemmyErrorType::InvalidFlairText.into())
+  } else {
+    Ok(())
+  }
+}
+
+pub fn is_valid_rule_title(title: &str) -> LemmyResult<()> {
+  min_length_check(title.trim(), 1, LemmyErrorType::InvalidRuleTitle)?;
+  max_length_check(
+    title,
+    RULE_TITLE_MAX_LENGTH,
+    LemmyErrorType::InvalidRuleTitle,
+  )?;
+  if has_newline(title) {
+    Err(LemmyErrorType::InvalidRuleTitle.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Drafts may be incomplete, so only the maximum lengths are checked here. The full validation
+/// happens when the draft gets published.
+pub fn is_valid_post_draft(name: &str, url: Option<&str>) -> LemmyResult<()> {
+  max_length_check(
+    name,
+    POST_TITLE_MAX_LENGTH,
+    LemmyErrorType::InvalidPostTitle,
+  )?;
+  if let Some(url) = url {
+    max_length_check(url, URL_MAX_LENGTH, LemmyErrorType::UrlLengthOverflow)?;
+  }
+  Ok(())
+}
+
 pub fn is_url_blocked(url: &Url, blocklist: &RegexSet) -> LemmyResult<()> {
   if blocklist.is_match(url.as_str()) {
     return Err(LemmyErrorType::BlockedUrl.into());
@@ -342,11 +448,19 @@ mod tests {
       check_urls_are_valid,
       is_url_blocked,
       is_valid_actor_name,
+      is_valid_api_token_name,
+      is_valid_automod_rule_name,
       is_valid_bio_field,
       is_valid_display_name,
+      is_valid_flair_text,
       is_valid_matrix_id,
+      is_valid_poll_options,
+      is_valid_post_draft,
       is_valid_post_title,
+      is_valid_rule_title,
       is_valid_url,
+      is_valid_webhook_secret,
+      is_valid_webhook_url,
       site_name_length_check,
       summary_length_check,
       truncate_for_db,
@@ -424,6 +538,64 @@ Line3",
     assert!(is_valid_post_title("\u{1f3f3}\u{fe0f}\u{200d}\u{26a7}\u{fe0f}").is_ok());
   }
 
+  #[test]
+  fn test_valid_poll_options() {
+    let options = |o: &[&str]| o.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(is_valid_poll_options(&options(&["Yes", "No"])).is_ok());
+    assert!(is_valid_poll_options(&options(&["Yes"])).is_err());
+    assert!(is_valid_poll_options(&options(&["Yes", "Yes "])).is_err());
+    assert!(is_valid_poll_options(&options(&["Yes", " "])).is_err());
+    assert!(is_valid_poll_options(&options(&["Yes", "No\nMaybe"])).is_err());
+    assert!(is_valid_poll_options(&options(&["x"; 21])).is_err());
+  }
+
+  #[test]
+  fn test_valid_webhook() -> LemmyResult<()> {
+    assert!(is_valid_webhook_url(&Url::parse("https://example.com/hook")?).is_ok());
+    assert!(is_valid_webhook_url(&Url::parse("magnet:?xt=urn:btih:123")?).is_err());
+    assert!(is_valid_webhook_secret("0123456789abcdef").is_ok());
+    assert!(is_valid_webhook_secret("too short").is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_valid_api_token_name() {
+    assert!(is_valid_api_token_name("my bot").is_ok());
+    assert!(is_valid_api_token_name(" ").is_err());
+    assert!(is_valid_api_token_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_valid_automod_rule_name() {
+    assert!(is_valid_automod_rule_name("Link spam").is_ok());
+    assert!(is_valid_automod_rule_name("").is_err());
+    assert!(is_valid_automod_rule_name(&"a".repeat(101)).is_err());
+  }
+
+  #[test]
+  fn test_valid_flair_text() {
+    assert!(is_valid_flair_text("Verified expert").is_ok());
+    assert!(is_valid_flair_text(" ").is_err());
+    assert!(is_valid_flair_text("two\nlines").is_err());
+    assert!(is_valid_flair_text(&"a".repeat(65)).is_err());
+  }
+
+  #[test]
+  fn test_valid_rule_title() {
+    assert!(is_valid_rule_title("No spam").is_ok());
+    assert!(is_valid_rule_title(" ").is_err());
+    assert!(is_valid_rule_title("two\nlines").is_err());
+    assert!(is_valid_rule_title(&"a".repeat(201)).is_err());
+  }
+
+  #[test]
+  fn test_valid_post_draft() {
+    assert!(is_valid_post_draft("", None).is_ok());
+    assert!(is_valid_post_draft("A title", Some("https://example.com")).is_ok());
+    assert!(is_valid_post_draft(&"a".repeat(201), None).is_err());
+    assert!(is_valid_post_draft("A title", Some(&"a".repeat(2001))).is_err());
+  }
+
   #[test]
   fn test_valid_matrix_id() {
     assert!(is_valid_matrix_id("@dess:matrix.org").is_ok());
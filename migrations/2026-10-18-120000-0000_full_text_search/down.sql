@@ -0,0 +1,11 @@
+ALTER TABLE post
+    DROP COLUMN search_vector;
+
+ALTER TABLE comment
+    DROP COLUMN search_vector;
+
+ALTER TABLE community
+    DROP COLUMN search_vector;
+
+DROP FUNCTION language_search_config;
+
@@ -0,0 +1,95 @@
+-- Full-text search for posts, comments and communities, using generated search_vector columns
+-- with GIN indexes. The columns are left out of the diesel schema by
+-- crates/db_schema_file/diesel_ltree.patch, so that they aren't read along with every row.
+--
+-- Maps a language id to the matching text search configuration, for stemming and stop words.
+-- Languages without a specific configuration use 'simple'.
+CREATE FUNCTION language_search_config (language_id int)
+    RETURNS regconfig
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE
+    AS $$
+    SELECT
+        CASE language_id
+        WHEN 8 THEN
+            'arabic'::regconfig
+        WHEN 22 THEN
+            'catalan'::regconfig
+        WHEN 31 THEN
+            'danish'::regconfig
+        WHEN 32 THEN
+            'german'::regconfig
+        WHEN 36 THEN
+            'greek'::regconfig
+        WHEN 37 THEN
+            'english'::regconfig
+        WHEN 39 THEN
+            'spanish'::regconfig
+        WHEN 41 THEN
+            'basque'::regconfig
+        WHEN 44 THEN
+            'finnish'::regconfig
+        WHEN 47 THEN
+            'french'::regconfig
+        WHEN 49 THEN
+            'irish'::regconfig
+        WHEN 56 THEN
+            'hindi'::regconfig
+        WHEN 60 THEN
+            'hungarian'::regconfig
+        WHEN 61 THEN
+            'armenian'::regconfig
+        WHEN 64 THEN
+            'indonesian'::regconfig
+        WHEN 71 THEN
+            'italian'::regconfig
+        WHEN 96 THEN
+            'lithuanian'::regconfig
+        WHEN 110 THEN
+            'norwegian'::regconfig
+        WHEN 112 THEN
+            'nepali'::regconfig
+        WHEN 114 THEN
+            'dutch'::regconfig
+        WHEN 115 THEN
+            'norwegian'::regconfig
+        WHEN 116 THEN
+            'norwegian'::regconfig
+        WHEN 129 THEN
+            'portuguese'::regconfig
+        WHEN 133 THEN
+            'romanian'::regconfig
+        WHEN 134 THEN
+            'russian'::regconfig
+        WHEN 147 THEN
+            'serbian'::regconfig
+        WHEN 151 THEN
+            'swedish'::regconfig
+        WHEN 153 THEN
+            'tamil'::regconfig
+        WHEN 162 THEN
+            'turkish'::regconfig
+        WHEN 177 THEN
+            'yiddish'::regconfig
+        ELSE
+            'simple'::regconfig
+        END
+$$;
+
+-- The post title has a higher weight than the body, which is used for relevance ranking.
+ALTER TABLE post
+    ADD COLUMN search_vector tsvector NOT NULL GENERATED ALWAYS AS (setweight(to_tsvector(language_search_config (language_id), name), 'A') || setweight(to_tsvector(language_search_config (language_id), coalesce(body, '')), 'B')) STORED;
+
+ALTER TABLE comment
+    ADD COLUMN search_vector tsvector NOT NULL GENERATED ALWAYS AS (to_tsvector(language_search_config (language_id), content)) STORED;
+
+-- Communities can have multiple languages, so they always use the 'simple' configuration.
+ALTER TABLE community
+    ADD COLUMN search_vector tsvector NOT NULL GENERATED ALWAYS AS (setweight(to_tsvector('simple', coalesce(title, '')), 'A') || setweight(to_tsvector('simple', coalesce(summary, '')), 'B') || setweight(to_tsvector('simple', coalesce(sidebar, '')), 'C')) STORED;
+
+CREATE INDEX idx_post_search ON post USING gin (search_vector);
+
+CREATE INDEX idx_comment_search ON comment USING gin (search_vector);
+
+CREATE INDEX idx_community_search ON community USING gin (search_vector);
+
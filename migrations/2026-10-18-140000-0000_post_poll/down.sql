@@ -0,0 +1,6 @@
+DROP TABLE post_poll_vote;
+
+DROP TABLE post_poll_option;
+
+DROP TABLE post_poll;
+
@@ -0,0 +1,38 @@
+-- Poll settings for posts which are polls. Federated as ActivityPub `Question`.
+CREATE TABLE post_poll (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    multiple_choice boolean NOT NULL DEFAULT FALSE,
+    end_time_at timestamptz,
+    hide_results_until_closed boolean NOT NULL DEFAULT FALSE,
+    closed_at timestamptz,
+    voter_count int NOT NULL DEFAULT 0,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+-- Options are identified by name in federation, so names must be unique per poll.
+CREATE TABLE post_poll_option (
+    id serial PRIMARY KEY,
+    post_id int NOT NULL REFERENCES post_poll ON UPDATE CASCADE ON DELETE CASCADE,
+    name varchar(200) NOT NULL,
+    vote_count int NOT NULL DEFAULT 0,
+    UNIQUE (post_id, name)
+);
+
+CREATE TABLE post_poll_vote (
+    post_poll_option_id int NOT NULL REFERENCES post_poll_option ON UPDATE CASCADE ON DELETE CASCADE,
+    person_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    post_id int NOT NULL REFERENCES post_poll ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_id, post_poll_option_id)
+);
+
+CREATE INDEX idx_post_poll_vote_post_person ON post_poll_vote (post_id, person_id);
+
+CREATE INDEX idx_post_poll_vote_option ON post_poll_vote (post_poll_option_id);
+
+-- For closing expired polls in the scheduled task
+CREATE INDEX idx_post_poll_end_time_open ON post_poll (end_time_at)
+WHERE
+    closed_at IS NULL;
+
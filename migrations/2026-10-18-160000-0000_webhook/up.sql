@@ -0,0 +1,43 @@
+CREATE TYPE webhook_event_type_enum AS enum (
+    'PostCreated',
+    'ReportCreated',
+    'RegistrationApplicationCreated',
+    'ModAction'
+);
+
+-- Outgoing webhooks, registered by admins for the whole site (community_id is null) or by mods
+-- for a single community.
+CREATE TABLE webhook (
+    id serial PRIMARY KEY,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    url text NOT NULL,
+    secret text NOT NULL,
+    events webhook_event_type_enum[] NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_webhook_community ON webhook (community_id);
+
+-- The delivery log. A delivery is retried while next_attempt_at is set.
+CREATE TABLE webhook_delivery (
+    id serial PRIMARY KEY,
+    webhook_id int NOT NULL REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE,
+    event webhook_event_type_enum NOT NULL,
+    payload text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    status_code int,
+    error text,
+    delivered_at timestamptz,
+    next_attempt_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_webhook_delivery_webhook ON webhook_delivery (webhook_id, published_at DESC);
+
+CREATE INDEX idx_webhook_delivery_next_attempt ON webhook_delivery (next_attempt_at)
+WHERE
+    next_attempt_at IS NOT NULL;
+
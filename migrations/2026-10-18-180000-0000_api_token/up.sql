@@ -0,0 +1,15 @@
+-- Named personal API tokens, which are separate from login sessions and limited to the given
+-- scopes. Only a hash of the token is stored, the token itself is shown once on creation.
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    token_hash text NOT NULL UNIQUE,
+    scopes text[] NOT NULL,
+    expires_at timestamptz,
+    last_used_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_api_token_local_user ON api_token (local_user_id);
+
@@ -0,0 +1,2 @@
+DROP TABLE post_revision, comment_revision;
+
@@ -0,0 +1,23 @@
+-- Previous versions of posts and comments, saved when they are edited. published_at is the time
+-- when that version was published, which is the creation or previous edit time.
+CREATE TABLE post_revision (
+    id serial PRIMARY KEY,
+    post_id int NOT NULL REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    body text,
+    url text,
+    nsfw boolean NOT NULL DEFAULT FALSE,
+    published_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_post_revision_post ON post_revision (post_id, published_at DESC);
+
+CREATE TABLE comment_revision (
+    id serial PRIMARY KEY,
+    comment_id int NOT NULL REFERENCES COMMENT ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL,
+    published_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_comment_revision_comment ON comment_revision (comment_id, published_at DESC);
+
@@ -0,0 +1,32 @@
+-- Unfinished posts and comments of local users. Drafts are never federated, and they are kept
+-- separate from post and comment so that they don't show up in listings or counts.
+CREATE TABLE post_draft (
+    id serial PRIMARY KEY,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL DEFAULT '',
+    url text,
+    body text,
+    alt_text text,
+    nsfw boolean,
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE SET NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_post_draft_creator ON post_draft (creator_id);
+
+CREATE TABLE comment_draft (
+    id serial PRIMARY KEY,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    post_id int NOT NULL REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    parent_id int REFERENCES COMMENT ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL DEFAULT '',
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE SET NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_comment_draft_creator ON comment_draft (creator_id);
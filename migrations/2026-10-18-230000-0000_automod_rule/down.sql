@@ -0,0 +1,3 @@
+DROP TABLE automod_rule;
+
+DROP TYPE automod_action_enum;
@@ -0,0 +1,31 @@
+CREATE TYPE automod_action_enum AS enum (
+    'Remove',
+    'Lock',
+    'Report',
+    'HoldForApproval',
+    'ApplyTag',
+    'Reply'
+);
+
+-- Automod rules of a community. A rule matches a new post if all of its conditions match, and then
+-- takes all of its actions.
+CREATE TABLE automod_rule (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    title_regex text,
+    body_regex text,
+    url_regex text,
+    domains text[] NOT NULL DEFAULT '{}',
+    language_ids int[] NOT NULL DEFAULT '{}',
+    max_account_age_days int,
+    max_person_post_score int,
+    actions automod_action_enum[] NOT NULL,
+    community_tag_id int REFERENCES community_tag ON UPDATE CASCADE ON DELETE SET NULL,
+    reply text,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_automod_rule_community ON automod_rule (community_id);
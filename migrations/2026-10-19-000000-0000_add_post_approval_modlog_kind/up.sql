@@ -0,0 +1,5 @@
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModApprovePost';
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModRejectPost';
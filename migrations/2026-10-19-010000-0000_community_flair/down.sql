@@ -0,0 +1,4 @@
+ALTER TABLE community_actions
+    DROP COLUMN flair_id;
+
+DROP TABLE community_flair;
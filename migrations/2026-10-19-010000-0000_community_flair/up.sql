@@ -0,0 +1,21 @@
+-- User flairs which are created by community moderators. Users can pick a self assignable flair,
+-- or mods can assign any flair to them.
+CREATE TABLE community_flair (
+    id serial PRIMARY KEY,
+    ap_id text NOT NULL UNIQUE,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    text text NOT NULL,
+    color tag_color_enum NOT NULL DEFAULT 'color01',
+    custom_emoji_id int REFERENCES custom_emoji ON UPDATE CASCADE ON DELETE SET NULL,
+    self_assignable boolean NOT NULL DEFAULT FALSE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    deleted boolean NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX idx_community_flair_community ON community_flair (community_id);
+
+ALTER TABLE community_actions
+    ADD COLUMN flair_id int REFERENCES community_flair ON UPDATE CASCADE ON DELETE SET NULL;
+
+CREATE INDEX idx_community_actions_flair ON community_actions (flair_id);
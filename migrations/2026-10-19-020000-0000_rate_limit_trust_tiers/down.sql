@@ -0,0 +1,6 @@
+ALTER TABLE local_site_rate_limit
+    DROP COLUMN new_account_max_age_days,
+    DROP COLUMN new_account_limit_percent,
+    DROP COLUMN established_account_limit_percent,
+    DROP COLUMN bot_account_limit_percent,
+    DROP COLUMN moderator_limit_percent;
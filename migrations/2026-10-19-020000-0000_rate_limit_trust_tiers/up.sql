@@ -0,0 +1,6 @@
+ALTER TABLE local_site_rate_limit
+    ADD COLUMN new_account_max_age_days int NOT NULL DEFAULT 7,
+    ADD COLUMN new_account_limit_percent int NOT NULL DEFAULT 50,
+    ADD COLUMN established_account_limit_percent int NOT NULL DEFAULT 100,
+    ADD COLUMN bot_account_limit_percent int NOT NULL DEFAULT 100,
+    ADD COLUMN moderator_limit_percent int NOT NULL DEFAULT 200;
@@ -0,0 +1,18 @@
+-- Rate limit buckets and idempotency keys which are shared between multiple Lemmy processes. The
+-- tables are unlogged because losing their content on a crash is harmless.
+CREATE UNLOGGED TABLE rate_limit_bucket (
+    key text PRIMARY KEY,
+    count int NOT NULL,
+    expires_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_rate_limit_bucket_expires_at ON rate_limit_bucket (expires_at);
+
+CREATE UNLOGGED TABLE idempotency_key (
+    local_user_id int NOT NULL,
+    key text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (local_user_id, key)
+);
+
+CREATE INDEX idx_idempotency_key_published_at ON idempotency_key (published_at);
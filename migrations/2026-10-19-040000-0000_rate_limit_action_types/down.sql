@@ -0,0 +1,11 @@
+ALTER TABLE local_site_rate_limit
+    DROP COLUMN vote_max_requests,
+    DROP COLUMN vote_interval_seconds,
+    DROP COLUMN follow_max_requests,
+    DROP COLUMN follow_interval_seconds,
+    DROP COLUMN report_max_requests,
+    DROP COLUMN report_interval_seconds,
+    DROP COLUMN private_message_max_requests,
+    DROP COLUMN private_message_interval_seconds,
+    DROP COLUMN resolve_object_max_requests,
+    DROP COLUMN resolve_object_interval_seconds;
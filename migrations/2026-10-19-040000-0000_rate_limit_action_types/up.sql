@@ -0,0 +1,11 @@
+ALTER TABLE local_site_rate_limit
+    ADD COLUMN vote_max_requests int NOT NULL DEFAULT 120,
+    ADD COLUMN vote_interval_seconds int NOT NULL DEFAULT 60,
+    ADD COLUMN follow_max_requests int NOT NULL DEFAULT 30,
+    ADD COLUMN follow_interval_seconds int NOT NULL DEFAULT 600,
+    ADD COLUMN report_max_requests int NOT NULL DEFAULT 10,
+    ADD COLUMN report_interval_seconds int NOT NULL DEFAULT 600,
+    ADD COLUMN private_message_max_requests int NOT NULL DEFAULT 12,
+    ADD COLUMN private_message_interval_seconds int NOT NULL DEFAULT 600,
+    ADD COLUMN resolve_object_max_requests int NOT NULL DEFAULT 30,
+    ADD COLUMN resolve_object_interval_seconds int NOT NULL DEFAULT 600;
@@ -0,0 +1,8 @@
+ALTER TYPE modlog_kind
+    ADD VALUE 'AdminResetFederationQueue';
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'AdminSkipFederationActivity';
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'AdminMarkInstanceDead';
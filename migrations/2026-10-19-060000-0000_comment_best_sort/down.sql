@@ -0,0 +1,53 @@
+DROP INDEX idx_comment_best;
+
+ALTER TABLE comment
+    DROP COLUMN best_rank;
+
+UPDATE
+    local_user
+SET
+    default_comment_sort_type = 'Hot'
+WHERE
+    default_comment_sort_type = 'Best';
+
+UPDATE
+    local_site
+SET
+    default_comment_sort_type = 'Hot'
+WHERE
+    default_comment_sort_type = 'Best';
+
+-- reverting an enum value addition is not supported by postgres:
+-- https://www.postgresql.org/docs/current/datatype-enum.html#DATATYPE-ENUM-IMPLEMENTATION-DETAILS
+-- so this workaround is necessary
+ALTER TABLE local_user
+    ALTER default_comment_sort_type DROP DEFAULT;
+
+ALTER TABLE local_site
+    ALTER default_comment_sort_type DROP DEFAULT;
+
+CREATE TYPE comment_sort_type_enum_old AS ENUM (
+    'Hot',
+    'Top',
+    'New',
+    'Old',
+    'Controversial'
+);
+
+ALTER TABLE local_user
+    ALTER COLUMN default_comment_sort_type TYPE comment_sort_type_enum_old
+    USING default_comment_sort_type::text::comment_sort_type_enum_old;
+
+ALTER TABLE local_site
+    ALTER COLUMN default_comment_sort_type TYPE comment_sort_type_enum_old
+    USING default_comment_sort_type::text::comment_sort_type_enum_old;
+
+DROP TYPE comment_sort_type_enum;
+
+ALTER TYPE comment_sort_type_enum_old RENAME TO comment_sort_type_enum;
+
+ALTER TABLE local_user
+    ALTER default_comment_sort_type SET DEFAULT 'Hot';
+
+ALTER TABLE local_site
+    ALTER default_comment_sort_type SET DEFAULT 'Hot';
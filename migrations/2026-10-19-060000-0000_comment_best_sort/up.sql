@@ -0,0 +1,15 @@
+ALTER TYPE comment_sort_type_enum
+    ADD VALUE 'Best';
+
+ALTER TABLE comment
+    ADD COLUMN best_rank real NOT NULL DEFAULT 0;
+
+-- Same calculation as r.best_rank, which isn't available during migrations
+UPDATE
+    comment
+SET
+    best_rank = (upvotes + 0.8211872076 - 1.2815515655 * sqrt(upvotes::float * downvotes / (upvotes + downvotes) + 0.4105936038)) / (upvotes + downvotes + 1.6423744152)
+WHERE
+    upvotes + downvotes > 0;
+
+CREATE INDEX idx_comment_best ON comment USING btree (best_rank DESC);
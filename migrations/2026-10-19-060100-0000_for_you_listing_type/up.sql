@@ -0,0 +1,2 @@
+ALTER TYPE listing_type_enum
+    ADD VALUE 'ForYou';
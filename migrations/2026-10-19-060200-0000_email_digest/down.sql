@@ -0,0 +1,6 @@
+ALTER TABLE local_user
+    DROP COLUMN email_digest,
+    DROP COLUMN last_email_digest_at;
+
+DROP TYPE email_digest_frequency_enum;
+
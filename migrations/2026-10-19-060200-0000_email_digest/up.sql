@@ -0,0 +1,15 @@
+CREATE TYPE email_digest_frequency_enum AS enum (
+    'Never',
+    'Daily',
+    'Weekly'
+);
+
+ALTER TABLE local_user
+    ADD COLUMN email_digest email_digest_frequency_enum NOT NULL DEFAULT 'Never',
+    ADD COLUMN last_email_digest_at timestamptz;
+
+-- For the scheduled task which finds users with a due digest
+CREATE INDEX idx_local_user_email_digest ON local_user (email_digest)
+WHERE
+    email_digest != 'Never';
+
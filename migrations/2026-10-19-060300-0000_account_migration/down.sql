@@ -0,0 +1,4 @@
+ALTER TABLE person
+    DROP COLUMN also_known_as,
+    DROP COLUMN moved_to;
+
@@ -0,0 +1,5 @@
+-- Account migration via ActivityPub Move
+ALTER TABLE person
+    ADD COLUMN also_known_as text[] NOT NULL DEFAULT '{}',
+    ADD COLUMN moved_to text;
+
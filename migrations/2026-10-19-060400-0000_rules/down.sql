@@ -0,0 +1,8 @@
+ALTER TABLE post_report
+    DROP COLUMN rule_id;
+
+ALTER TABLE comment_report
+    DROP COLUMN rule_id;
+
+DROP TABLE rule;
+
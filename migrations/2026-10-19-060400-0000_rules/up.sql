@@ -0,0 +1,25 @@
+-- Structured rules, either for a single community or for the whole site (community_id is null).
+CREATE TABLE rule (
+    id serial PRIMARY KEY,
+    ap_id text NOT NULL UNIQUE,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    title text NOT NULL,
+    description text,
+    position int NOT NULL DEFAULT 0,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    deleted boolean NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX idx_rule_community ON rule (community_id);
+
+ALTER TABLE post_report
+    ADD COLUMN rule_id int REFERENCES rule ON UPDATE CASCADE ON DELETE SET NULL;
+
+ALTER TABLE comment_report
+    ADD COLUMN rule_id int REFERENCES rule ON UPDATE CASCADE ON DELETE SET NULL;
+
+CREATE INDEX idx_post_report_rule ON post_report (rule_id);
+
+CREATE INDEX idx_comment_report_rule ON comment_report (rule_id);
+
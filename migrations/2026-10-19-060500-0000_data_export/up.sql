@@ -0,0 +1,14 @@
+-- ZIP archives with all data of a user, which are built in the background and can be downloaded
+-- once finished. The archives are written to disk, only their path is stored here. Old archives
+-- are deleted by a scheduled task.
+CREATE TABLE data_export (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    finished_at timestamptz,
+    failed boolean NOT NULL DEFAULT FALSE,
+    archive_path text
+);
+
+CREATE INDEX idx_data_export_local_user ON data_export (local_user_id);
+
@@ -0,0 +1,4 @@
+DROP TABLE content_import_failure;
+
+DROP TABLE content_import;
+
@@ -0,0 +1,22 @@
+-- Imports of posts and comments from the archive of another account. The items are resolved in
+-- the background, and their progress and failures are tracked here.
+CREATE TABLE content_import (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    finished_at timestamptz,
+    total_items int NOT NULL,
+    processed_items int NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_content_import_local_user ON content_import (local_user_id);
+
+CREATE TABLE content_import_failure (
+    id serial PRIMARY KEY,
+    content_import_id int NOT NULL REFERENCES content_import ON UPDATE CASCADE ON DELETE CASCADE,
+    ap_id text NOT NULL,
+    reason text NOT NULL
+);
+
+CREATE INDEX idx_content_import_failure_import ON content_import_failure (content_import_id);
+
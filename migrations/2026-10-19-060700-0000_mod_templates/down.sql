@@ -0,0 +1,5 @@
+ALTER TABLE modlog
+    DROP COLUMN mod_template_id;
+
+DROP TABLE mod_template;
+
@@ -0,0 +1,17 @@
+-- Saved removal reasons and canned replies, which moderators can select when removing, locking
+-- or warning.
+CREATE TABLE mod_template (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    title text NOT NULL,
+    body text NOT NULL,
+    rule_id int REFERENCES rule ON UPDATE CASCADE ON DELETE SET NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_mod_template_community ON mod_template (community_id);
+
+ALTER TABLE modlog
+    ADD COLUMN mod_template_id int REFERENCES mod_template ON UPDATE CASCADE ON DELETE SET NULL;
+
@@ -0,0 +1,2 @@
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModAutomodPost';
@@ -0,0 +1 @@
+DROP INDEX idx_community_actions_community_followed;
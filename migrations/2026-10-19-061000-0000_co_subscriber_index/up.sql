@@ -0,0 +1,5 @@
+-- Used to find the most recent subscribers of communities, for community recommendations and the
+-- ForYou listing.
+CREATE INDEX idx_community_actions_community_followed ON community_actions (community_id, followed_at DESC)
+WHERE
+    followed_at IS NOT NULL;